@@ -16,9 +16,11 @@
 */
 
 use std::ffi::CString;
+use c166_core::error::DecodeError;
 use c166_core::reg::*;
 use c166_core::r2::*;
 use c166_core::instruction::*;
+use c166_core::sfr::RegisterMap;
 
 // Assume 20MHz
 const CLOCK_RATE : u32 = 20000000;
@@ -241,7 +243,7 @@ fn format_adcon(value: u16) -> String {
     strings.join("\n")
 }
 
-fn annotate_sfr_immed(an: *mut RAnal, pc: u64, values: &InstructionArguments) {
+fn annotate_sfr_immed(an: *mut RAnal, pc: u64, values: &InstructionArguments, registers: &'static RegisterMap) {
     let mut strings : Vec<String> = Vec::new();
 
     let op1: &Operand = values.op1.as_ref().unwrap();
@@ -285,7 +287,15 @@ fn annotate_sfr_immed(an: *mut RAnal, pc: u64, values: &InstructionArguments) {
 
             strings.push(format!("WDTREL = Initial timer value 0x{:04X}", value & 0xFF00));
         }
-        _ => {}
+        _ => {
+            // Otherwise name whatever fields the register description has
+            let info = sfr.to_phys16().ok()
+                .and_then(|address| registers.by_address(address));
+
+            if let Some(info) = info {
+                strings.extend(info.describe(value));
+            }
+        }
     }
 
     if !strings.is_empty() {
@@ -293,19 +303,59 @@ fn annotate_sfr_immed(an: *mut RAnal, pc: u64, values: &InstructionArguments) {
     }
 }
 
-pub fn annotate_sfr_ops(isn: &Instruction, values: &InstructionArguments, an: *mut RAnal, pc: u64) {
-    let op_flags : u32 = isn.r2_op_type.uint_value();
-    let op_type : _RAnalOpType = _RAnalOpType(0x000000FF & op_flags);
-
-    match op_type {
-        _RAnalOpType::R_ANAL_OP_TYPE_MOV => {
+pub fn annotate_sfr_ops(isn: &Instruction, values: &InstructionArguments, registers: &'static RegisterMap, an: *mut RAnal, pc: u64) {
+    match isn.op_type.kind {
+        OpKind::Mov => {
             let op1 = isn.op1.unwrap();
             let op2 = isn.op2.unwrap();
             if (op1 == OperandType::WordRegister (1)|| op1 == OperandType::ByteRegister(0)) &&
                 (op2 == OperandType::ImmediateData8 || op2 == OperandType::ImmediateData16) {
-                    annotate_sfr_immed(an, pc, &values);
+                    annotate_sfr_immed(an, pc, &values, registers);
             };
         },
         _ => {}
     }
 }
+
+// mem operands that land on an SFR/ESFR get the register's name, others their 24-bit
+// address.  mem_addr is where the decoder's memory model put the operand, registers the map it
+// named the operands from.  Without a mem_addr the DPPs aren't known and neither is what the
+// operand lands on.
+pub fn annotate_mem_ops(isn: &Instruction, values: &InstructionArguments, mem_addr: Option<u32>, registers: &'static RegisterMap, an: *mut RAnal, pc: u64) {
+    if !isn.signature().contains(&Some(OperandType::DirectMemory16)) {
+        return;
+    }
+
+    let mut strings : Vec<String> = Vec::new();
+
+    for op in [values.op1, values.op2, values.op3].iter() {
+        if let Some(Operand::Mem16(mem)) = op {
+            let address: u16 = match mem_addr {
+                Some(mem_addr) if mem_addr > 0xFFFF => {
+                    strings.push(format!("{:04X}h = {:06X}h", mem, mem_addr));
+                    continue;
+                },
+                Some(mem_addr) => mem_addr as u16,
+                None => continue
+            };
+
+            match Reg::from_address(address, registers) {
+                Ok(Reg::SFR(_)) |
+                Ok(Reg::ESFR(_)) |
+                Err(_) => {},
+                Ok(reg) => strings.push(format!("{:04X}h = {}", address, reg)),
+            }
+        }
+    }
+
+    if !strings.is_empty() {
+        add_comments_at_address(an, pc, &strings);
+    }
+}
+
+// Permissive decoding lets instructions with odd reserved fields through, which is worth a look
+pub fn annotate_reserved(reserved: &Option<DecodeError>, an: *mut RAnal, pc: u64) {
+    if let Some(err) = reserved {
+        add_comments_at_address(an, pc, &vec![format!("Reserved field: {}", err)]);
+    }
+}
@@ -20,15 +20,18 @@
 #[macro_use]
 extern crate runtime_fmt;
 
+#[macro_use]
+extern crate lazy_static;
+
 extern crate c166_core;
 
 use std::os::raw::c_void;
 use std::os::raw::c_char;
-use std::convert::TryFrom;
+use std::sync::Mutex;
 
 use c166_core::r2::*;
 use c166_core::instruction::*;
-use c166_core::encoding::*;
+use c166_core::{DecodeContext, FlowKind};
 
 mod annotations;
 use annotations::*;
@@ -49,6 +52,11 @@ macro_rules! cstr {
   );
 }
 
+lazy_static! {
+    // r2 analyses an instruction at a time, this carries EXT* windows across them
+    static ref CONTEXT: Mutex<DecodeContext> = Mutex::new(DecodeContext::new());
+}
+
 fn condition_to_r2(condition: &OpCondition) -> _RAnalCond {
     match condition {
         OpCondition::cc_UC  => _RAnalCond::R_ANAL_COND_AL,
@@ -149,6 +157,19 @@ extern "C" fn c166_set_reg_profile(a: *mut RAnal) -> i32 {
         gpr     s0tic       .16     86      0\n\
 
         gpr     syscon      .16     88      0\n\
+
+        gpr     idx0        .16     90      0\n\
+        gpr     idx1        .16     92      0\n\
+        gpr     mah         .16     94      0\n\
+        gpr     mal         .16     96      0\n\
+        gpr     mas         .16     98      0\n\
+        gpr     mcw         .16     100     0\n\
+        gpr     mrw         .16     102     0\n\
+        gpr     msw         .16     104     0\n\
+        gpr     qr0         .16     106     0\n\
+        gpr     qr1         .16     108     0\n\
+        gpr     qx0         .16     110     0\n\
+        gpr     qx1         .16     112     0\n\
     ");
 
     unsafe {
@@ -169,83 +190,72 @@ extern "C" fn c166_archinfo(_anal: *mut RAnal, query: i32) -> i32 {
     }
 }
 
-extern "C" fn c166_op(an: *mut RAnal, raw_op: *mut RAnalOp, pc: u64, buf: *const u8, _len: i32) -> i32 {
+extern "C" fn c166_op(an: *mut RAnal, raw_op: *mut RAnalOp, pc: u64, buf: *const u8, len: i32) -> i32 {
     let out_op : &mut RAnalOp;
     let bytes : &[u8];
+    let cpu;
 
     unsafe {
         out_op = &mut (*raw_op);
-        // Gross.
-        bytes = std::slice::from_raw_parts(buf as *const u8, 4 as usize);
+        cpu = cpu_model((*an).cpu);
+        bytes = match len > 0 {
+            true => std::slice::from_raw_parts(buf as *const u8, len as usize),
+            false => &[]
+        };
     }
 
-    match Instruction::try_from(bytes) {
-        Ok(isn) => {
-            let encoding = Encoding::from(&isn.encoding);
+    let result = CONTEXT.lock().unwrap().decode(bytes, pc as u32, cpu);
+
+    match result {
+        Ok(decoded) => {
+            let isn = &decoded.instruction;
 
-            out_op.id = bytes[0] as i32;
+            out_op.id = isn.id as i32;
             out_op.nopcode = 1;
             out_op.family = R_ANAL_OP_FAMILY_CPU; // TODO: set privileged as appropriate
-            out_op.type_ = isn.r2_op_type.uint_value();
-            out_op.size = encoding.length;
+            out_op.type_ = _RAnalOpType::from(&isn.op_type).uint_value();
+            out_op.size = decoded.length as i32;
 
             out_op.addr = pc;
 
-            let op_type = _RAnalOpType(0x000000FF & out_op.type_);
+            if let Some(mem_addr) = decoded.mem_addr {
+                out_op.ptr = mem_addr as i64;
+            }
 
-            match op_type {
-                _RAnalOpType::R_ANAL_OP_TYPE_RET => {
+            // Minimum times, external bus accesses aren't known here
+            if let Some(times) = decoded.state_times(cpu) {
+                out_op.cycles = times.taken as i32;
+                out_op.failcycles = times.states as i32;
+            }
+
+            match decoded.flow {
+                FlowKind::Return => {
                     out_op.eob = true;
                 },
-                _RAnalOpType::R_ANAL_OP_TYPE_JMP | _RAnalOpType::R_ANAL_OP_TYPE_CALL => {
+                FlowKind::Jump | FlowKind::ConditionalJump | FlowKind::Call | FlowKind::Trap | FlowKind::Indirect => {
                     // Always go to the next instruction on failure
                     out_op.fail = pc + (out_op.size as u64);
 
-                    match (encoding.decode)(&isn, bytes) {
-                        Ok(values) => {
-                            out_op.cond = match values.op1.as_ref().unwrap() {
-                                &Operand::Condition(ref condition) => condition_to_r2(condition).uint_value() as i32,
-                                _ => 0
-                            };
-
-                            if out_op.cond == 0 {
-                                out_op.eob = true;
-                            }
-
-                            match (isn.op2, values.op2) {
-                                (Some(OperandType::DirectCaddr16), Some(Operand::Direct(d, _width))) => {
-                                    match (isn.op1.as_ref().unwrap(), values.op1.as_ref().unwrap()) {
-                                        (OperandType::DirectSegment8, Operand::Direct(seg, _width)) => {
-                                            // grab from op1 Some(seg) => (0x10000 * seg as u64) + (address as u64),
-                                            out_op.jump = (0x10000 * *seg as u64) + (d as u64)
-                                        },
-                                        _ => out_op.jump = d as u64
-                                    };
-                                },
-                                (Some(OperandType::DirectRelative8S), Some(Operand::Direct(d, _width))) => {
-                                    // TODO: Grab size of next op instead of hardcoding it to 2
-                                    out_op.jump = pc + ( ((d as u64)+1) * 2 );
-                                }
-                                _ => {}
-                            };
-                        },
-                        Err(_) => {
-                            out_op.id = -1;
-                                out_op.size = -1;
-                            out_op.type_ = _RAnalOpType::R_ANAL_OP_TYPE_ILL.uint_value();
-                        }
+                    out_op.cond = match decoded.arguments.op1 {
+                        Some(Operand::Condition(ref condition)) => condition_to_r2(condition).uint_value() as i32,
+                        _ => 0
+                    };
+
+                    if out_op.cond == 0 {
+                        out_op.eob = true;
                     }
-                },
-                _ => {}
-            }
 
-            match (encoding.decode)(&isn, bytes) {
-                Ok(values) => {
-                    annotate_sfr_ops(&isn, &values, an, pc);
-                    process_esil(&isn, &values, raw_op);
+                    if let Some(target) = decoded.targets.first() {
+                        out_op.jump = *target as u64;
+                    }
                 },
                 _ => {}
             }
+
+            annotate_sfr_ops(isn, &decoded.arguments, decoded.registers, an, pc);
+            annotate_mem_ops(isn, &decoded.arguments, decoded.mem_addr, decoded.registers, an, pc);
+            annotate_reserved(&decoded.reserved, an, pc);
+            process_esil(isn, &decoded.arguments, raw_op);
         },
         Err(_) => {
             out_op.id = -1;
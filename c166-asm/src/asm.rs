@@ -17,9 +17,11 @@
 
 use std::os::raw::c_char;
 use std::ffi::CStr;
-use std::str;
 
 use c166_core::r2::*;
+use c166_core::assemble_listing;
+use c166_core::diagnostic::{render, Diagnostic};
+use c166_core::error::AsmError;
 use c166_core::parser::*;
 
 lazy_static! {
@@ -30,44 +32,45 @@ lazy_static! {
     };
 }
 
-pub extern "C" fn c166_assemble(_asm: *mut RAsm, raw_op: *mut RAsmOp, buf: *const c_char) -> i32 {
+pub extern "C" fn c166_assemble(asm: *mut RAsm, raw_op: *mut RAsmOp, buf: *const c_char) -> i32 {
     let op_lut: &OpLookUpTable = &OP_LUT;
+    let cpu = unsafe { cpu_model((*asm).cpu) };
 
     let c_str: &CStr = unsafe { CStr::from_ptr(buf) };
-    let init_data: &[u8] = c_str.to_bytes_with_nul();
-    let data: &str = str::from_utf8(init_data).unwrap();
     let out_op : &mut RAsmOp = unsafe {&mut (*raw_op)};
 
+    let pc = unsafe { (*asm).pc as u32 };
+
     out_op.size = 0;
-        match asm_lines(data) {
-        Ok((_remainder, ops)) => {
-            for op in ops {
-                eprintln!("");
-                eprintln!("OP: {:?}", op);
 
-                match operation_to_bytes(&op, &op_lut) {
-                    Ok(out_bytes) => {
-                        eprintln!("OKAY: {:X?}", out_bytes);
-                        for byte in out_bytes.iter() {
-                            if out_op.size < R_ASM_BUFSIZE as i32 {
-                                out_op.buf[out_op.size as usize] = *byte;
-                                out_op.size += 1;
-                            } else {
-                                panic!("Not enough room, max len is 0x{:X}", R_ASM_BUFSIZE);
-                            }
-                        }
-                    },
-                    Err(msg) => {
-                        eprintln!("ASM: {:?}", data);
-                        eprintln!("ERROR: {:?}", msg);
-                    },
-                }
-            }
-        },
+    let data: &str = match c_str.to_str() {
+        Ok(data) => data,
         Err(e) => {
-            eprintln!("ERR: {:?}", e)
+            let diagnostic = Diagnostic::new(AsmError::NotUtf8 { reason: e.to_string() }, None);
+            eprint!("{}", diagnostic.render(""));
+            return out_op.size;
         }
     };
 
+    let result = assemble_listing(data, pc, &op_lut, cpu).and_then(|assembly| {
+        let length = assembly.image(0).len();
+        match assembly.origin() {
+            // rasm2 puts the bytes at pc, an ORG elsewhere can't be honoured
+            Some(origin) if origin != pc => Err(vec![Diagnostic::new(AsmError::Misplaced { origin: origin, pc: pc }, None)]),
+            _ if length > R_ASM_BUFSIZE as usize => Err(vec![Diagnostic::new(AsmError::TooLong { length: length, max: R_ASM_BUFSIZE as usize }, None)]),
+            _ => Ok(assembly)
+        }
+    });
+
+    match result {
+        Ok(assembly) => {
+            for byte in assembly.image(0).iter() {
+                out_op.buf[out_op.size as usize] = *byte;
+                out_op.size += 1;
+            }
+        },
+        Err(diagnostics) => eprint!("{}", render(&diagnostics, data))
+    };
+
     out_op.size
 }
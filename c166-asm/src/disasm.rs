@@ -17,56 +17,55 @@
 
 use std::os::raw::c_char;
 use std::{ptr,slice};
-use std::convert::TryFrom;
+use std::sync::Mutex;
 
 use c166_core::r2::*;
-use c166_core::instruction::Instruction;
-use c166_core::encoding::Encoding;
-use c166_core::opformat::*;
+use c166_core::DecodeContext;
 
-pub extern "C" fn c166_disassemble(raw_asm: *mut RAsm, raw_op: *mut RAsmOp, buf: *const u8, _len: i32) -> i32 {
+lazy_static! {
+    // r2 disassembles an instruction at a time, this carries EXT* windows across them
+    static ref CONTEXT: Mutex<DecodeContext> = Mutex::new(DecodeContext::new());
+}
+
+pub extern "C" fn c166_disassemble(raw_asm: *mut RAsm, raw_op: *mut RAsmOp, buf: *const u8, len: i32) -> i32 {
     let asm : &RAsm;
     let out_op : &mut RAsmOp;
     let bytes;
+    let cpu;
+    let options;
 
     unsafe {
         asm = &(*raw_asm);
+        cpu = cpu_model(asm.cpu);
+        options = format_options(asm.features);
         out_op = &mut (*raw_op);
 
-        bytes = slice::from_raw_parts(buf as *const u8, 4 as usize);
+        bytes = match len > 0 {
+            true => slice::from_raw_parts(buf as *const u8, len as usize),
+            false => &[]
+        };
     }
 
-    match Instruction::try_from(bytes) {
-        Ok(op) => {
-            let encoding = Encoding::from(&op.encoding);
+    let result = CONTEXT.lock().unwrap().decode(bytes, asm.pc as u32, cpu);
 
-            // https://github.com/rust-lang/rust/issues/18343
-            match (encoding.decode)(&op, bytes) {
-                Ok(values) => {
-                    if asm.pc > <u32>::max_value() as u64 {
-                        out_op.size = -1;
-                        out_op.payload = 0;
-                        out_op.buf_asm[0] = 0;
-                    } else {
-                        let desc = format_op(&op, &values, asm.pc as u32);
+    match result {
+        Ok(decoded) => {
+            if asm.pc > <u32>::max_value() as u64 {
+                out_op.size = -1;
+                out_op.payload = 0;
+                out_op.buf_asm[0] = 0;
+            } else {
+                let desc = decoded.format(&options);
 
-                        out_op.size = encoding.length;
-                        out_op.payload = 0;
-                        out_op.buf_asm[desc.len()] = 0;
+                out_op.size = decoded.length as i32;
+                out_op.payload = 0;
+                out_op.buf_asm[desc.len()] = 0;
 
-                        unsafe {
-                            ptr::copy(desc.as_bytes() as *const [u8] as *const c_char, &mut out_op.buf_asm as *mut [c_char] as *mut c_char, desc.len());
-                        }
-                    }
-                    out_op.size
-                },
-                Err(_) => {
-                    out_op.size = -1;
-                    out_op.payload = 0;
-                    out_op.buf_asm[0] = 0;
-                    out_op.size
+                unsafe {
+                    ptr::copy(desc.as_bytes() as *const [u8] as *const c_char, &mut out_op.buf_asm as *mut [c_char] as *mut c_char, desc.len());
                 }
             }
+            out_op.size
         },
         Err(_) => {
             out_op.size = -1;
@@ -48,7 +48,7 @@ const C166_ASM_PLUGIN: RAsmPlugin = RAsmPlugin {
     version:        cstr!(env!("CARGO_PKG_VERSION")),
     license:        cstr!("GPL3"),
     user:           ptr::null_mut(),
-    cpus:           EMPTY_STRING,
+    cpus:           cstr!("80c166,c167cr,c165,st10,xc16x"), // CpuModel::name()
     desc:           cstr!("c166 assembler plugin"),
     bits:           16,
     endian:         0,
@@ -59,7 +59,7 @@ const C166_ASM_PLUGIN: RAsmPlugin = RAsmPlugin {
     modify:         None,
     set_subarch:    None,
     mnemonics:      Some(mnemonics::c166_mnemonic_by_id),
-    features:       EMPTY_STRING,
+    features:       cstr!("r2,keil,tasking,0x,upper,lower,nohash,nocc"), // FormatOptions::from_features()
 };
 
 #[no_mangle]
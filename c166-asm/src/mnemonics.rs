@@ -18,19 +18,13 @@
 use std::ffi::CString;
 use std::ptr;
 use std::os::raw::c_char;
-use std::convert::TryFrom;
 
 use c166_core::r2::*;
-use c166_core::instruction::Instruction;
+use c166_core::opcodes::{instructions, lookup};
 
 pub extern "C" fn c166_mnemonic_by_id(_raw_asm: *mut RAsm, op_id: i32, is_json: bool) -> *mut c_char {
     if op_id == -1 {
-        let mut opcodes : Vec<&str> = Vec::with_capacity(0xFF);
-        for n in 0x00..=0xFF {
-            if let Ok(op) = Instruction::try_from(n as u8) {
-                opcodes.push(op.mnemonic);
-            }
-        }
+        let mut opcodes : Vec<&str> = instructions().map(|op| op.mnemonic).collect();
 
         opcodes.push(""); // Gotta have that trailing newline
 
@@ -40,12 +34,12 @@ pub extern "C" fn c166_mnemonic_by_id(_raw_asm: *mut RAsm, op_id: i32, is_json:
         return unsafe { r_str_new (c_string.as_ptr()) }
     }
 
-    if op_id > <u8>::max_value() as i32 {
+    if op_id < 0 || op_id > <u8>::max_value() as i32 {
         return ptr::null_mut();
     }
 
-    match Instruction::try_from(op_id as u8) {
-        Ok(op) => {
+    match lookup(op_id as u8) {
+        Some(op) => {
             // r2 wants a newline terminated string... :/
             let c_string = match is_json {
                 true => CString::new(format!("[\"{}\"]\n", op.mnemonic)).unwrap(),
@@ -54,7 +48,7 @@ pub extern "C" fn c166_mnemonic_by_id(_raw_asm: *mut RAsm, op_id: i32, is_json:
 
             return unsafe { r_str_new (c_string.as_ptr()) };
         },
-        Err(_) => {
+        None => {
             return ptr::null_mut();
         }
     }
@@ -15,12 +15,20 @@
     along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
 */
 
+#[cfg(feature = "r2")]
 extern crate bindgen;
+extern crate toml;
 
+use std::collections::HashSet;
 use std::env;
+use std::fmt::Write as FmtWrite;
+use std::fs;
 use std::path::Path;
 use std::path::PathBuf;
 
+use toml::value::{Table, Value};
+
+#[cfg(feature = "r2")]
 fn build_bindings(in_path: &PathBuf, out_path: &PathBuf) {
     bindgen::builder()
         .header(in_path.to_str().unwrap())
@@ -37,12 +45,328 @@ fn build_bindings(in_path: &PathBuf, out_path: &PathBuf) {
         .unwrap()
 }
 
+// Accessors for the ISA spec, a malformed spec should fail the build with a
+// message pointing at the offending entry.
+fn get<'a>(table: &'a Table, key: &str, context: &str) -> &'a Value {
+    match table.get(key) {
+        Some(value) => value,
+        None => panic!("{}: missing \"{}\"", context, key)
+    }
+}
+
+fn get_str<'a>(table: &'a Table, key: &str, context: &str) -> &'a str {
+    get(table, key, context).as_str().unwrap_or_else(|| panic!("{}: \"{}\" must be a string", context, key))
+}
+
+fn get_opt_str<'a>(table: &'a Table, key: &str, context: &str) -> Option<&'a str> {
+    table.get(key).map(|_| get_str(table, key, context))
+}
+
+fn get_int(table: &Table, key: &str, context: &str) -> i64 {
+    get(table, key, context).as_integer().unwrap_or_else(|| panic!("{}: \"{}\" must be an integer", context, key))
+}
+
+fn get_bool(table: &Table, key: &str, context: &str) -> bool {
+    get(table, key, context).as_bool().unwrap_or_else(|| panic!("{}: \"{}\" must be a boolean", context, key))
+}
+
+fn get_array<'a>(table: &'a Table, key: &str, context: &str) -> &'a [Value] {
+    match table.get(key) {
+        Some(value) => value.as_array().unwrap_or_else(|| panic!("{}: \"{}\" must be an array", context, key)),
+        None => &[]
+    }
+}
+
+fn get_strs<'a>(value: &'a Value, context: &str) -> Vec<&'a str> {
+    let values: &[Value] = value.as_array().unwrap_or_else(|| panic!("{}: expected an array of strings", context));
+    values.iter().map(|v| v.as_str().unwrap_or_else(|| panic!("{}: expected an array of strings", context))).collect()
+}
+
+fn as_table<'a>(value: &'a Value, context: &str) -> &'a Table {
+    value.as_table().unwrap_or_else(|| panic!("{}: expected a table", context))
+}
+
+fn comment(out: &mut String, indent: &str, text: &str) {
+    for line in text.lines() {
+        match line.is_empty() {
+            true => writeln!(out, "{}//", indent).unwrap(),
+            false => writeln!(out, "{}// {}", indent, line).unwrap()
+        }
+    }
+}
+
+// What a flag line says happens to the flag, anything other than the fixed phrases depends on the operands
+fn flag_effect(flag: &str) -> &'static str {
+    match &flag[3..] {
+        "Always cleared." => "Cleared",
+        "Always set." => "Set",
+        "Not affected." => "Unchanged",
+        _ => "Computed"
+    }
+}
+
+fn parse_bytes(bytes: &str, context: &str) -> Vec<u8> {
+    bytes.split_whitespace()
+        .map(|byte| u8::from_str_radix(byte, 16).unwrap_or_else(|_| panic!("{}: \"{}\" is not a hex byte", context, byte)))
+        .collect()
+}
+
+fn generate_encoding_types(encodings: &[Value]) -> (String, HashSet<String>) {
+    let mut names: HashSet<String> = HashSet::new();
+    let mut variants = String::new();
+    let mut formats = String::new();
+    let mut lengths = String::new();
+
+    for encoding in encodings {
+        let encoding = as_table(encoding, "encoding");
+        let name = get_str(encoding, "name", "encoding");
+        let context = format!("encoding {}", name);
+        let format = get_str(encoding, "format", &context);
+        let length = get_int(encoding, "length", &context);
+
+        if !names.insert(name.to_string()) {
+            panic!("{}: defined more than once", context);
+        }
+        if length != 2 && length != 4 {
+            panic!("{}: length must be 2 or 4", context);
+        }
+
+        writeln!(variants, "    {},", name).unwrap();
+        writeln!(formats, "            EncodingType::{} => \"{}\",", name, format).unwrap();
+        writeln!(lengths, "            EncodingType::{} => {},", name, length).unwrap();
+    }
+
+    let mut out = String::new();
+    writeln!(out, "#[allow(non_camel_case_types)]").unwrap();
+    writeln!(out, "#[derive(Clone, Copy, Debug, Eq, PartialEq)]").unwrap();
+    writeln!(out, "pub enum EncodingType {{\n{}}}\n", variants).unwrap();
+    writeln!(out, "impl EncodingType {{").unwrap();
+    writeln!(out, "    // Layout of the encoded bytes, as named in the manual").unwrap();
+    writeln!(out, "    pub fn format(&self) -> &'static str {{\n        match self {{\n{}        }}\n    }}\n", formats).unwrap();
+    writeln!(out, "    pub fn length(&self) -> usize {{\n        match self {{\n{}        }}\n    }}", lengths).unwrap();
+    writeln!(out, "}}").unwrap();
+
+    (out, names)
+}
+
+fn generate_instructions(instructions: &[Value], encodings: &HashSet<String>) -> (String, String) {
+    let mut opcodes: Vec<Option<u8>> = vec![None; 256];
+    let mut consts = String::new();
+    let mut tests = String::new();
+
+    for instruction in instructions {
+        let instruction = as_table(instruction, "instruction");
+        let name = get_str(instruction, "name", "instruction");
+        let summary = get_str(instruction, "summary", name);
+
+        writeln!(consts, "// {}: {}", name, summary).unwrap();
+        if let Some(description) = get_opt_str(instruction, "description", name) {
+            comment(&mut consts, "", description);
+        }
+        let mut flag_effects: Vec<&str> = vec!["Unchanged"; 5];
+        if let Some(flags) = instruction.get("flags") {
+            let flags = get_strs(flags, name);
+            let order: Vec<char> = flags.iter().filter_map(|flag| flag.chars().next()).collect();
+            if order != ['E', 'Z', 'V', 'C', 'N'] || flags.iter().any(|flag| flag.get(1..3) != Some(": ")) {
+                panic!("{}: flags must be listed as E, Z, V, C, N", name);
+            }
+            for flag in flags.iter() {
+                writeln!(consts, "// {}", flag).unwrap();
+            }
+            flag_effects = flags.iter().map(|flag| flag_effect(flag)).collect();
+        }
+        writeln!(consts).unwrap();
+        let flag_effects: Vec<String> = flag_effects.iter().map(|effect| format!("FlagEffect::{}", effect)).collect();
+
+        for opcode in get_array(instruction, "opcode", name) {
+            let opcode = as_table(opcode, name);
+            let id = get_int(opcode, "id", name);
+            if !(0..=0xFF).contains(&id) {
+                panic!("{}: opcode {:X}h is out of range", name, id);
+            }
+            let id = id as u8;
+            let context = format!("{} opcode {:02X}h", name, id);
+
+            if opcodes[id as usize].replace(id).is_some() {
+                panic!("{}: defined more than once", context);
+            }
+
+            let mnemonic = match get_opt_str(opcode, "mnemonic", &context) {
+                Some(mnemonic) => mnemonic.to_string(),
+                None => name.to_lowercase()
+            };
+            let encoding = get_str(opcode, "encoding", &context);
+            if !encodings.contains(encoding) {
+                panic!("{}: unknown encoding {}", context, encoding);
+            }
+            let operands = get_strs(get(opcode, "operands", &context), &context);
+            if operands.len() > 3 {
+                panic!("{}: at most three operands are allowed", context);
+            }
+            let kind = get_str(opcode, "kind", &context);
+            let reg = get_bool(opcode, "reg", &context);
+            let esil = get_opt_str(opcode, "esil", &context).unwrap_or("");
+            // Test vectors run against the default C167CR unless it lacks the opcode
+            let (cpus, test_cpu) = match opcode.get("cpus") {
+                Some(cpus) => {
+                    let cpus = get_strs(cpus, &context);
+                    let test_cpu = match cpus.contains(&"C167CR") {
+                        true => String::new(),
+                        false => format!("::cpu::CpuModel::{}, ", cpus.first().unwrap_or_else(|| panic!("{}: cpus is empty", context)))
+                    };
+                    let cpus: Vec<String> = cpus.iter().map(|cpu| format!("CpuModel::{}", cpu)).collect();
+                    (format!("&[{}]", cpus.join(", ")), test_cpu)
+                },
+                None => ("&CpuModel::ALL".to_string(), String::new())
+            };
+
+            writeln!(consts, "const OP_{:02X}: Instruction<'static> = Instruction {{", id).unwrap();
+            for form in get_array(opcode, "forms", &context) {
+                for line in get_strs(form, &context) {
+                    comment(&mut consts, "    ", line);
+                }
+            }
+            writeln!(consts, "    id: 0x{:02X},", id).unwrap();
+            writeln!(consts, "    mnemonic: {:?},", mnemonic).unwrap();
+            writeln!(consts, "    encoding: EncodingType::{},", encoding).unwrap();
+            for n in 0..3 {
+                match operands.get(n) {
+                    Some(operand) => writeln!(consts, "    op{}: Some(OperandType::{}),", n + 1, operand).unwrap(),
+                    None => writeln!(consts, "    op{}: None,", n + 1).unwrap()
+                }
+            }
+            writeln!(consts, "    op_type: OpType {{ kind: OpKind::{}, reg: {} }},", kind, reg).unwrap();
+            writeln!(consts, "    esil: {:?},", esil).unwrap();
+            writeln!(consts, "    flags: [{}],", flag_effects.join(", ")).unwrap();
+            writeln!(consts, "    cpus: {},", cpus).unwrap();
+            writeln!(consts, "}};\n").unwrap();
+
+            for (key, roundtrip) in [("tests", true), ("disasm_tests", false)].iter() {
+                for (n, test) in get_array(opcode, key, &context).iter().enumerate() {
+                    let test = get_strs(test, &context);
+                    if test.len() != 2 {
+                        panic!("{}: {} entries must be [bytes, asm]", context, key);
+                    }
+                    let bytes = parse_bytes(test[0], &context);
+                    if bytes.first() != Some(&id) {
+                        panic!("{}: test vector \"{}\" doesn't start with the opcode", context, test[0]);
+                    }
+                    let bytes: Vec<String> = bytes.iter().map(|byte| format!("0x{:02X}", byte)).collect();
+                    let bytes = bytes.join(", ");
+                    let suffix = match *roundtrip {
+                        true => format!("{}", n),
+                        false => format!("disasm_{}", n)
+                    };
+
+                    writeln!(tests, "#[test]\nfn op_{:02x}_{}() {{", id, suffix).unwrap();
+                    writeln!(tests, "    test_disasm_op!({}[{}], {:?});", test_cpu, bytes, test[1]).unwrap();
+                    if *roundtrip {
+                        writeln!(tests, "    test_asm_op!({}{:?}, [{}]);", test_cpu, test[1], bytes).unwrap();
+                    }
+                    writeln!(tests, "}}\n").unwrap();
+                }
+            }
+        }
+    }
+
+    let mut table = String::new();
+    writeln!(table, "pub static OPCODES: [Option<Instruction<'static>>; 256] = [").unwrap();
+    for opcode in opcodes {
+        match opcode {
+            Some(id) => writeln!(table, "    Some(OP_{:02X}),", id).unwrap(),
+            None => writeln!(table, "    None,").unwrap()
+        }
+    }
+    writeln!(table, "];\n").unwrap();
+    table.push_str(consts.trim_end());
+    table.push('\n');
+
+    (table, tests)
+}
+
+fn generate_mac_ops(mac_ops: &[Value]) -> String {
+    let mut ids: HashSet<u8> = HashSet::new();
+    let mut entries = String::new();
+
+    for mac_op in mac_ops {
+        let mac_op = as_table(mac_op, "mac");
+        let mnemonic = get_str(mac_op, "mnemonic", "mac");
+        let id = get_int(mac_op, "id", mnemonic);
+        if !(0..=0xFF).contains(&id) || !ids.insert(id as u8) {
+            panic!("{}: sub-op {:X}h is out of range or defined more than once", mnemonic, id);
+        }
+        let shape = match get_str(mac_op, "shape", mnemonic) {
+            "binary" => "Binary",
+            "shift" => "Shift",
+            "bare" => "Bare",
+            shape => panic!("{}: unknown shape {}", mnemonic, shape)
+        };
+        let kind = get_str(mac_op, "kind", mnemonic);
+        let summary = get_str(mac_op, "summary", mnemonic);
+
+        writeln!(entries, "    // {}", summary).unwrap();
+        writeln!(entries, "    MacOp {{ id: 0x{:02X}, mnemonic: {:?}, shape: MacShape::{}, kind: OpKind::{} }},", id, mnemonic, shape, kind).unwrap();
+    }
+
+    format!("pub static MAC_OPS: [MacOp; {}] = [\n{}];\n", ids.len(), entries)
+}
+
+fn generate_isa(manifest_path: &Path, out_path: &Path) {
+    let spec_path = manifest_path.join("isa").join("c166.toml");
+    println!("cargo:rerun-if-changed={}", spec_path.to_str().unwrap());
+
+    let spec: Value = fs::read_to_string(&spec_path)
+        .expect("Unable to read the ISA spec")
+        .parse()
+        .unwrap_or_else(|e| panic!("{}: {}", spec_path.to_str().unwrap(), e));
+    let spec = as_table(&spec, "spec");
+
+    let (encoding_types, encodings) = generate_encoding_types(get_array(spec, "encoding", "spec"));
+    let (opcodes, tests) = generate_instructions(get_array(spec, "instruction", "spec"), &encodings);
+    let mac_ops = generate_mac_ops(get_array(spec, "mac", "spec"));
+
+    fs::write(out_path.join("encoding_type.rs"), encoding_types).unwrap();
+    fs::write(out_path.join("opcodes.rs"), opcodes).unwrap();
+    fs::write(out_path.join("isa_tests.rs"), tests).unwrap();
+    fs::write(out_path.join("mac_ops.rs"), mac_ops).unwrap();
+}
+
+// Every description in sfr/ is embedded, the device is named after the file
+fn generate_sfr_devices(manifest_path: &Path, out_path: &Path) {
+    let sfr_path = manifest_path.join("sfr");
+    println!("cargo:rerun-if-changed={}", sfr_path.to_str().unwrap());
+
+    let mut files: Vec<PathBuf> = fs::read_dir(&sfr_path)
+        .expect("Unable to read the SFR descriptions")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().and_then(|extension| extension.to_str()) == Some("toml"))
+        .collect();
+    files.sort();
+
+    let mut devices = String::new();
+    for file in files.iter() {
+        println!("cargo:rerun-if-changed={}", file.to_str().unwrap());
+        let device = file.file_stem().unwrap().to_str().unwrap();
+        writeln!(devices, "    ({:?}, include_str!({:?})),", device, file.canonicalize().unwrap().to_str().unwrap()).unwrap();
+    }
+
+    let out = format!("pub static BUILTIN_DEVICES: [(&str, &str); {}] = [\n{}];\n", files.len(), devices);
+    fs::write(out_path.join("sfr_devices.rs"), out).unwrap();
+}
+
 fn main() {
     let manifest_path = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
     let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
 
-    let path_bindings_h = Path::new(&manifest_path).join("..").join("bindings.h").canonicalize().unwrap();
+    generate_isa(&manifest_path, &out_path);
+    generate_sfr_devices(&manifest_path, &out_path);
+
+    // Without the r2 feature there's nothing else to generate, the core is pure rust.
+    #[cfg(feature = "r2")]
+    {
+        let path_bindings_h = Path::new(&manifest_path).join("..").join("bindings.h").canonicalize().unwrap();
 
-    println!("cargo:rerun-if-changed={}", path_bindings_h.to_str().unwrap());
-    build_bindings(&path_bindings_h, &out_path);
+        println!("cargo:rerun-if-changed={}", path_bindings_h.to_str().unwrap());
+        build_bindings(&path_bindings_h, &out_path);
+    }
 }
@@ -0,0 +1,623 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+// Assembling a whole source rather than a single operation.  Labels take the address of the line
+// they're on and operands can name them before they're defined: a pass sizes what it can't resolve
+// yet with a placeholder and the next pass fills in the real value.  Passes repeat until no label
+// moves, which is normally the second one.
+//
+// Every section, and the code outside of any, has a location counter of its own.  A section
+// opened for the first time without an AT goes after everything assembled so far, ENDS goes back
+// to where the enclosing code left off.  DS and ALIGN leave gaps rather than filling them.
+//
+// Errors don't stop anything.  A line that doesn't parse is left out, a value that can't be
+// worked out is 0 and an operation that can't be encoded takes no room, so that everything
+// wrong with the source comes out of the one run.
+
+use std::collections::HashMap;
+
+use ::cpu::CpuModel;
+use ::diagnostic::{valid_forms, Diagnostic, Span};
+use ::error::{AsmError, EncodeError};
+use ::expr::{BinaryOp, Expr, ExprError};
+use ::instruction::Operand;
+use ::memory::byte_access;
+use ::parser::*;
+
+const MAX_PASSES: usize = 8;
+// The 16M the CPU can address
+const MAX_ADDRESS: u32 = 0xFFFFFF;
+
+// Signed so that a constant can be negative, values are range checked where they're used
+pub type SymbolTable = HashMap<String, i64>;
+
+// An error and the part of the source it's about
+type Located<'s> = (AsmError, &'s str);
+
+// Bytes placed from address onward
+#[derive(Clone, Debug, PartialEq)]
+pub struct Block {
+    // None outside of any SECTION
+    pub section: Option<String>,
+    pub address: u32,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Assembly {
+    // In the order they were assembled
+    pub blocks: Vec<Block>,
+    pub symbols: SymbolTable,
+}
+
+impl Assembly {
+    // The lowest address anything was assembled to
+    pub fn origin(&self) -> Option<u32> {
+        self.blocks.iter().map(|block| block.address).min()
+    }
+
+    // Everything from origin() on as one run of bytes with fill in the gaps, where blocks overlap
+    // the later one wins
+    pub fn image(&self, fill: u8) -> Vec<u8> {
+        let origin = match self.origin() {
+            Some(origin) => origin,
+            None => return Vec::new()
+        };
+
+        let end = self.blocks.iter().map(|block| block.address + block.bytes.len() as u32).max().unwrap_or(origin);
+        let mut image = vec![fill; (end - origin) as usize];
+        for block in self.blocks.iter() {
+            let start = (block.address - origin) as usize;
+            image[start..start + block.bytes.len()].copy_from_slice(&block.bytes);
+        }
+
+        image
+    }
+}
+
+struct Pass<'s> {
+    source: &'s str,
+    pc: u32,
+    // The furthest any location counter got, where new sections go
+    high: u32,
+    blocks: Vec<Block>,
+    symbols: SymbolTable,
+    // Names given with SET, the only ones that can be given another value
+    variables: Vec<&'s str>,
+    // The names that stand for an address, see address_names
+    addresses: Vec<&'s str>,
+    // Whether a name nothing defines can be hex without the leading 0 (FF30h), the way r2 lists it
+    bare_hex: bool,
+    // Innermost last, with the address the enclosing code was at
+    open: Vec<(&'s str, u32)>,
+    // Where each closed section left off
+    sections: HashMap<&'s str, u32>,
+    // Everything wrong in this pass, it went on with placeholders
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'s> Pass<'s> {
+    fn new(source: &'s str, addresses: Vec<&'s str>, bare_hex: bool, origin: u32) -> Pass<'s> {
+        Pass {
+            source: source,
+            pc: origin,
+            high: origin,
+            blocks: vec![Block { section: None, address: origin, bytes: Vec::new() }],
+            symbols: SymbolTable::new(),
+            variables: Vec::new(),
+            addresses: addresses,
+            bare_hex: bare_hex,
+            open: Vec::new(),
+            sections: HashMap::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn fail(&mut self, (error, at): Located<'s>, forms: Vec<String>) {
+        let mut diagnostic = Diagnostic::new(error, Some(Span::locate(self.source, at)));
+        diagnostic.forms = forms;
+        self.diagnostics.push(diagnostic);
+    }
+
+    fn lookup(&self, name: &str, known: &SymbolTable) -> Option<i64> {
+        match name {
+            "$" => Some(self.pc as i64),
+            // Labels further up already have this pass's address
+            _ => match self.symbols.get(name).or_else(|| known.get(name)) {
+                Some(&value) => Some(value),
+                None if self.bare_hex => suffixed_hex(name).map(|value| value as i64),
+                None => None
+            }
+        }
+    }
+
+    // Anything that can't be worked out yet is 0 for this pass, negative values are two's complement
+    fn value(&mut self, expr: &Expr<'s>, argument: &'s str, known: &SymbolTable, min: i64, max: u32, line: usize) -> u32 {
+        let value = evaluate(expr, argument, &|name: &str| self.lookup(name, known), min, max, line);
+
+        match value {
+            Ok(value) => value as u32,
+            Err(located) => {
+                self.fail(located, Vec::new());
+                0
+            }
+        }
+    }
+
+    // EQU and SET take any value, it's the operand or directive using it that has a range
+    fn constant(&mut self, expr: &Expr<'s>, argument: &'s str, known: &SymbolTable, line: usize) -> i64 {
+        let value = work_out(expr, argument, &|name: &str| self.lookup(name, known), line);
+
+        match value {
+            Ok(value) => value,
+            Err(located) => {
+                self.fail(located, Vec::new());
+                0
+            }
+        }
+    }
+
+    // A name given twice keeps its first value
+    fn define(&mut self, name: &'s str, value: i64, variable: bool, line: usize) {
+        let redefinable = variable && self.variables.contains(&name);
+        if self.symbols.contains_key(name) && !redefinable {
+            return self.fail((AsmError::DuplicateSymbol { line: line, name: name.to_string() }, name), Vec::new());
+        }
+
+        if variable {
+            self.variables.push(name);
+        }
+        self.symbols.insert(name.to_string(), value);
+    }
+
+    fn relocate(&mut self, address: u32) {
+        let section = self.open.last().map(|&(name, _)| name.to_string());
+        self.pc = address;
+
+        match self.blocks.last_mut() {
+            Some(ref mut block) if block.bytes.is_empty() => {
+                block.section = section;
+                block.address = address;
+            },
+            _ => self.blocks.push(Block { section: section, address: address, bytes: Vec::new() })
+        }
+    }
+
+    fn emit(&mut self, bytes: &[u8]) {
+        if let Some(block) = self.blocks.last_mut() {
+            block.bytes.extend_from_slice(bytes);
+        }
+
+        self.pc += bytes.len() as u32;
+        self.high = self.high.max(self.pc);
+    }
+
+    fn reserve(&mut self, size: u32) {
+        let end = self.pc + size;
+        self.relocate(end);
+        self.high = self.high.max(end);
+    }
+
+    fn align(&mut self, alignment: u32) {
+        if alignment > 1 && self.pc % alignment != 0 {
+            let size = alignment - self.pc % alignment;
+            self.reserve(size);
+        }
+    }
+
+    fn open_section(&mut self, name: &'s str, at: Option<u32>) {
+        let start = match (at, self.sections.get(name)) {
+            (Some(at), _) => at,
+            (None, Some(&pc)) => pc,
+            (None, None) => self.high
+        };
+
+        self.open.push((name, self.pc));
+        self.relocate(start);
+    }
+
+    // An ENDS that doesn't match leaves the sections as they are
+    fn close_section(&mut self, name: &'s str, line: usize) {
+        match self.open.last().cloned() {
+            Some((open, outer)) if open == name => {
+                self.open.pop();
+                self.sections.insert(name, self.pc);
+                self.relocate(outer);
+            },
+            _ => self.fail((AsmError::UnbalancedSection { line: line, name: name.to_string() }, name), Vec::new())
+        }
+    }
+
+    // DB and DW, size is 1 or 2.  Values can be signed or unsigned.
+    fn data(&mut self, statement: &Statement<'s>, items: &[DataItem<'s>], size: usize, known: &SymbolTable, line: usize) {
+        let (min, max) = match size {
+            1 => (-0x80, 0xFF),
+            _ => (-0x8000, 0xFFFF)
+        };
+
+        let mut bytes: Vec<u8> = Vec::new();
+        for (index, item) in items.iter().enumerate() {
+            let values: Vec<u32> = match *item {
+                DataItem::Value(ref expr) => vec![self.value(expr, argument(statement, index), known, min, max, line)],
+                DataItem::Text(text) => text.bytes().map(|byte| byte as u32).collect()
+            };
+
+            for value in values {
+                bytes.push(value as u8);
+                if size == 2 {
+                    bytes.push((value >> 8) as u8);
+                }
+            }
+        }
+
+        self.emit(&bytes);
+    }
+}
+
+// The statement's operand or argument at index as written, or all of it
+fn argument<'s>(statement: &Statement<'s>, index: usize) -> &'s str {
+    statement.arguments.get(index).cloned().unwrap_or(statement.text)
+}
+
+// The operation as written, without any label in front of it
+fn operation_text<'s>(statement: &Statement<'s>, operation: &AsmOperation<'s>) -> &'s str {
+    let offset = (operation.mnem.as_ptr() as usize).saturating_sub(statement.text.as_ptr() as usize);
+    &statement.text[offset.min(statement.text.len())..]
+}
+
+// source has to end in a line ending or a NUL, nothing after an END is read.  A line that doesn't
+// parse is an empty statement, so that the statements still go by line.
+fn parse_source(source: &str) -> (Vec<Statement>, Vec<Diagnostic>) {
+    let mut buf = source;
+    let mut statements: Vec<Statement> = Vec::new();
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+
+    while !buf.is_empty() {
+        match statement(buf) {
+            Ok((remainder, statement)) => {
+                let end = statement.directive == Some(Directive::End);
+                statements.push(statement);
+                buf = remainder;
+
+                if end {
+                    break;
+                }
+            },
+            Err(e) => {
+                // Where the line stopped making sense, up to its end
+                let rest = match e {
+                    ::nom::Err::Error(::nom::Context::Code(rest, _)) |
+                    ::nom::Err::Failure(::nom::Context::Code(rest, _)) => rest,
+                    _ => buf
+                };
+                let rest = rest.trim_start_matches(&[',', ' ', '\t'][..]);
+                let rest = rest[..rest.find(is_line_end).unwrap_or(rest.len())].trim_end();
+
+                let error = AsmError::Syntax { line: statements.len() + 1 };
+                diagnostics.push(Diagnostic::new(error, Some(Span::locate(source, rest))));
+
+                statements.push(Statement {
+                    label: None,
+                    operation: None,
+                    symbols: Vec::new(),
+                    directive: None,
+                    text: &buf[..0],
+                    arguments: Vec::new()
+                });
+                buf = &buf[buf.find('\n').map_or(buf.len(), |index| index + 1)..];
+            }
+        }
+    }
+
+    (statements, diagnostics)
+}
+
+fn work_out<'s, F>(expr: &Expr<'s>, argument: &'s str, lookup: &F, line: usize) -> Result<i64, Located<'s>>
+    where F: Fn(&str) -> Option<i64> {
+    expr.evaluate(lookup).map_err(|e| match e {
+        ExprError::UndefinedSymbol(name) => (AsmError::UndefinedSymbol { line: line, name: name.to_string() }, name),
+        ExprError::DivideByZero => (AsmError::DivideByZero { line: line }, argument),
+    })
+}
+
+fn evaluate<'s, F>(expr: &Expr<'s>, argument: &'s str, lookup: &F, min: i64, max: u32, line: usize) -> Result<i64, Located<'s>>
+    where F: Fn(&str) -> Option<i64> {
+    let value = work_out(expr, argument, lookup, line)?;
+
+    match value >= min && value <= max as i64 {
+        true => Ok(value),
+        false => Err((AsmError::OutOfRange { line: line, value: value, min: min, max: max }, argument))
+    }
+}
+
+// calls and jmps take the segment of their target as an operand of its own
+fn is_segment_slot(mnemonic: &str, index: usize) -> bool {
+    index == 0 && (mnemonic == "calls" || mnemonic == "jmps")
+}
+
+// Whether the expression is an address with a segment of its own, rather than a number that as an
+// address is an offset into pc's segment.  addresses are the names that stand for one.
+fn carries_segment(expr: &Expr, addresses: &[&str]) -> bool {
+    match *expr {
+        Expr::Symbol(name) => name == "$" || addresses.contains(&name),
+        Expr::Binary(BinaryOp::Add, ref lhs, ref rhs) => carries_segment(lhs, addresses) || carries_segment(rhs, addresses),
+        // The distance between two addresses is a number
+        Expr::Binary(BinaryOp::Sub, ref lhs, ref rhs) => carries_segment(lhs, addresses) && !carries_segment(rhs, addresses),
+        _ => false
+    }
+}
+
+// Labels, and the EQU and SET names given an address.  Nothing here depends on the order of the
+// source so that names used before they're defined count as well.
+fn address_names<'s>(statements: &[Statement<'s>]) -> Vec<&'s str> {
+    let mut names: Vec<&'s str> = Vec::new();
+
+    loop {
+        let count = names.len();
+        for statement in statements.iter() {
+            let name = match (statement.label, statement.directive.as_ref()) {
+                (Some(name), Some(Directive::Equ(value))) |
+                (Some(name), Some(Directive::Set(value))) if carries_segment(value, &names) => name,
+                (Some(_), Some(Directive::Equ(_))) |
+                (Some(_), Some(Directive::Set(_))) |
+                (Some(_), Some(Directive::Section(_))) |
+                (Some(_), Some(Directive::Ends)) |
+                (None, _) => continue,
+                (Some(name), _) => name
+            };
+
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        if names.len() == count {
+            return names;
+        }
+    }
+}
+
+// The operation with its expressions worked out.  Placeholders stand in for all of them, they
+// are chosen so that any encoding taking the operand can hold them.  How far the value can go
+// is down to the slot, the encoder checks the narrower ones, and whether a branch target is in
+// pc's segment.
+fn resolve<'s, F>(statement: &Statement<'s>, operation: &AsmOperation<'s>, lookup: F, addresses: &[&str], pc: u32, line: usize, placeholders: bool) -> Result<(AsmOperation<'s>, AsmContext), Located<'s>>
+    where F: Fn(&str) -> Option<i64> {
+    let mut operands = operation.operands.clone();
+    let mut context = AsmContext { pc: pc, bank: None, segments: [None; 3] };
+    // calls and jmps name the segment themselves
+    let segmented = is_segment_slot(operation.mnem, 0);
+
+    for &(index, ref symbolic) in statement.symbols.iter() {
+        let segment_slot = is_segment_slot(operation.mnem, index);
+        let argument = argument(statement, index);
+
+        operands[index] = match (symbolic, placeholders) {
+            (Symbolic::Address(_), true) if segment_slot => Operand::Mem16((pc >> 16) as u16),
+            // A relative branch to itself is always in range
+            (Symbolic::Address(_), true) => Operand::Mem16(pc as u16),
+            // An address stands for its segment there, a number is the segment
+            (Symbolic::Address(expr), false) if segment_slot => match carries_segment(expr, addresses) {
+                true => Operand::Mem16((evaluate(expr, argument, &lookup, 0, MAX_ADDRESS, line)? >> 16) as u16),
+                false => Operand::Mem16(evaluate(expr, argument, &lookup, 0, 0xFF, line)? as u16)
+            },
+            (Symbolic::Address(expr), false) => {
+                let address = evaluate(expr, argument, &lookup, 0, MAX_ADDRESS, line)? as u32;
+                let whole = carries_segment(expr, addresses) || address > 0xFFFF;
+                if let (true, false, Some(segment)) = (whole, segmented, context.segments.get_mut(index)) {
+                    *segment = Some((address >> 16) as u8);
+                }
+
+                Operand::Mem16(address as u16)
+            },
+            (Symbolic::Immediate(_), true) => Operand::Immediate(0, 0),
+            // A negative immediate is sign extended to the operand's width, which picks data8 or
+            // data16.  data3 and data4 are zero extended so they never take one.
+            (Symbolic::Immediate(expr), false) => {
+                let immediate = match byte_access(operation.mnem, index) {
+                    true => evaluate(expr, argument, &lookup, -0x80, 0xFF, line).map(|value| value as u8 as u16),
+                    false => evaluate(expr, argument, &lookup, -0x8000, 0xFFFF, line).map(|value| value as u16)
+                };
+                Operand::Immediate(immediate?, 0)
+            },
+            (Symbolic::Offset(expr), _) => {
+                let offset = match placeholders {
+                    true => 0,
+                    false => evaluate(expr, argument, &lookup, -0x8000, 0xFFFF, line)? as u16
+                };
+                match operands[index] {
+                    Operand::IndirectAndImmediate(reg, _) => Operand::IndirectAndImmediate(reg, offset),
+                    operand => operand
+                }
+            },
+        };
+    }
+
+    let resolved = AsmOperation {
+        mnem: operation.mnem,
+        operands: operands
+    };
+
+    Ok((resolved, context))
+}
+
+// The operand an encoding error is about where it names one, otherwise the whole operation
+fn encode_error<'s>(statement: &Statement<'s>, operation: &AsmOperation<'s>, error: EncodeError, line: usize) -> Located<'s> {
+    let operand = match error {
+        EncodeError::UnknownMnemonic { .. } => return (AsmError::Encode { line: line, error: error }, operation.mnem),
+        EncodeError::MissingOperand { operand, .. } |
+        EncodeError::InvalidOperand { operand, .. } |
+        EncodeError::InvalidRegister { operand, .. } |
+        EncodeError::OutOfRange { operand, .. } |
+        EncodeError::UnsupportedRegister { operand, .. } => (operand as usize).checked_sub(1),
+        // The target is the last operand of every relative branch
+        EncodeError::UnreachableTarget { .. } => statement.arguments.len().checked_sub(1),
+        _ => None
+    };
+
+    let at = operand.and_then(|index| statement.arguments.get(index).cloned())
+        .unwrap_or_else(|| operation_text(statement, operation));
+
+    (AsmError::Encode { line: line, error: error }, at)
+}
+
+fn encode<'s>(pass: &mut Pass<'s>, statement: &Statement<'s>, operation: &AsmOperation<'s>, known: &SymbolTable, op_lut: &OpLookUpTable, cpu: CpuModel, line: usize) {
+    let pc = pass.pc;
+
+    let (encoded, failure) = {
+        let addresses = &pass.addresses;
+        let lookup = |name: &str| pass.lookup(name, known);
+        let encode_with = |placeholders: bool| {
+            resolve(statement, operation, lookup, addresses, pc, line, placeholders).and_then(|(resolved, context)| {
+                operation_to_bytes_in_context(&resolved, op_lut, cpu, &context).map_err(|e| encode_error(statement, operation, e, line))
+            })
+        };
+
+        match encode_with(false) {
+            Ok(encoded) => (encoded, None),
+            // When even the placeholders don't encode no later pass will do any better, and the
+            // operation takes no room
+            Err(located) => (encode_with(true).unwrap_or_default(), Some(located))
+        }
+    };
+
+    if let Some(located) = failure {
+        let forms = match located.0 {
+            AsmError::Encode { error: EncodeError::NoMatchingEncoding { ref mnemonic }, .. } => valid_forms(mnemonic, cpu),
+            _ => Vec::new()
+        };
+        pass.fail(located, forms);
+    }
+    pass.emit(&encoded);
+}
+
+// known holds the symbols as the previous pass left them
+fn run_pass<'s>(source: &'s str, statements: &[Statement<'s>], bare_hex: bool, origin: u32, known: &SymbolTable, op_lut: &OpLookUpTable, cpu: CpuModel) -> Pass<'s> {
+    let mut pass = Pass::new(source, address_names(statements), bare_hex, origin);
+
+    for (index, statement) in statements.iter().enumerate() {
+        let line = index + 1;
+        let directive = statement.directive.as_ref();
+        let first = argument(statement, 0);
+
+        // A label is the address of its line, except for the directives it names
+        match (statement.label, directive) {
+            (Some(name), Some(Directive::Equ(value))) => {
+                let value = pass.constant(value, first, known, line);
+                pass.define(name, value, false, line);
+                continue;
+            },
+            (Some(name), Some(Directive::Set(value))) => {
+                let value = pass.constant(value, first, known, line);
+                pass.define(name, value, true, line);
+                continue;
+            },
+            (Some(name), Some(Directive::Section(at))) => {
+                let at = at.as_ref().map(|at| pass.value(at, first, known, 0, MAX_ADDRESS, line));
+                pass.open_section(name, at);
+                continue;
+            },
+            (Some(name), Some(Directive::Ends)) => {
+                pass.close_section(name, line);
+                continue;
+            },
+            (Some(label), _) => {
+                let pc = pass.pc as i64;
+                pass.define(label, pc, false, line);
+            },
+            (None, _) => {}
+        }
+
+        match directive {
+            Some(Directive::Org(address)) => {
+                let address = pass.value(address, first, known, 0, MAX_ADDRESS, line);
+                pass.relocate(address);
+            },
+            Some(Directive::Db(items)) => pass.data(statement, items, 1, known, line),
+            Some(Directive::Dw(items)) => pass.data(statement, items, 2, known, line),
+            Some(Directive::Ds(size)) => {
+                let size = pass.value(size, first, known, 0, MAX_ADDRESS, line);
+                pass.reserve(size);
+            },
+            Some(Directive::Align(alignment)) => {
+                let alignment = pass.value(alignment, first, known, 0, MAX_ADDRESS, line);
+                pass.align(alignment);
+            },
+            Some(Directive::Even) => pass.align(2),
+            Some(Directive::End) => break,
+            // The named ones, which the grammar only allows with a name
+            Some(_) => {},
+            None => {}
+        }
+
+        if let Some(ref operation) = statement.operation {
+            encode(&mut pass, statement, operation, known, op_lut, cpu, line);
+        }
+    }
+
+    pass.blocks.retain(|block| !block.bytes.is_empty());
+
+    pass
+}
+
+fn assemble_in(source: &str, origin: u32, op_lut: &OpLookUpTable, cpu: CpuModel, bare_hex: bool) -> Result<Assembly, Vec<Diagnostic>> {
+    let source = format!("{}\0", source);
+    let (statements, mut diagnostics) = parse_source(&source);
+
+    let mut symbols = SymbolTable::new();
+    for _ in 0..MAX_PASSES {
+        let pass = run_pass(&source, &statements, bare_hex, origin, &symbols, op_lut, cpu);
+
+        if pass.symbols == symbols {
+            diagnostics.extend(pass.diagnostics);
+            diagnostics.sort_by_key(|diagnostic| diagnostic.span.map(|span| (span.line, span.column)));
+
+            return match diagnostics.is_empty() {
+                true => Ok(Assembly {
+                    blocks: pass.blocks,
+                    symbols: pass.symbols
+                }),
+                false => Err(diagnostics)
+            };
+        }
+
+        symbols = pass.symbols;
+    }
+
+    diagnostics.push(Diagnostic::new(AsmError::Unsettled { passes: MAX_PASSES }, None));
+    Err(diagnostics)
+}
+
+// Assembles source to origin onward.  Errors come back all together, in the order of the source.
+// Hex starts with a digit (0FF30h), a name that doesn't is a symbol.
+pub fn assemble(source: &str, origin: u32, op_lut: &OpLookUpTable, cpu: CpuModel) -> Result<Assembly, Vec<Diagnostic>> {
+    assemble_in(source, origin, op_lut, cpu, false)
+}
+
+// As assemble, for what r2 lists: hex can go without the leading 0 (FF30h) as long as nothing has
+// that name
+pub fn assemble_listing(source: &str, origin: u32, op_lut: &OpLookUpTable, cpu: CpuModel) -> Result<Assembly, Vec<Diagnostic>> {
+    assemble_in(source, origin, op_lut, cpu, true)
+}
+
+// A source that places itself with ORG and SECTION ... AT
+pub fn assemble_source(source: &str, cpu: CpuModel) -> Result<Assembly, Vec<Diagnostic>> {
+    let mut op_lut: OpLookUpTable = OpLookUpTable::new();
+    build_lut(&mut op_lut);
+
+    assemble(source, 0, &op_lut, cpu)
+}
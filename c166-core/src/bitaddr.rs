@@ -17,12 +17,13 @@
 
 use ::instruction::*;
 use ::reg::*;
+use ::opformat::FormatOptions;
 
-pub fn bitoff_to_string(offset: u8, is_ext : bool) -> Result<String, String> {
+pub fn bitoff_to_string(offset: u8, is_ext : bool, options: &FormatOptions) -> Result<String, String> {
     match offset {
         0x00...0x7F => {
             // RAM
-            Ok(format!("{:04X}h", 0xFD00 + ((2 * offset) as u16)))
+            Ok(options.hex(0xFD00 + (2 * offset as u32), 4))
         },
         0x80...0xEF => {
             // Special fn registers
@@ -30,26 +31,42 @@ pub fn bitoff_to_string(offset: u8, is_ext : bool) -> Result<String, String> {
                 false => {
                     // SFR
                     let address : u16 = 0xFF00 + (2 * (offset & 0b01111111)) as u16;
-                    match Reg::from_phys16(address, &OperandType::WordRegister(0)) {
-                        Ok(reg) => Ok(format!("{}", reg)),
-                        Err(_) => Err(format!("No reg found at {:04X}h", address))
-                    }
+                    let reg = Reg::from_address(address, options.registers).map_err(|e| e.to_string())?;
+                    Ok(options.reg(&reg))
                 },
                 true => {
                     // 'reg' accesses to the ESFR area require a preceding EXT*R instruction to switch the base address
                     // not available in the SAB 8XC166(W) devices
                     // ESFR
                     let address = 0xF100 + ((2 * (offset & 0b01111111)) as u16);
-                    Ok(format!("{:04X}", address))
+                    let reg = Reg::from_address(address, options.registers).map_err(|e| e.to_string())?;
+                    Ok(options.reg(&reg))
                 }
             }
         },
         0xF0...0xFF => {
-            let reg = Reg::from_reg8(offset, &OperandType::WordRegister(0)).unwrap();
-            Ok(format!("{}", reg))
+            let reg = Reg::from_reg8(offset, &OperandType::WordRegister(0)).map_err(|e| e.to_string())?;
+            Ok(options.reg(&reg))
         },
         _ => {
             Err(format!("Invalid bit offset {:X}", offset))
         }
     }
 }
+
+// A bitoff.bit pair, using the name of the bit when the register description has one
+pub fn bitaddr_to_string(offset: u8, bit: u8, is_ext : bool, options: &FormatOptions) -> Result<String, String> {
+    if let 0x80..=0xEF = offset {
+        let base: u16 = match is_ext {
+            true => 0xF100,
+            false => 0xFF00
+        };
+        let address = base + ((2 * (offset & 0b01111111)) as u16);
+
+        if let Some(name) = options.registers.bit_name(address, bit) {
+            return Ok(options.name(&name));
+        }
+    }
+
+    Ok(format!("{}.{}", bitoff_to_string(offset, is_ext, options)?, bit))
+}
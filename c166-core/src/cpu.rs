@@ -0,0 +1,125 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::fmt;
+use std::str::FromStr;
+
+use ::error::CpuError;
+use ::instruction::Instruction;
+use ::reg::Reg;
+use ::sfr::RegisterMap;
+
+// The members of the family differ in which instructions and SFRs they have.
+// The C167CR is the superset the decoder has always assumed, so it's the default.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CpuModel {
+    // SAB 80C166(W), no EXT* sequences, no ATOMIC and no ESFR space
+    C166,
+    C167CR,
+    C165,
+    ST10,
+    XC16x,
+}
+
+impl CpuModel {
+    pub const ALL: [CpuModel; 5] = [CpuModel::C166, CpuModel::C167CR, CpuModel::C165, CpuModel::ST10, CpuModel::XC16x];
+
+    // As used by r2's asm.cpu
+    pub fn name(&self) -> &'static str {
+        match self {
+            CpuModel::C166      => "80c166",
+            CpuModel::C167CR    => "c167cr",
+            CpuModel::C165      => "c165",
+            CpuModel::ST10      => "st10",
+            CpuModel::XC16x     => "xc16x",
+        }
+    }
+
+    // The register description in sfr/, the C165 makes do with the C167CR's
+    pub fn device(&self) -> &'static str {
+        match self {
+            CpuModel::C166      => "80c166",
+            CpuModel::C167CR |
+            CpuModel::C165      => "c167cr",
+            CpuModel::ST10      => "st10",
+            CpuModel::XC16x     => "xc16x",
+        }
+    }
+
+    pub fn registers(&self) -> &'static RegisterMap {
+        RegisterMap::builtin(self.device()).unwrap_or_else(|| panic!("No register description for the {}", self))
+    }
+
+    // The extended SFR space at F000h and the EXT*R instructions that reach it
+    pub fn has_esfr(&self) -> bool {
+        *self != CpuModel::C166
+    }
+
+    // The multiply-accumulate unit and its CoXXX instructions
+    pub fn has_mac(&self) -> bool {
+        *self == CpuModel::ST10 || *self == CpuModel::XC16x
+    }
+
+    pub fn supports(&self, isn: &Instruction) -> bool {
+        isn.cpus.contains(self)
+    }
+
+    // Whether the part has reg at all, SFRs it has go by their name in its register map
+    pub fn has_reg(&self, reg: &Reg) -> bool {
+        self.register(reg).is_some()
+    }
+
+    // The part's own version of reg, with the name and address its register map gives it
+    pub fn register(&self, reg: &Reg) -> Option<Reg> {
+        self.register_in(reg, self.registers())
+    }
+
+    // As register, going by a register map other than the part's own
+    pub fn register_in(&self, reg: &Reg, registers: &'static RegisterMap) -> Option<Reg> {
+        match (!reg.is_extended() || self.has_esfr()) && (!reg.is_mac() || self.has_mac()) {
+            true => reg.named_by(registers),
+            false => None
+        }
+    }
+}
+
+impl Default for CpuModel {
+    fn default() -> CpuModel {
+        CpuModel::C167CR
+    }
+}
+
+impl FromStr for CpuModel {
+    type Err = CpuError;
+
+    fn from_str(s: &str) -> Result<CpuModel, CpuError> {
+        match s.to_lowercase().as_str() {
+            "80c166" | "c166" | "sab80c166"     => Ok(CpuModel::C166),
+            "c167cr" | "c167"                   => Ok(CpuModel::C167CR),
+            "c165"                              => Ok(CpuModel::C165),
+            "st10"                              => Ok(CpuModel::ST10),
+            "xc16x" | "xc161" | "xc164" | "xc167" => Ok(CpuModel::XC16x),
+            _ => Err(CpuError::UnknownCpu(s.to_string()))
+        }
+    }
+}
+
+impl fmt::Display for CpuModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -0,0 +1,572 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use ::cpu::CpuModel;
+use ::effects::{effects, Effects};
+use ::encoding::{check_reserved, Encoding};
+use ::error::DecodeError;
+use ::instruction::*;
+use ::mac::MacOp;
+use ::memory::{byte_access, MemoryModel, Override};
+use ::opformat::{format_op_with, FormatOptions};
+use ::reg::Reg;
+use ::sfr::RegisterMap;
+use ::timing::{state_times, StateTimes};
+
+// Where control goes after an instruction
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FlowKind {
+    Fallthrough,
+    Jump,
+    ConditionalJump,
+    Call,
+    Return,
+    Trap,
+    // jmpi and calli, the destination is in a GPR
+    Indirect,
+}
+
+impl<'a> From<&'a OpType> for FlowKind {
+    fn from(op_type: &'a OpType) -> Self {
+        match op_type.kind {
+            OpKind::Jmp     => FlowKind::Jump,
+            OpKind::CJmp    => FlowKind::ConditionalJump,
+            OpKind::Call    => FlowKind::Call,
+            OpKind::Ret     => FlowKind::Return,
+            OpKind::Trap    => FlowKind::Trap,
+            _               => FlowKind::Fallthrough,
+        }
+    }
+}
+
+// The EXT* instructions that change how the instructions after them address memory
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExtKind {
+    Extr,
+    Extp,
+    Extpr,
+    Exts,
+    Extsr,
+}
+
+impl ExtKind {
+    pub fn from_mnemonic(mnemonic: &str) -> Option<ExtKind> {
+        match mnemonic {
+            "extr"  => Some(ExtKind::Extr),
+            "extp"  => Some(ExtKind::Extp),
+            "extpr" => Some(ExtKind::Extpr),
+            "exts"  => Some(ExtKind::Exts),
+            "extsr" => Some(ExtKind::Extsr),
+            _       => None
+        }
+    }
+
+    // reg and bitoff operands address the ESFRs
+    pub fn is_extended(&self) -> bool {
+        match *self {
+            ExtKind::Extr | ExtKind::Extpr | ExtKind::Extsr => true,
+            _ => false
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ExtWindow {
+    pub kind: ExtKind,
+    // Instructions still affected, 1..=4
+    pub remaining: u8,
+    // The page (EXTP*) or segment (EXTS*), None when it's taken from a GPR
+    pub base: Option<u16>,
+}
+
+impl ExtWindow {
+    // What the window does to mem operands
+    pub fn mem_override(&self) -> Option<Override> {
+        match self.kind {
+            ExtKind::Extr => None,
+            ExtKind::Extp | ExtKind::Extpr => Some(Override::Page(self.base)),
+            ExtKind::Exts | ExtKind::Extsr => Some(Override::Segment(self.base)),
+        }
+    }
+}
+
+// What to do with an instruction whose reserved fields hold something the encoder wouldn't write
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DecodeMode {
+    // Decode it as the CPU would, noting the field in DecodedInstruction::reserved
+    Permissive,
+    // Reject it
+    Strict,
+}
+
+impl Default for DecodeMode {
+    fn default() -> DecodeMode {
+        DecodeMode::Permissive
+    }
+}
+
+// State carried from one instruction to the next, starting out as the part comes out of reset.
+// Decoding instructions one at a time (e.g. as r2 does) only continues when the address follows
+// on from the last instruction, anything else is taken to be a jump or a seek.  That closes the
+// window and leaves the DPPs and CP unknown until they're loaded again, the code there may run
+// with any values.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DecodeContext {
+    pub window: Option<ExtWindow>,
+    pub memory: MemoryModel,
+    // Where the next instruction is expected to be
+    pub next_addr: Option<u32>,
+    pub mode: DecodeMode,
+}
+
+impl DecodeContext {
+    pub fn new() -> DecodeContext {
+        DecodeContext::default()
+    }
+
+    pub fn with_mode(mode: DecodeMode) -> DecodeContext {
+        DecodeContext { mode: mode, ..DecodeContext::default() }
+    }
+
+    pub fn is_extended(&self) -> bool {
+        match self.window {
+            Some(window) => window.kind.is_extended(),
+            None => false
+        }
+    }
+
+    // The 24-bit address a mem operand refers to, if it can be worked out
+    pub fn mem_address(&self, mem: u16) -> Option<u32> {
+        self.memory.translate(mem)
+    }
+
+    fn set_window(&mut self, window: Option<ExtWindow>) {
+        self.window = window;
+        self.memory.ext = window.and_then(|window| window.mem_override());
+    }
+
+    // Whatever comes next may not follow on from what came before
+    fn forget(&mut self) {
+        self.set_window(None);
+        self.memory.dpp = [None; 4];
+        self.memory.cp = None;
+        self.next_addr = None;
+    }
+
+    // Follows loads of the DPPs and SYSCON's segmentation bit
+    fn track_registers(&mut self, decoded: &DecodedInstruction) {
+        let reg = match decoded.arguments.op1 {
+            Some(Operand::Register(reg)) => reg,
+            _ => return
+        };
+
+        // These only read their first operand
+        match decoded.instruction.op_type.kind {
+            OpKind::Cmp | OpKind::Push | OpKind::Jmp | OpKind::CJmp | OpKind::Call => return,
+            _ => {}
+        }
+
+        let value = match (decoded.mnemonic.as_str(), decoded.arguments.op2) {
+            ("mov", Some(Operand::Immediate(value, _))) |
+            ("scxt", Some(Operand::Immediate(value, _))) => Some(value),
+            _ => None
+        };
+
+        match reg {
+            Reg::DPP0 => self.memory.set_dpp(0, value),
+            Reg::DPP1 => self.memory.set_dpp(1, value),
+            Reg::DPP2 => self.memory.set_dpp(2, value),
+            Reg::DPP3 => self.memory.set_dpp(3, value),
+            Reg::CP => self.memory.cp = value,
+            // Wherever the part has it
+            _ if reg.to_string() == "SYSCON" => {
+                // SGTDIS
+                if let Some(value) = value {
+                    self.memory.segmented = value & (1 << 11) == 0;
+                }
+            },
+            _ => {}
+        }
+    }
+
+    // Moves past a decoded instruction, opening a window for an EXT* and using up the current one otherwise
+    pub fn step(&mut self, decoded: &DecodedInstruction) {
+        self.track_registers(decoded);
+
+        let window = match ExtKind::from_mnemonic(&decoded.mnemonic) {
+            Some(kind) => {
+                let (count, base) = match kind {
+                    ExtKind::Extr => (decoded.arguments.op1, None),
+                    _ => (decoded.arguments.op2, decoded.arguments.op1)
+                };
+
+                Some(ExtWindow {
+                    kind: kind,
+                    remaining: match count {
+                        Some(Operand::Immediate(count, _)) => count as u8,
+                        _ => 1
+                    },
+                    base: match base {
+                        Some(Operand::Immediate(base, _)) |
+                        Some(Operand::Page10(base)) => Some(base),
+                        _ => None
+                    },
+                })
+            },
+            None => match self.window {
+                Some(ExtWindow { remaining: 1, .. }) | None => None,
+                Some(window) => Some(ExtWindow { remaining: window.remaining - 1, ..window })
+            }
+        };
+
+        self.set_window(window);
+        self.next_addr = Some(decoded.addr + decoded.length as u32);
+    }
+
+    // Decodes the instruction at addr in light of whatever came before it
+    pub fn decode(&mut self, bytes: &[u8], addr: u32, cpu: CpuModel) -> Result<DecodedInstruction, DecodeError> {
+        match self.next_addr {
+            Some(next_addr) if next_addr != addr => self.forget(),
+            _ => {}
+        }
+
+        let result = decode_in_context(bytes, addr, cpu, cpu.registers(), self);
+        match &result {
+            Ok(decoded) => self.step(decoded),
+            Err(_) => self.forget()
+        }
+
+        result
+    }
+}
+
+#[derive(Debug)]
+pub struct DecodedInstruction {
+    pub addr: u32,
+    pub length: usize,
+    pub bytes: Vec<u8>,
+    // The sub-op mnemonic where there is one (e.g. extp rather than ext*)
+    pub mnemonic: String,
+    pub operands: Vec<Operand>,
+    pub flow: FlowKind,
+    // Known destinations of a jump, call, or trap.  Indirect jumps have none.
+    pub targets: Vec<u32>,
+    // The 24-bit address of the mem operand, when the DPPs or an EXTP*/EXTS* window say
+    pub mem_addr: Option<u32>,
+    // A reserved field not holding what it should, permissive decoding lets these through
+    pub reserved: Option<DecodeError>,
+    // The register map the operands were named from
+    pub registers: &'static RegisterMap,
+
+    pub instruction: Instruction<'static>,
+    pub arguments: InstructionArguments,
+}
+
+impl DecodedInstruction {
+    // Where the instruction would go if it sat at addr.  Indirect jumps have none.
+    pub fn branch_targets(&self, addr: u32) -> Vec<u32> {
+        get_branch_targets(&self.arguments, addr, self.length)
+    }
+
+    // Registers, flags and memory the instruction reads and writes
+    pub fn effects(&self) -> Effects {
+        effects(self)
+    }
+
+    // Minimum states on the part, None where there's no timing model for it
+    pub fn state_times(&self, cpu: CpuModel) -> Option<StateTimes> {
+        state_times(self, cpu)
+    }
+}
+
+impl DecodedInstruction {
+    // The listing for another assembler, Display gives the default.  Bits are named from the same
+    // register map as the rest of the operands.
+    pub fn format(&self, options: &FormatOptions) -> String {
+        format_op_with(&self.instruction, &self.arguments, self.addr, &FormatOptions { registers: self.registers, ..*options })
+    }
+}
+
+impl fmt::Display for DecodedInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.format(&FormatOptions::default()))
+    }
+}
+
+fn get_flow(isn: &Instruction) -> FlowKind {
+    match (FlowKind::from(&isn.op_type), isn.op2) {
+        (FlowKind::Jump, Some(OperandType::Indirect(_))) |
+        (FlowKind::ConditionalJump, Some(OperandType::Indirect(_))) |
+        (FlowKind::Call, Some(OperandType::Indirect(_))) => FlowKind::Indirect,
+        (flow, _) => flow
+    }
+}
+
+// Where a rel8 operand sends an instruction at addr: a signed word offset from the following
+// instruction.  IP wraps around within the code segment.
+pub fn relative_target(rel: i8, addr: u32, length: usize) -> u32 {
+    let offset: i32 = rel as i32 * 2;
+    let ip = (addr as i32 + length as i32 + offset) as u32;
+    (addr & 0xFF0000) | (ip & 0xFFFF)
+}
+
+fn get_branch_targets(values: &InstructionArguments, addr: u32, length: usize) -> Vec<u32> {
+    let mut targets: Vec<u32> = vec![];
+
+    for operand in [values.op1, values.op2].iter() {
+        match *operand {
+            Some(Operand::Rel8(rel)) => {
+                targets.push(relative_target(rel, addr, length));
+            },
+            Some(Operand::CodeAddr16(caddr)) => {
+                match values.op1 {
+                    // Inter-segment
+                    Some(Operand::Segment8(seg)) => {
+                        targets.push(((seg as u32) << 16) | caddr as u32);
+                    },
+                    // Within the current segment
+                    _ => targets.push((addr & 0xFF0000) | caddr as u32)
+                }
+            },
+            Some(Operand::Trap7(trap)) => {
+                // Vectors live in segment 0, four bytes apart
+                targets.push((trap as u32) * 4);
+            },
+            _ => {}
+        }
+    }
+
+    targets
+}
+
+fn check_registers(isn: &Instruction, values: &InstructionArguments, cpu: CpuModel, registers: &'static RegisterMap) -> Result<(), DecodeError> {
+    let operands = [values.op1, values.op2, values.op3];
+
+    for (n, operand) in operands.iter().enumerate() {
+        match operand {
+            Some(Operand::Register(reg)) |
+            Some(Operand::Indirect(reg)) |
+            Some(Operand::IndirectPostIncrement(reg)) |
+            Some(Operand::IndirectPreDecrement(reg)) |
+            Some(Operand::IndirectAndImmediate(reg, _)) |
+            Some(Operand::IndirectPostDecrement(reg)) |
+            Some(Operand::IndirectPostAdd(reg, _)) |
+            Some(Operand::IndirectPostSub(reg, _)) if cpu.register_in(reg, registers).is_none() => {
+                return Err(DecodeError::UnsupportedRegister { opcode: isn.id, operand: n as u8 + 1, reg: *reg, cpu: cpu });
+            },
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+// Inside an EXTR window the short reg operands (but not the GPRs) refer to ESFRs
+fn extend_registers(isn: &Instruction, values: &mut InstructionArguments) {
+    values.extended = true;
+
+    for (op_type, operand) in [(isn.op1, &mut values.op1), (isn.op2, &mut values.op2), (isn.op3, &mut values.op3)].iter_mut() {
+        let reg = match (*op_type, &**operand) {
+            (Some(OperandType::WordRegister(_)), Some(Operand::Register(reg))) |
+            (Some(OperandType::ByteRegister(_)), Some(Operand::Register(reg))) if reg.to_reg4().is_err() && !reg.is_extended() => *reg,
+            _ => continue
+        };
+
+        if let Ok(esfr) = reg.to_reg8().and_then(|short| Reg::from_reg8(short, &OperandType::ExtendedRegister)) {
+            **operand = Some(Operand::Register(esfr));
+        }
+    }
+}
+
+// Memory operands that land in the register bank are really GPRs
+fn bank_registers(isn: &Instruction, values: &mut InstructionArguments, context: &DecodeContext) {
+    let bank = match context.memory.bank() {
+        Some(bank) => bank,
+        None => return
+    };
+
+    let mnemonic = match values.mnemonic.as_ref() {
+        Some(mnem) => mnem.clone(),
+        None => isn.mnemonic.to_string(),
+    };
+
+    for (index, operand) in [&mut values.op1, &mut values.op2, &mut values.op3].iter_mut().enumerate() {
+        let address = match **operand {
+            Some(Operand::Mem16(mem)) => context.mem_address(mem),
+            _ => continue
+        };
+
+        let reg = match address {
+            Some(address) if address <= 0xFFFF => bank.register(address as u16, byte_access(&mnemonic, index)),
+            _ => None
+        };
+
+        if let Some(reg) = reg {
+            **operand = Some(Operand::Register(reg));
+        }
+    }
+}
+
+fn get_mem_addr(values: &InstructionArguments, context: &DecodeContext) -> Option<u32> {
+    [values.op1, values.op2, values.op3].iter()
+        .filter_map(|operand| match *operand {
+            Some(Operand::Mem16(mem)) => context.mem_address(mem),
+            _ => None
+        })
+        .next()
+}
+
+// Every SFR and ESFR is named by the device's register map, addresses it doesn't describe are
+// left as addresses
+fn name_registers(values: &mut InstructionArguments, registers: &'static RegisterMap) {
+    for operand in [&mut values.op1, &mut values.op2, &mut values.op3].iter_mut() {
+        let address = match operand {
+            Some(Operand::Register(reg)) if reg.to_reg4().is_err() => match reg.to_phys16() {
+                Ok(address) => address,
+                Err(_) => continue
+            },
+            _ => continue
+        };
+
+        if let Ok(reg) = Reg::from_address(address, registers) {
+            **operand = Some(Operand::Register(reg));
+        }
+    }
+}
+
+// Decodes the single instruction at the start of bytes, which lives at addr
+pub fn decode(bytes: &[u8], addr: u32) -> Result<DecodedInstruction, DecodeError> {
+    decode_for_cpu(bytes, addr, CpuModel::default())
+}
+
+// As decode, rejecting instructions and registers the part doesn't have
+pub fn decode_for_cpu(bytes: &[u8], addr: u32, cpu: CpuModel) -> Result<DecodedInstruction, DecodeError> {
+    decode_with_registers(bytes, addr, cpu, cpu.registers())
+}
+
+// As decode_for_cpu, naming registers from a register map other than the part's own
+pub fn decode_with_registers(bytes: &[u8], addr: u32, cpu: CpuModel, registers: &'static RegisterMap) -> Result<DecodedInstruction, DecodeError> {
+    decode_in_context(bytes, addr, cpu, registers, &DecodeContext::new())
+}
+
+// As decode_with_registers, applying any EXT* window the context has open.  The context isn't
+// advanced, DecodeContext::decode does both.
+pub fn decode_in_context(bytes: &[u8], addr: u32, cpu: CpuModel, registers: &'static RegisterMap, context: &DecodeContext) -> Result<DecodedInstruction, DecodeError> {
+    let opcode: u8 = *bytes.first().ok_or(DecodeError::Incomplete { needed: 1 })?;
+    let isn: Instruction<'static> = Instruction::try_from(opcode)?;
+    if !cpu.supports(&isn) {
+        return Err(DecodeError::UnsupportedInstruction { opcode: opcode, cpu: cpu });
+    }
+
+    let encoding = Encoding::from(&isn.encoding);
+    let mut values = (encoding.decode)(&isn, bytes)?;
+    let reserved = match (check_reserved(&isn, bytes), context.mode) {
+        (Ok(()), _) => None,
+        (Err(e), DecodeMode::Permissive) => Some(e),
+        (Err(e), DecodeMode::Strict) => return Err(e),
+    };
+    if context.is_extended() {
+        extend_registers(&isn, &mut values);
+    }
+    name_registers(&mut values, registers);
+    check_registers(&isn, &values, cpu, registers)?;
+    let mem_addr = get_mem_addr(&values, context);
+    bank_registers(&isn, &mut values, context);
+    let length = encoding.length as usize;
+
+    let mnemonic = match values.mnemonic.as_ref() {
+        Some(mnem) => mnem.clone(),
+        None => isn.mnemonic.to_string(),
+    };
+
+    // The CoXXX opcodes share a table entry, what they actually do depends on the MAC sub-op
+    let isn = match MacOp::from_mnemonic(&mnemonic) {
+        Some(mac_op) => Instruction { op_type: mac_op.op_type(), ..isn },
+        None => isn
+    };
+
+    let operands: Vec<Operand> = [values.op1, values.op2, values.op3].iter().filter_map(|op| *op).collect();
+
+    Ok(DecodedInstruction {
+        addr: addr,
+        length: length,
+        bytes: bytes[0..length].to_vec(),
+        mnemonic: mnemonic,
+        operands: operands,
+        flow: get_flow(&isn),
+        targets: get_branch_targets(&values, addr, length),
+        mem_addr: mem_addr,
+        reserved: reserved,
+        registers: registers,
+        instruction: isn,
+        arguments: values,
+    })
+}
+
+// Walks a buffer an instruction at a time.  Undecodable words are reported and
+// skipped, a truncated instruction at the end of the buffer ends the walk.
+pub struct DecodeIter<'a> {
+    bytes: &'a [u8],
+    addr: u32,
+    cpu: CpuModel,
+    context: DecodeContext,
+}
+
+impl<'a> Iterator for DecodeIter<'a> {
+    type Item = Result<DecodedInstruction, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        let result = self.context.decode(self.bytes, self.addr, self.cpu);
+
+        // Every instruction is at least a word long and word aligned
+        let advance = match &result {
+            Ok(decoded) => decoded.length,
+            Err(DecodeError::Incomplete { .. }) => self.bytes.len(),
+            Err(_) => 2,
+        };
+
+        let advance = advance.min(self.bytes.len());
+        self.bytes = &self.bytes[advance..];
+        self.addr += advance as u32;
+
+        Some(result)
+    }
+}
+
+pub fn decode_all<'a>(bytes: &'a [u8], addr: u32) -> DecodeIter<'a> {
+    decode_all_for_cpu(bytes, addr, CpuModel::default())
+}
+
+pub fn decode_all_for_cpu<'a>(bytes: &'a [u8], addr: u32, cpu: CpuModel) -> DecodeIter<'a> {
+    decode_all_in_mode(bytes, addr, cpu, DecodeMode::default())
+}
+
+pub fn decode_all_in_mode<'a>(bytes: &'a [u8], addr: u32, cpu: CpuModel, mode: DecodeMode) -> DecodeIter<'a> {
+    DecodeIter {
+        bytes: bytes,
+        addr: addr,
+        cpu: cpu,
+        context: DecodeContext::with_mode(mode),
+    }
+}
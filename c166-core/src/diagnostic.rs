@@ -0,0 +1,182 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+// Assembler errors with where in the source they are, so that they can be shown under the line
+// they're about:
+//
+//   Line 3, column 17: undefined symbol "lable"
+//           jmpr cc_Z, lable
+//                      ^^^^^
+
+use std::fmt;
+use std::iter;
+
+use ::cpu::CpuModel;
+use ::encoding::EncodingType;
+use ::error::AsmError;
+use ::instruction::{Instruction, OperandType};
+use ::opcodes::{forms, table_mnemonic};
+
+// Lines and columns are 1-based, columns and lengths count characters
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+}
+
+impl Span {
+    // Where part sits in source, part has to be a slice of it
+    pub fn locate(source: &str, part: &str) -> Span {
+        let offset = (part.as_ptr() as usize).saturating_sub(source.as_ptr() as usize).min(source.len());
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+        Span {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            // An empty part still gets a caret, at the end of the line say
+            length: part.chars().count().max(1),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub error: AsmError,
+    // None when it isn't down to any one line
+    pub span: Option<Span>,
+    // The forms an operation can take, when none of them fit what it was given
+    pub forms: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn new(error: AsmError, span: Option<Span>) -> Diagnostic {
+        Diagnostic {
+            error: error,
+            span: span,
+            forms: Vec::new(),
+        }
+    }
+
+    // The message, the line it's about with a caret under the span, and the forms if there are any
+    pub fn render(&self, source: &str) -> String {
+        let mut text = format!("{}\n", self);
+
+        if let Some(span) = self.span {
+            let line = source.split('\n').nth(span.line - 1).unwrap_or("").trim_end_matches(&['\r', '\0'][..]);
+
+            // Tabs stay tabs so that the caret lines up however wide they're shown
+            let indent: String = line.chars().chain(iter::repeat(' '))
+                .take(span.column - 1)
+                .map(|c| match c {
+                    '\t' => '\t',
+                    _ => ' '
+                })
+                .collect();
+
+            text.push_str(&format!("    {}\n    {}{}\n", line, indent, "^".repeat(span.length)));
+        }
+
+        if !self.forms.is_empty() {
+            text.push_str("    valid forms:\n");
+            for form in self.forms.iter() {
+                text.push_str(&format!("        {}\n", form));
+            }
+        }
+
+        text
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.span {
+            Some(span) => write!(f, "Line {}, column {}: {}", span.line, span.column, self.error.message()),
+            None => write!(f, "{}", self.error)
+        }
+    }
+}
+
+// Every diagnostic, one after the other
+pub fn render(diagnostics: &[Diagnostic], source: &str) -> String {
+    diagnostics.iter().map(|diagnostic| diagnostic.render(source)).collect()
+}
+
+// As the user's manual writes the operand
+fn operand_form(isn: &Instruction, op_type: OperandType) -> &'static str {
+    let reg8 = match isn.encoding {
+        EncodingType::reg8 | EncodingType::reg8_mem16 | EncodingType::reg8_data8_nop8 | EncodingType::reg8_data16 => true,
+        _ => false
+    };
+
+    match op_type {
+        OperandType::Condition => "cc",
+        OperandType::ByteRegister(_) | OperandType::WordRegister(_) | OperandType::ExtendedRegister if reg8 => "reg",
+        OperandType::ByteRegister(_) => "Rb",
+        OperandType::WordRegister(_) | OperandType::ExtendedRegister => "Rw",
+        OperandType::DirectMemory16 => "mem",
+        OperandType::DirectCaddr16 => "caddr",
+        OperandType::DirectSegment8 => "seg",
+        OperandType::DirectRelative8S => "rel",
+        OperandType::Indirect(_) => "[Rw]",
+        OperandType::IndirectPostIncrement(_) => "[Rw+]",
+        OperandType::IndirectPreDecrement(_) => "[-Rw]",
+        OperandType::IndirectAndImmediate(_) => "[Rw+#data16]",
+        OperandType::IndexPointer => "[IDXi]",
+        OperandType::IndirectModified(_) => "[Rw]",
+        OperandType::MacRegister => "CoReg",
+        OperandType::BitAddr(_) => "bitaddr",
+        OperandType::BitOffset(_) => "bitoff",
+        OperandType::ImmediateData3 => "#data3",
+        OperandType::ImmediateData4 => "#data4",
+        OperandType::ImmediateData8 => "#data8",
+        OperandType::ImmediateData16 => "#data16",
+        OperandType::ImmediateMask8 => "#mask8",
+        OperandType::ImmediateTrap7 => "#trap7",
+        OperandType::ImmediatePage10 => "#pag10",
+        OperandType::ImmediateSegment8 => "#seg8",
+        OperandType::ImmediateIrange2 => "#irang2",
+    }
+}
+
+// The operand combinations mnemonic takes on the cpu, "mov Rw, #data16" and so on.  Mnemonics
+// that share their opcodes with others (extp, the CoXXX ops) get none, the table can't tell their
+// operands apart.
+pub fn valid_forms(mnemonic: &str, cpu: CpuModel) -> Vec<String> {
+    let mnemonic = mnemonic.to_lowercase();
+    if table_mnemonic(&mnemonic) != mnemonic {
+        return Vec::new();
+    }
+
+    let mut valid: Vec<String> = Vec::new();
+    for isn in forms(&mnemonic).filter(|isn| cpu.supports(isn)) {
+        let operands: Vec<&str> = isn.signature().iter()
+            .filter_map(|op_type| op_type.map(|op_type| operand_form(isn, op_type)))
+            .collect();
+
+        let form = match operands.is_empty() {
+            true => mnemonic.clone(),
+            false => format!("{} {}", mnemonic, operands.join(", "))
+        };
+        if !valid.contains(&form) {
+            valid.push(form);
+        }
+    }
+
+    valid
+}
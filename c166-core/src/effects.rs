@@ -0,0 +1,393 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+// What an instruction reads and writes: registers (including the implicit SP, CSP, MDH/MDL, the
+// MAC unit's and PSW), PSW flags and memory.  Where it depends on state the instruction can't
+// see (whether reti and trap move CSP depends on segmentation) the effects err on the side of
+// listing the access.
+
+use ::decoder::DecodedInstruction;
+use ::instruction::*;
+use ::mac::MacOp;
+use ::memory::byte_access;
+use ::reg::Reg;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Flag {
+    E,
+    Z,
+    V,
+    C,
+    N,
+}
+
+impl Flag {
+    // In the order Instruction::flags lists them
+    pub const ALL: [Flag; 5] = [Flag::E, Flag::Z, Flag::V, Flag::C, Flag::N];
+
+    // The flag's bit in PSW
+    pub fn mask(&self) -> u16 {
+        match *self {
+            Flag::N => 1 << 0,
+            Flag::C => 1 << 1,
+            Flag::V => 1 << 2,
+            Flag::Z => 1 << 3,
+            Flag::E => 1 << 4,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Access {
+    Read,
+    Write,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Address {
+    // A 16-bit data address, still to go through the DPPs
+    Direct(u16),
+    // The GPR's value plus an offset
+    Indirect(Reg, u16),
+    // The top of the system stack, SP before a pop and after a push
+    Stack,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MemoryAccess {
+    pub address: Address,
+    // In bytes
+    pub width: u8,
+    pub access: Access,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Effects {
+    pub reads: Vec<Reg>,
+    pub writes: Vec<Reg>,
+    // E, Z, V, C, N
+    pub flags: [FlagEffect; 5],
+    // In the order they happen
+    pub memory: Vec<MemoryAccess>,
+}
+
+impl Effects {
+    pub fn flag(&self, flag: Flag) -> FlagEffect {
+        self.flags[Flag::ALL.iter().position(|f| *f == flag).unwrap()]
+    }
+
+    pub fn reads_reg(&self, reg: Reg) -> bool {
+        self.reads.contains(&reg)
+    }
+
+    pub fn writes_reg(&self, reg: Reg) -> bool {
+        self.writes.contains(&reg)
+    }
+
+    fn read(&mut self, reg: Reg) {
+        if !self.reads.contains(&reg) {
+            self.reads.push(reg);
+        }
+    }
+
+    fn write(&mut self, reg: Reg) {
+        if !self.writes.contains(&reg) {
+            self.writes.push(reg);
+        }
+    }
+
+    fn access(&mut self, address: Address, width: u8, access: Access) {
+        self.memory.push(MemoryAccess { address: address, width: width, access: access });
+    }
+
+    fn push(&mut self, reg: Reg) {
+        self.read(reg);
+        self.read(Reg::SP);
+        self.write(Reg::SP);
+        self.access(Address::Stack, 2, Access::Write);
+    }
+
+    fn pop(&mut self, reg: Reg) {
+        self.read(Reg::SP);
+        self.write(Reg::SP);
+        self.access(Address::Stack, 2, Access::Read);
+        self.write(reg);
+    }
+
+    // The IP isn't a register the enum knows, only the stack traffic shows up
+    fn push_ip(&mut self) {
+        self.read(Reg::SP);
+        self.write(Reg::SP);
+        self.access(Address::Stack, 2, Access::Write);
+    }
+
+    fn pop_ip(&mut self) {
+        self.read(Reg::SP);
+        self.write(Reg::SP);
+        self.access(Address::Stack, 2, Access::Read);
+    }
+
+    // A MAC op works on the accumulator in MAH and MAL and leaves its flags in MSW.  MCW says how
+    // a product is shifted and whether the accumulator saturates.
+    fn accumulate(&mut self, kind: OpKind, mnemonic: &str) {
+        let (read, write) = match (kind, mnemonic) {
+            (OpKind::Nop, _) => return,
+            // The product or the operands take the accumulator's place
+            (OpKind::Mov, _) => (false, true),
+            (OpKind::Mul, _) if mnemonic.starts_with("comul") => (false, true),
+            // Only the flags come out of a compare
+            (_, "cocmp") => (true, false),
+            _ => (true, true)
+        };
+
+        if read {
+            self.read(Reg::MAH);
+            self.read(Reg::MAL);
+        }
+        if write {
+            self.read(Reg::MCW);
+            self.write(Reg::MAH);
+            self.write(Reg::MAL);
+        }
+        self.write(Reg::MSW);
+    }
+
+    // The value of an operand is used, read is false when it's only overwritten
+    fn operand(&mut self, operand: &Operand, width: u8, extended: bool, read: bool, write: bool) {
+        let address = match *operand {
+            Operand::Register(reg) => {
+                if read {
+                    self.read(reg);
+                }
+                if write {
+                    self.write(reg);
+                }
+                return;
+            },
+            Operand::BitAddr(offset, _) => match bit_target(offset, extended) {
+                Some(Operand::Register(reg)) => return self.operand(&Operand::Register(reg), width, extended, read, write),
+                Some(Operand::Mem16(address)) => Address::Direct(address),
+                _ => return
+            },
+            Operand::Mem16(address) => Address::Direct(address),
+            Operand::Indirect(reg) => {
+                self.read(reg);
+                Address::Indirect(reg, 0)
+            },
+            Operand::IndirectAndImmediate(reg, offset) => {
+                self.read(reg);
+                Address::Indirect(reg, offset)
+            },
+            // The pointer moves after the access
+            Operand::IndirectPostIncrement(reg) |
+            Operand::IndirectPostDecrement(reg) |
+            Operand::IndirectPostAdd(reg, _) |
+            Operand::IndirectPostSub(reg, _) => {
+                self.read(reg);
+                self.write(reg);
+                if let Operand::IndirectPostAdd(_, step) | Operand::IndirectPostSub(_, step) = *operand {
+                    self.read(step);
+                }
+                Address::Indirect(reg, 0)
+            },
+            // [-Rw] decrements first, the access is at the new value
+            Operand::IndirectPreDecrement(reg) => {
+                self.read(reg);
+                self.write(reg);
+                Address::Indirect(reg, 0)
+            },
+            // Immediates and code addresses, which are where control goes, aren't data
+            _ => return
+        };
+
+        if read {
+            self.access(address, width, Access::Read);
+        }
+        if write {
+            self.access(address, width, Access::Write);
+        }
+    }
+}
+
+// The register or RAM word a bitoff refers to
+fn bit_target(offset: u8, extended: bool) -> Option<Operand> {
+    match offset {
+        0x00..=0x7F => Some(Operand::Mem16(0xFD00 + (2 * offset as u16))),
+        0x80..=0xEF => {
+            let base: u16 = match extended {
+                true => 0xF100,
+                false => 0xFF00
+            };
+            Reg::from_phys16(base + (2 * (offset & 0x7F) as u16)).ok().map(Operand::Register)
+        },
+        _ => Reg::from_reg8(offset, &OperandType::WordRegister(0)).ok().map(Operand::Register)
+    }
+}
+
+pub fn effects(decoded: &DecodedInstruction) -> Effects {
+    let isn = &decoded.instruction;
+    let values = &decoded.arguments;
+    let extended = values.extended;
+    let mnemonic = decoded.mnemonic.as_str();
+
+    let mut effects = Effects {
+        reads: Vec::new(),
+        writes: Vec::new(),
+        flags: isn.flags,
+        memory: Vec::new(),
+    };
+
+    let width = |index: usize| -> u8 {
+        match byte_access(mnemonic, index) {
+            true => 1,
+            false => 2
+        }
+    };
+
+    let conditional = match values.op1 {
+        Some(Operand::Condition(OpCondition::cc_UC)) => false,
+        Some(Operand::Condition(_)) => true,
+        _ => false
+    };
+    if conditional {
+        effects.read(Reg::PSW);
+    }
+
+    let ops = [values.op1, values.op2, values.op3];
+    let op = |index: usize| -> Option<Operand> {
+        match ops[index] {
+            Some(Operand::Condition(_)) | None => None,
+            operand => operand
+        }
+    };
+
+    // Whatever is left to do with each operand: (read, write)
+    let mut roles: [(bool, bool); 3] = [(true, false); 3];
+
+    match (isn.op_type.kind, mnemonic) {
+        // The MAC ops only read their operands, whatever the kind of op
+        (kind, _) if MacOp::from_mnemonic(mnemonic).is_some() => effects.accumulate(kind, mnemonic),
+        (_, "mul") | (_, "mulu") => {
+            effects.write(Reg::MDH);
+            effects.write(Reg::MDL);
+        },
+        (_, "div") | (_, "divu") => {
+            effects.read(Reg::MDL);
+            effects.write(Reg::MDH);
+            effects.write(Reg::MDL);
+        },
+        (_, "divl") | (_, "divlu") => {
+            effects.read(Reg::MDH);
+            effects.read(Reg::MDL);
+            effects.write(Reg::MDH);
+            effects.write(Reg::MDL);
+        },
+        // The carry comes in from PSW
+        (_, "addc") | (_, "addcb") | (_, "subc") | (_, "subcb") => {
+            effects.read(Reg::PSW);
+            roles[0] = (true, true);
+        },
+        // The pointer in op1 moves after the compare
+        (_, "cmpi1") | (_, "cmpi2") | (_, "cmpd1") | (_, "cmpd2") => roles[0] = (true, true),
+        (_, "bmov") | (_, "bmovn") | (_, "bfldl") | (_, "bfldh") => roles[0] = (true, true),
+        (_, "prior") => roles[0] = (false, true),
+        (_, "scxt") => {
+            if let Some(Operand::Register(reg)) = op(0) {
+                effects.push(reg);
+            }
+            roles[0] = (false, true);
+        },
+        (_, "jbc") | (_, "jnbs") => roles[0] = (true, true),
+        (_, "calls") => {
+            effects.push(Reg::CSP);
+            effects.push_ip();
+            effects.write(Reg::CSP);
+        },
+        (_, "pcall") => {
+            if let Some(Operand::Register(reg)) = op(0) {
+                effects.push(reg);
+            }
+            effects.push_ip();
+            roles[0] = (false, false);
+        },
+        (_, "jmps") => effects.write(Reg::CSP),
+        (_, "ret") => effects.pop_ip(),
+        (_, "rets") => {
+            effects.pop_ip();
+            effects.pop(Reg::CSP);
+        },
+        (_, "retp") => {
+            effects.pop_ip();
+            if let Some(Operand::Register(reg)) = op(0) {
+                effects.pop(reg);
+            }
+            roles[0] = (false, false);
+        },
+        (_, "reti") => {
+            effects.pop_ip();
+            effects.pop(Reg::CSP);
+            effects.pop(Reg::PSW);
+        },
+        (_, "trap") => {
+            effects.push(Reg::PSW);
+            effects.push(Reg::CSP);
+            effects.push_ip();
+            effects.write(Reg::CSP);
+            roles[0] = (false, false);
+        },
+        // Not MAC ops, they move the accumulator or data out
+        (_, "costore") => roles[0] = (false, true),
+        (_, "comov") => roles[0] = (false, true),
+        (OpKind::Call, _) => effects.push_ip(),
+        (OpKind::Push, _) => {
+            if let Some(Operand::Register(reg)) = op(0) {
+                effects.push(reg);
+            }
+            roles[0] = (false, false);
+        },
+        (OpKind::Pop, _) => {
+            if let Some(Operand::Register(reg)) = op(0) {
+                effects.pop(reg);
+            }
+            roles[0] = (false, false);
+        },
+        (OpKind::Mov, _) => roles[0] = (false, true),
+        (OpKind::Add, _) | (OpKind::Sub, _) | (OpKind::And, _) | (OpKind::Or, _) | (OpKind::Xor, _) |
+        (OpKind::Cpl, _) | (OpKind::Shl, _) | (OpKind::Shr, _) | (OpKind::Rol, _) | (OpKind::Ror, _) => roles[0] = (true, true),
+        _ => {}
+    }
+
+    for (index, (read, write)) in roles.iter().enumerate() {
+        let operand = match op(index) {
+            None => continue,
+            // jmpi and calli go to the address in the GPR rather than reading through it
+            Some(Operand::Indirect(reg)) if isn.op_type.kind == OpKind::CJmp || isn.op_type.kind == OpKind::Call => {
+                effects.read(reg);
+                continue;
+            },
+            Some(operand) => operand
+        };
+
+        effects.operand(&operand, width(index), extended, *read, *write);
+    }
+
+    if effects.flags.iter().any(|flag| *flag != FlagEffect::Unchanged) {
+        effects.write(Reg::PSW);
+    }
+
+    effects
+}
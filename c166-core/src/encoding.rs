@@ -19,65 +19,63 @@ use std::convert::TryFrom;
 
 use byteorder::ByteOrder;
 use byteorder::LittleEndian;
-use num_traits::{FromPrimitive, ToPrimitive};
 
+use ::error::{DecodeError, EncodeError};
 use ::instruction::*;
+use ::mac::*;
 use ::reg::*;
 
-#[allow(non_camel_case_types)]
-#[derive(Debug)]
-pub enum EncodingType {
-    NO_ARGS2,
-    NO_ARGS4,
-    op_d7,
-    op_dc,
-    op_d1, // atomic_extr
-    bitoff8_mask8_data8,
-    bitaddr8_bitaddr8_bit4_bit4,
-    bitaddr8_rel8_bit4_0,
-    bitopcode4_e_bitaddr8,
-    bitopcode4_f_bitaddr8,
-    cond4_0_mem16,
-    cond4_reg4,
-    condopcode4_d_rel8s,
-    reg8_mem16,
-    reg8_data8_nop8,
-    reg4_or_data3,
-    _0_reg4_mem16,
-    _f_reg4_mem16,
-    _f_reg4_data16,
-    rel8s,
-    reg4_data4,
-    reg4_0,
-    reg4_dup,
-    reg4_reg4,
-    reg4_reg4_data16,
-    reg8,
-    reg8_data16,
-    seg8_mem16,
-    trap7,
-}
+// EncodingType is generated by build.rs from the [[encoding]] entries of isa/c166.toml
+include!(concat!(env!("OUT_DIR"), "/encoding_type.rs"));
 
 pub struct Encoding<'a> {
     pub name : &'static str,
     pub length : i32,
-    pub encode : fn(&Instruction, &InstructionArguments) -> Result<Vec<u8>, &'static str>,
-    pub decode : fn(&'a Instruction, &[u8]) -> Result<InstructionArguments, &'static str>
+    pub encode : fn(&Instruction, &InstructionArguments) -> Result<Vec<u8>, EncodeError>,
+    pub decode : fn(&'a Instruction, &[u8]) -> Result<InstructionArguments, DecodeError>
 }
 
-fn get_reg_op_position<'a>(isn: &'a Instruction) -> Option<OperandType> {
-    match isn.op1.as_ref().unwrap() {
-        OperandType::Indirect(_) |
-        OperandType::IndirectPostIncrement(_) |
-        OperandType::IndirectPreDecrement(_) |
-        OperandType::IndirectAndImmediate(_) |
-        OperandType::ByteRegister(_) |
-        OperandType::WordRegister(_) => isn.op1,
-        _ => isn.op2
+// Every encoding has a fixed length, anything short of that is a truncated instruction
+fn require_length(buf: &[u8], length: usize) -> Result<(), DecodeError> {
+    match buf.len() < length {
+        true => Err(DecodeError::Incomplete { needed: length - buf.len() }),
+        false => Ok(())
+    }
+}
+
+fn invalid_encoding(isn: &Instruction, offset: usize, reason: &'static str) -> DecodeError {
+    DecodeError::InvalidEncoding { opcode: isn.id, encoding: isn.encoding, offset: offset, reason: reason }
+}
+
+fn invalid_decoded_operand(isn: &Instruction, operand: u8, offset: usize, reason: &'static str) -> DecodeError {
+    DecodeError::InvalidOperand { opcode: isn.id, encoding: isn.encoding, operand: operand, offset: offset, reason: reason }
+}
+
+fn get_op_type(isn: &Instruction, operand: u8) -> Result<OperandType, DecodeError> {
+    let op_type = match operand {
+        1 => isn.op1,
+        2 => isn.op2,
+        _ => isn.op3,
+    };
+
+    op_type.ok_or(invalid_decoded_operand(isn, operand, 0, "Instruction doesn't take this operand"))
+}
+
+// Returns the register operand of a two operand instruction along with its position in the asm statement
+fn get_reg_op_position<'a>(isn: &'a Instruction) -> Result<(u8, OperandType), DecodeError> {
+    match isn.op1 {
+        Some(op_type @ OperandType::Indirect(_)) |
+        Some(op_type @ OperandType::IndirectPostIncrement(_)) |
+        Some(op_type @ OperandType::IndirectPreDecrement(_)) |
+        Some(op_type @ OperandType::IndirectAndImmediate(_)) |
+        Some(op_type @ OperandType::ByteRegister(_)) |
+        Some(op_type @ OperandType::WordRegister(_)) => Ok((1, op_type)),
+        _ => Ok((2, get_op_type(isn, 2)?))
     }
 }
 
-fn get_reg4<'a>(op_type: &'a OperandType, reg0: u8, reg1: u8) -> Operand {
+// Register nibbles always live in the second byte of the instruction
+fn get_reg4<'a>(isn: &Instruction, op_type: &'a OperandType, operand: u8, reg0: u8, reg1: u8) -> Result<Operand, DecodeError> {
     // Position in the encoded op, not in the asm statement
     let position: u8 = match &op_type {
         OperandType::Indirect(r) |
@@ -86,23 +84,214 @@ fn get_reg4<'a>(op_type: &'a OperandType, reg0: u8, reg1: u8) -> Operand {
         OperandType::ByteRegister(r) |
         OperandType::WordRegister(r) |
         OperandType::IndirectAndImmediate(r) => *r,
-        _ => unreachable!(),
+        _ => return Err(invalid_decoded_operand(isn, operand, 1, "Operand is not a GPR"))
     };
 
     let register = match position {
         0 => reg0,
         1 => reg1,
-        _ => unreachable!(),
+        _ => return Err(invalid_decoded_operand(isn, operand, 1, "GPR position must be 0 or 1"))
+    };
+
+    let gpr = |reg_type: &OperandType| {
+        Reg::from_reg4(register, reg_type).map_err(|_| invalid_decoded_operand(isn, operand, 1, "Invalid GPR"))
     };
 
     match &op_type {
-        OperandType::Indirect(r)                => Operand::Indirect(Reg::from_reg4(register, &OperandType::WordRegister(*r)).unwrap()),
-        OperandType::IndirectPreDecrement(r)    => Operand::IndirectPreDecrement(Reg::from_reg4(register, &OperandType::WordRegister(*r)).unwrap()),
-        OperandType::IndirectPostIncrement(r)   => Operand::IndirectPostIncrement(Reg::from_reg4(register, &OperandType::WordRegister(*r)).unwrap()),
+        OperandType::Indirect(r)                => Ok(Operand::Indirect(gpr(&OperandType::WordRegister(*r))?)),
+        OperandType::IndirectPreDecrement(r)    => Ok(Operand::IndirectPreDecrement(gpr(&OperandType::WordRegister(*r))?)),
+        OperandType::IndirectPostIncrement(r)   => Ok(Operand::IndirectPostIncrement(gpr(&OperandType::WordRegister(*r))?)),
         OperandType::ByteRegister(_) |
-        OperandType::WordRegister(_)            => Operand::Register(Reg::from_reg4(register, &op_type).unwrap()),
-        OperandType::IndirectAndImmediate(r)    => Operand::IndirectAndImmediate(Reg::from_reg4(register, &OperandType::WordRegister(*r)).unwrap(), 0),
-        _ => unreachable!(),
+        OperandType::WordRegister(_)            => Ok(Operand::Register(gpr(&op_type)?)),
+        OperandType::IndirectAndImmediate(r)    => Ok(Operand::IndirectAndImmediate(gpr(&OperandType::WordRegister(*r))?, 0)),
+        _ => Err(invalid_decoded_operand(isn, operand, 1, "Operand is not a GPR"))
+    }
+}
+
+fn get_condition(isn: &Instruction, operand: u8, offset: usize, condition: u8) -> Result<OpCondition, DecodeError> {
+    OpCondition::try_from(condition).map_err(|_| invalid_decoded_operand(isn, operand, offset, "Invalid condition code"))
+}
+
+fn get_operand<'b>(isn: &Instruction, args: &'b InstructionArguments, operand: u8) -> Result<&'b Operand, EncodeError> {
+    let op = match operand {
+        1 => args.op1.as_ref(),
+        2 => args.op2.as_ref(),
+        _ => args.op3.as_ref(),
+    };
+
+    op.ok_or(EncodeError::MissingOperand { opcode: isn.id, encoding: isn.encoding, operand: operand })
+}
+
+fn invalid_operand(isn: &Instruction, operand: u8, reason: &'static str) -> EncodeError {
+    EncodeError::InvalidOperand { opcode: isn.id, encoding: isn.encoding, operand: operand, reason: reason }
+}
+
+fn invalid_register(isn: &Instruction, operand: u8, reg: &Reg) -> EncodeError {
+    EncodeError::InvalidRegister { opcode: isn.id, encoding: isn.encoding, operand: operand, reg: *reg }
+}
+
+fn out_of_range(isn: &Instruction, operand: u8, value: u16, max: u16) -> EncodeError {
+    EncodeError::OutOfRange { opcode: isn.id, encoding: isn.encoding, operand: operand, value: value as u32, max: max as u32 }
+}
+
+fn encode_reg4(isn: &Instruction, operand: u8, reg: &Reg) -> Result<u8, EncodeError> {
+    reg.to_reg4().map_err(|_| invalid_register(isn, operand, reg))
+}
+
+fn encode_reg8(isn: &Instruction, operand: u8, reg: &Reg) -> Result<u8, EncodeError> {
+    reg.to_reg8().map_err(|_| invalid_register(isn, operand, reg))
+}
+
+// #irange2 is written as 1..=4 but stored as 0..=3
+fn encode_irange(isn: &Instruction, operand: u8, value: u16) -> Result<u8, EncodeError> {
+    match value {
+        1..=4 => Ok((value - 1) as u8),
+        _ => Err(out_of_range(isn, operand, value, 4))
+    }
+}
+
+// The ext* and atomic/extr ops share an opcode, the mnemonic picks the sub-op
+fn get_sub_mnemonic<'b>(isn: &'b Instruction, args: &'b InstructionArguments) -> &'b str {
+    match args.mnemonic.as_ref() {
+        Some(mnemonic) => mnemonic.as_str(),
+        None => isn.mnemonic
+    }
+}
+
+fn invalid_mnemonic(isn: &Instruction, args: &InstructionArguments) -> EncodeError {
+    EncodeError::InvalidMnemonic { opcode: isn.id, encoding: isn.encoding, mnemonic: get_sub_mnemonic(isn, args).to_string() }
+}
+
+// Pulls the short register address and the 16-bit value out of a two operand instruction, the operands
+// may be given in either order.  get_value returns None for operands that aren't the value we're after.
+fn get_reg8_and_value(isn: &Instruction, args: &InstructionArguments, get_value: fn(&Operand) -> Option<u16>) -> Result<(u8, u16), EncodeError> {
+    let mut reg: Option<u8> = None;
+    let mut value: Option<u16> = None;
+
+    for operand in 1..=2 {
+        match get_operand(isn, args, operand)? {
+            Operand::Register(r) => {
+                reg = Some(encode_reg8(isn, operand, r)?);
+            },
+            op @ _ => match get_value(op) {
+                Some(v) => value = Some(v),
+                None => return Err(invalid_operand(isn, operand, "Expected a register or a value"))
+            }
+        }
+    }
+
+    match (reg, value) {
+        (Some(reg), Some(value)) => Ok((reg, value)),
+        (None, _) => Err(invalid_operand(isn, 1, "One operand must be a register")),
+        (_, None) => Err(invalid_operand(isn, 2, "One operand must be a value")),
+    }
+}
+
+fn immediate_value(op: &Operand) -> Option<u16> {
+    match op {
+        Operand::Immediate(value, _width) => Some(*value),
+        _ => None
+    }
+}
+
+fn memory_value(op: &Operand) -> Option<u16> {
+    match op {
+        Operand::Mem16(value) => Some(*value),
+        _ => None
+    }
+}
+
+fn code_address_value(op: &Operand) -> Option<u16> {
+    match op {
+        Operand::CodeAddr16(value) => Some(*value),
+        _ => None
+    }
+}
+
+// The CoXXX ops share their opcodes, the mnemonic picks the MAC operation
+fn get_mac_op(isn: &Instruction, args: &InstructionArguments, shape: Option<MacShape>) -> Result<&'static MacOp, EncodeError> {
+    match MacOp::from_mnemonic(get_sub_mnemonic(isn, args)) {
+        Some(mac_op) if shape.is_none() || shape == Some(mac_op.shape) => Ok(mac_op),
+        _ => Err(invalid_mnemonic(isn, args))
+    }
+}
+
+fn decode_mac_op(isn: &Instruction, sub_op: u8, shape: Option<MacShape>) -> Result<&'static MacOp, DecodeError> {
+    match MacOp::from_sub_op(sub_op) {
+        Some(mac_op) if shape.is_none() || shape == Some(mac_op.shape) => Ok(mac_op),
+        Some(_) => Err(invalid_encoding(isn, 2, "MAC operation doesn't take these operands")),
+        None => Err(invalid_encoding(isn, 2, "Unknown MAC operation"))
+    }
+}
+
+// Returns the pointer register's number and the ⊗ nibble of a [Rw⊗] operand
+fn encode_gpr_pointer(isn: &Instruction, args: &InstructionArguments, operand: u8) -> Result<(u8, u8), EncodeError> {
+    match pointer_mode(get_operand(isn, args, operand)?) {
+        Some((pointer, mode)) if index_number(pointer).is_none() => Ok((encode_reg4(isn, operand, &pointer)?, mode.to_nibble())),
+        _ => Err(invalid_operand(isn, operand, "Operand must be [Rw⊗]"))
+    }
+}
+
+// As encode_gpr_pointer for an [IDXi⊗] operand
+fn encode_index_pointer(isn: &Instruction, args: &InstructionArguments, operand: u8) -> Result<(u8, u8), EncodeError> {
+    let (pointer, mode) = pointer_mode(get_operand(isn, args, operand)?)
+        .ok_or(invalid_operand(isn, operand, "Operand must be [IDXi⊗]"))?;
+
+    match index_number(pointer) {
+        Some(number) => Ok((number, mode.to_nibble())),
+        None => Err(invalid_register(isn, operand, &pointer))
+    }
+}
+
+fn encode_mac_reg(isn: &Instruction, args: &InstructionArguments, operand: u8) -> Result<u8, EncodeError> {
+    match get_operand(isn, args, operand)? {
+        Operand::Register(reg) => mac_reg_number(*reg).ok_or(invalid_register(isn, operand, reg)),
+        _ => Err(invalid_operand(isn, operand, "Operand must be a MAC register"))
+    }
+}
+
+fn encode_gpr(isn: &Instruction, args: &InstructionArguments, operand: u8) -> Result<u8, EncodeError> {
+    match get_operand(isn, args, operand)? {
+        Operand::Register(reg) if reg.is_word_register() => encode_reg4(isn, operand, reg),
+        _ => Err(invalid_operand(isn, operand, "Operand must be a word GPR"))
+    }
+}
+
+fn decode_pointer(isn: &Instruction, operand: u8, offset: usize, pointer: Reg, nibble: u8) -> Result<Operand, DecodeError> {
+    match PointerMode::from_nibble(nibble) {
+        Some(mode) => Ok(pointer_operand(pointer, mode)),
+        None => Err(invalid_decoded_operand(isn, operand, offset, "Invalid pointer modification"))
+    }
+}
+
+fn decode_gpr(isn: &Instruction, operand: u8, offset: usize, nibble: u8) -> Result<Reg, DecodeError> {
+    Reg::from_reg4(nibble, &OperandType::WordRegister(0)).map_err(|_| invalid_decoded_operand(isn, operand, offset, "Invalid GPR"))
+}
+
+fn decode_index_pointer(isn: &Instruction, operand: u8, nibble: u8) -> Result<Reg, DecodeError> {
+    index_pointer(nibble).ok_or(invalid_decoded_operand(isn, operand, 1, "Index pointer must be IDX0 or IDX1"))
+}
+
+fn decode_mac_reg(isn: &Instruction, operand: u8, nibble: u8) -> Result<Operand, DecodeError> {
+    match mac_reg(nibble) {
+        Some(reg) => Ok(Operand::Register(reg)),
+        None => Err(invalid_decoded_operand(isn, operand, 1, "Invalid MAC register"))
+    }
+}
+
+// What the encoder fills the don't care byte of reg8_data8_nop8 with
+const NOP8: u8 = 0x42;
+
+// Checks the fields the decoders skip over, which the CPU ignores but the encoders always write the
+// same way.  Anything else there is more likely data or damage than code.  buf must already have
+// decoded as isn.
+pub fn check_reserved(isn: &Instruction, buf: &[u8]) -> Result<(), DecodeError> {
+    match isn.encoding {
+        EncodingType::reg8_data8_nop8 if buf[3] != NOP8 => Err(invalid_encoding(isn, 3, "Fourth byte isn't the usual filler")),
+        EncodingType::trap7 if buf[1] & 0b00000001 != 0 => Err(invalid_encoding(isn, 1, "Lowest bit must be zero")),
+        // Only atomic (00) and extr (10) are defined
+        EncodingType::op_d1 if buf[1] & 0b01000000 != 0 => Err(invalid_encoding(isn, 1, "Undefined sub-op")),
+        _ => Ok(())
     }
 }
 
@@ -111,24 +300,26 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
         match encoding_type.clone() {
             EncodingType::NO_ARGS2 => {
                 Encoding {
-                    name: "NO_ARGS2",
-                    length: 2,
-                    encode: |isn, _args| {
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
+                    encode: |isn, args| {
                         match isn.id {
                             0xDB => Ok(vec![0xDB, 0x00]),
                             0xFB => Ok(vec![0xFB, 0x88]),
                             0xCB => Ok(vec![0xCB, 0x00]),
                             0xCC => Ok(vec![0xCC, 0x00]),
-                            _ => Err("EncodingType::NO_ARGS2: Invalid OP")
+                            _ => Err(invalid_mnemonic(isn, args))
                         }
                     },
-                    decode: |_isn, buf| {
+                    decode: |isn, buf| {
+                        require_length(buf, 2)?;
+
                         match &buf[0..2] {
                             [0xDB, 0x00] |
                             [0xFB, 0x88] |
                             [0xCB, 0x00] |
                             [0xCC, 0x00] => Ok(InstructionArguments {..Default::default()}),
-                            _ => Err("EncodingType::NO_ARGS2: Invalid instruction")
+                            _ => Err(invalid_encoding(isn, 1, "Second byte doesn't match the opcode"))
                         }
                     }
                 }
@@ -136,9 +327,9 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::NO_ARGS4 => {
                 Encoding {
-                    name: "NO_ARGS4",
-                    length: 4,
-                    encode: |isn, _args| {
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
+                    encode: |isn, args| {
                         match isn.id {
                             0xB7 => Ok(vec![0xB7, 0x48, 0xB7, 0xB7]),
                             0xA7 => Ok(vec![0xA7, 0x58, 0xA7, 0xA7]),
@@ -146,10 +337,12 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
                             0xB5 => Ok(vec![0xB5, 0x4A, 0xB5, 0xB5]),
                             0xA5 => Ok(vec![0xA5, 0x5A, 0xA5, 0xA5]),
                             0x87 => Ok(vec![0x87, 0x78, 0x87, 0x87]),
-                            _ => Err("EncodingType::NO_ARGS4: Invalid OP")
+                            _ => Err(invalid_mnemonic(isn, args))
                         }
                     },
-                    decode: |_isn, buf| {
+                    decode: |isn, buf| {
+                        require_length(buf, 4)?;
+
                         match &buf[0..4] {
                             [0xB7, 0x48, 0xB7, 0xB7] |
                             [0xA7, 0x58, 0xA7, 0xA7] |
@@ -157,7 +350,7 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
                             [0xB5, 0x4A, 0xB5, 0xB5] |
                             [0xA5, 0x5A, 0xA5, 0xA5] |
                             [0x87, 0x78, 0x87, 0x87] => Ok(InstructionArguments {..Default::default()}),
-                            _ => Err("EncodingType::NO_ARGS4: Invalid instruction")
+                            _ => Err(invalid_encoding(isn, 1, "Trailing bytes don't match the opcode"))
                         }
                     }
                 }
@@ -165,38 +358,38 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::reg4_data4 => {
                 Encoding {
-                    name: "In",
-                    length: 2,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let data: u16 = match args.op2.expect("Second operand required for EncodingType::reg4_data4") {
-                            Operand::Immediate(data, _width) => data,
-                            _ => return Err("EncodingType::reg4_data4: Second operand must be #data4")
+                        let data: u16 = match get_operand(isn, args, 2)? {
+                            Operand::Immediate(data, _width) => *data,
+                            _ => return Err(invalid_operand(isn, 2, "Second operand must be #data4"))
                         };
 
                         if data > 0x0F {
-                            return Err("EncodingType::reg4_data4: #data4 must be 0x00..=0x0F");
+                            return Err(out_of_range(isn, 2, data, 0x0F));
                         }
 
-                        let reg: Reg = match args.op1.expect("EncodingType:::reg4_data4: First operand required") {
-                            Operand::Register(reg) => reg,
-                            _ => return Err("EncodingType:::reg4_data4: First operand must be a register")
+                        let reg4: u8 = match get_operand(isn, args, 1)? {
+                            Operand::Register(reg) => encode_reg4(isn, 1, reg)?,
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be a register"))
                         };
 
-                        let reg4: u8 = reg.to_reg4().expect("EncodingType:::reg4_data4: Register must be GPR");
-
                         let byte: u8 = ((data as u8) << 4) | (reg4 & 0b00001111);
 
                         Ok(vec![isn.id, byte])
                     },
                     decode: |isn, buf| {
+                        require_length(buf, 2)?;
+
                         let register0 : u8 = buf[1] & 0b00001111;
                         let data : u8 = (buf[1] & 0b11110000) >> 4;
 
-                        let reg_op = get_reg_op_position(&isn);
+                        let (reg_pos, reg_op) = get_reg_op_position(&isn)?;
 
-                        let reg = get_reg4(reg_op.as_ref().unwrap(), register0, register0);
+                        let reg = get_reg4(isn, &reg_op, reg_pos, register0, register0)?;
 
-                        if reg_op == isn.op2 {
+                        if reg_pos == 2 {
                             Ok(InstructionArguments {
                                 op1: Some(Operand::Immediate(data as u16, 4)),
                                 op2: Some(reg),
@@ -215,25 +408,27 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::_0_reg4_mem16 => {
                 Encoding {
-                    name: "_0n_MM_MM",
-                    length: 4,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let reg0 = match args.op1.as_ref().unwrap() {
+                        let reg0 = match get_operand(isn, args, 1)? {
                             Operand::Indirect(reg) |
                             Operand::IndirectPostIncrement(reg) |
                             Operand::IndirectPreDecrement(reg) |
-                            Operand::Register(reg) => reg.to_reg4().expect("EncodingType::_0_reg4_mem16: Invalid GPR"),
-                            _ => return Err("EncodingType::_0_reg4_mem16: Invalid Operand 1")
+                            Operand::Register(reg) => encode_reg4(isn, 1, reg)?,
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be indirect or register"))
                         };
 
-                        let mem = match args.op2.as_ref().unwrap() {
-                            Operand::Direct(direct, _width) => direct,
-                            _ => return Err("EncodingType::_0_reg4_mem16: Second operand must be memory")
+                        let mem = match get_operand(isn, args, 2)? {
+                            Operand::Mem16(direct) => direct,
+                            _ => return Err(invalid_operand(isn, 2, "Second operand must be memory"))
                         };
 
                         Ok(vec![isn.id, reg0 & 0x00FF, (mem & 0x00FF) as u8, ((mem & 0xFF00) >> 8) as u8])
                     },
                     decode: |isn, buf| {
+                        require_length(buf, 4)?;
+
                         match buf[1] & 0b11110000 {
                             0 => {
                                 let register0 : u8 = buf[1] & 0b00001111;
@@ -241,24 +436,24 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
                                 let slice = &buf[2..4];
                                 let memory : u16 = LittleEndian::read_u16(slice);
 
-                                let reg_op = get_reg_op_position(&isn);
-                                let op1 = get_reg4(&reg_op.unwrap(), register0, register0);
+                                let (reg_pos, reg_op) = get_reg_op_position(&isn)?;
+                                let op1 = get_reg4(isn, &reg_op, reg_pos, register0, register0)?;
 
-                                if reg_op == isn.op1 {
+                                if reg_pos == 1 {
                                     Ok(InstructionArguments {
                                         op1: Some(op1),
-                                        op2: Some(Operand::Direct(memory, 16)),
+                                        op2: Some(Operand::Mem16(memory)),
                                         ..Default::default()
                                     })
                                 } else {
                                     Ok(InstructionArguments {
-                                        op1: Some(Operand::Direct(memory, 16)),
+                                        op1: Some(Operand::Mem16(memory)),
                                         op2: Some(op1),
                                         ..Default::default()
                                     })
                                 }
                             },
-                            _ => Err("EncodingType::_0_reg4_mem16: Invalid instruction")
+                            _ => Err(invalid_encoding(isn, 1, "Upper nibble must be zero"))
                         }
                     }
                 }
@@ -266,35 +461,32 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::condopcode4_d_rel8s => {
                 Encoding {
-                    name: "cc_rr",
-                    length: 2,
-                    encode: |_isn, args| {
-                        let cond = match args.op1.unwrap() {
-                            Operand::Condition(cond) => cond.to_u8().unwrap(),
-                            _ => unreachable!()
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
+                    encode: |isn, args| {
+                        let cond = match get_operand(isn, args, 1)? {
+                            Operand::Condition(cond) => *cond as u8,
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be a condition code"))
                         };
 
-                        let rel = match args.op2.unwrap() {
-                            Operand::Direct(d, _width) => {
-                                if d > <i8>::max_value() as u16 {
-                                    return Err("EncodingType::condopcode4_d_rel8s: Relative values must fit into an i8 type")
-                                }
-                                d as u8
-                            },
-                            _ => return Err("EncodingType::condopcode4_d_rel8s: Second operand must be a relative address")
+                        let rel = match get_operand(isn, args, 2)? {
+                            Operand::Rel8(rel) => *rel as u8,
+                            _ => return Err(invalid_operand(isn, 2, "Second operand must be a relative address"))
                         };
 
-                        Ok(vec![(cond & 0x0F) << 4 | 0x0D, rel / 2])
+                        Ok(vec![(cond & 0x0F) << 4 | 0x0D, rel])
                     },
-                    decode: |_isn, buf| {
+                    decode: |isn, buf| {
+                        require_length(buf, 2)?;
+
                         let condition : u8 = (buf[0] & 0b11110000) >> 4;
                         let relative : u8 = buf[1];
 
-                        let op_condition : OpCondition = try!(OpCondition::try_from(condition));
+                        let op_condition : OpCondition = get_condition(isn, 1, 0, condition)?;
 
                         Ok(InstructionArguments {
                             op1: Some(Operand::Condition(op_condition)),
-                            op2: Some(Operand::Direct(relative as u16, 8)),
+                            op2: Some(Operand::Rel8(relative as i8)),
                             ..Default::default()
                         })
                    }
@@ -303,53 +495,47 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::op_d7 => {
                 Encoding {
-                    name: "ext_d7",
-                    length: 4,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let irange = match args.op2.unwrap() {
-                            Operand::Immediate(immed, _width) => (immed - 1) as u8,
-                            _ => unreachable!()
+                        let irange = match get_operand(isn, args, 2)? {
+                            Operand::Immediate(immed, _width) => encode_irange(isn, 2, *immed)?,
+                            _ => return Err(invalid_operand(isn, 2, "Second operand must be #irange2"))
                         };
 
-                        match args.mnemonic.as_ref().unwrap().as_str() {
-                            "extp"  => {
-                                let page = match args.op1.unwrap() {
-                                    Operand::Immediate(immed, _width) => immed,
-                                    _ => return Err("EncodingType::ext_d7: extp requires a page operand")
-                                };
-
-                                Ok(vec![isn.id, (0b01 << 6) | (irange << 4), (page & 0xFF) as u8, ((page & 0b1100000000) >> 8) as u8 ])
-                            },
-                            "extpr" => {
-                                let page = match args.op1.unwrap() {
-                                    Operand::Immediate(immed, _width) => immed,
-                                    _ => return Err("EncodingType::ext_d7: extpr requires a page operand")
-                                };
-
-                                Ok(vec![isn.id, (0b11 << 6) | (irange << 4), (page & 0xFF) as u8, ((page & 0b1100000000) >> 8) as u8 ])
+                        let mnemonic = get_sub_mnemonic(isn, args);
+                        let immed = match (mnemonic, get_operand(isn, args, 1)?) {
+                            ("extp", Operand::Page10(page)) |
+                            ("extpr", Operand::Page10(page)) => {
+                                if *page > 0x3FF {
+                                    return Err(out_of_range(isn, 1, *page, 0x3FF));
+                                }
+                                *page
                             },
-                            "exts"  => {
-                                let seg = match args.op1.unwrap() {
-                                    Operand::Immediate(immed, _width) => immed as u8,
-                                    _ => return Err("EncodingType::ext_d7: exts requires an immediate segment operand")
-                                };
-
-                                Ok(vec![isn.id, (0b00 << 6) | (irange << 4), seg, 0x00 ])
+                            ("exts", Operand::Immediate(seg, _width)) |
+                            ("extsr", Operand::Immediate(seg, _width)) => {
+                                if *seg > <u8>::max_value() as u16 {
+                                    return Err(out_of_range(isn, 1, *seg, <u8>::max_value() as u16));
+                                }
+                                *seg
                             },
-                            "extsr" => {
-                                let seg = match args.op1.unwrap() {
-                                    Operand::Immediate(immed, _width) => immed as u8,
-                                    _ => return Err("EncodingType::ext_d7: extsr requires an immediate segment operand")
-                                };
+                            ("extp", _) | ("extpr", _) => return Err(invalid_operand(isn, 1, "First operand must be #pag10")),
+                            ("exts", _) | ("extsr", _) => return Err(invalid_operand(isn, 1, "First operand must be #seg8")),
+                            _ => return Err(invalid_mnemonic(isn, args))
+                        };
 
-                                Ok(vec![isn.id, (0b10 << 6) as u8 | (irange << 4), seg, 0x00 ])
-                            },
-                            _ => return Err("EncodingType::ext_d7: Invalid mnemonic. Encoding only applies to extp/extpr and exts/extsr")
+                        match mnemonic {
+                            "extp"  => Ok(vec![isn.id, (0b01 << 6) | (irange << 4), (immed & 0xFF) as u8, ((immed & 0b1100000000) >> 8) as u8 ]),
+                            "extpr" => Ok(vec![isn.id, (0b11 << 6) | (irange << 4), (immed & 0xFF) as u8, ((immed & 0b1100000000) >> 8) as u8 ]),
+                            "exts"  => Ok(vec![isn.id, (0b00 << 6) | (irange << 4), immed as u8, 0x00 ]),
+                            _       => Ok(vec![isn.id, (0b10 << 6) as u8 | (irange << 4), immed as u8, 0x00 ]),
                         }
                     },
-                    decode: |_isn, buf| {
+                    decode: |isn, buf| {
+                        require_length(buf, 4)?;
+
                         if (buf[1] & 0b00001111) != 0 {
-                            return Err("EncodingType::ext_d7: Instruction was invalid")
+                            return Err(invalid_encoding(isn, 1, "Lower nibble must be zero"))
                         }
 
                         let sub_op : u8 = (buf[1] & 0b11000000) >> 6;
@@ -358,17 +544,11 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
                             1 => "extp",
                             3 => "extpr",
                             0 => "exts",
-                            2 => "extsr",
-                            _ => "InvalidSubOp"
+                            _ => "extsr",
                         };
 
                         let irange : u8 = ((buf[1] & 0b00110000) >> 4) + 1;
 
-                        if irange > 4 {
-                            // This should be unreachable, but...
-                            return Err("EncodingType::ext_d7: Invalid instruction, irange must be 0..=4")
-                        }
-
                         let mut values = InstructionArguments {
                             mnemonic: Some(mnem.to_string()),
                             sub_op: Some(sub_op),
@@ -376,27 +556,26 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
                             ..Default::default()
                         };
 
-                        match (buf[1] & 0b11000000) >> 6 {
+                        match sub_op {
                             0b10 | 0b00 => {
                                 // Seg op
                                 match buf[3] {
                                     0x00 => {
                                         values.op1 = Some(Operand::Immediate(buf[2] as u16, 8));
                                     },
-                                    _    => return Err("EncodingType::ext_d7: Invalid instruction. Fourth byte must be zero.")
+                                    _    => return Err(invalid_encoding(isn, 3, "Fourth byte must be zero"))
                                 }
                             },
-                            0b11 | 0b01 => {
+                            _ => {
                                 // Page is 10 bits so the top 6 bits of byte 3 need to be zero
                                 match (buf[3] & 0b11111100) >> 2 {
                                     0x00 => {
                                         let page : u16 = ((buf[3] & 0b00000011) as u16) << 8 | buf[2] as u16;
-                                        values.op1 = Some(Operand::Immediate(page, 10));
+                                        values.op1 = Some(Operand::Page10(page));
                                     },
-                                    _    => return Err("EncodingType::ext_d7: Invalid instruction. Top 6 bits of fourth byte must be zero")
+                                    _    => return Err(invalid_encoding(isn, 3, "Top 6 bits of fourth byte must be zero"))
                                 }
                             },
-                            _ => unreachable!()
                         }
 
                         Ok(values)
@@ -406,42 +585,43 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::op_dc => {
                 Encoding {
-                    name: "ext_dc",
-                    length: 2,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let reg = match args.op1.unwrap() {
-                            Operand::Register(reg) => reg.to_reg4().unwrap(),
-                            _ => unreachable!()
+                        let reg = match get_operand(isn, args, 1)? {
+                            Operand::Register(reg) => encode_reg4(isn, 1, reg)?,
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be a register"))
                         };
 
-                        let irange = match args.op2.unwrap() {
-                            Operand::Immediate(immed, _width) => (immed - 1) as u8,
-                            _ => unreachable!()
+                        let irange = match get_operand(isn, args, 2)? {
+                            Operand::Immediate(immed, _width) => encode_irange(isn, 2, *immed)?,
+                            _ => return Err(invalid_operand(isn, 2, "Second operand must be #irange2"))
                         };
 
-                        match args.mnemonic.as_ref().unwrap().as_str() {
+                        match get_sub_mnemonic(isn, args) {
                             "extp"  => Ok(vec![isn.id, (0b01 << 6) | (irange << 4) | reg]),
                             "extpr" => Ok(vec![isn.id, (0b11 << 6) | (irange << 4) | reg]),
                             "exts"  => Ok(vec![isn.id, (0b00 << 6) | (irange << 4) | reg]),
                             "extsr" => Ok(vec![isn.id, (0b10 << 6) | (irange << 4) | reg]),
-                            _ => return Err("EncodingType::op_dc: Invalid mnemonic. This encoding only applies to extp/extpr and exts/extsr")
+                            _ => Err(invalid_mnemonic(isn, args))
                         }
                     },
                     decode: |isn, buf| {
+                        require_length(buf, 2)?;
+
                         let sub_op : u8 = (buf[1] & 0b11000000) >> 6;
 
                         let mnem = match sub_op {
                             1 => "extp",
                             3 => "extpr",
                             0 => "exts",
-                            2 => "extsr",
-                            _ => "InvalidSubOp"
+                            _ => "extsr",
                         };
 
                         let irange : u8 = ((buf[1] & 0b00110000) >> 4) + 1;
 
                         let register : u8 = buf[1] & 0b00001111;
-                        let op1 = get_reg4(&isn.op1.unwrap(), register, register);
+                        let op1 = get_reg4(isn, &get_op_type(isn, 1)?, 1, register, register)?;
                         Ok(InstructionArguments {
                             mnemonic: Some(mnem.to_string()),
                             sub_op: Some(sub_op),
@@ -455,49 +635,55 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::reg4_or_data3 => {
                 Encoding {
-                    name: "data3_or_reg",
-                    length: 2,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let reg = match args.op1.unwrap() {
-                            Operand::Register(reg) => reg.to_reg4().unwrap(),
-                            _ => return Err("EncodingType::reg4_or_data3: First operand must be a register")
+                        let reg = match get_operand(isn, args, 1)? {
+                            Operand::Register(reg) => encode_reg4(isn, 1, reg)?,
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be a register"))
                         };
 
-                        match args.op2.unwrap() {
+                        match get_operand(isn, args, 2)? {
                             Operand::Immediate(immed, _width) => {
-                                Ok(vec![isn.id, (reg << 4) | immed as u8])
+                                if *immed > 0b111 {
+                                    return Err(out_of_range(isn, 2, *immed, 0b111));
+                                }
+                                Ok(vec![isn.id, (reg << 4) | *immed as u8])
                             },
                             Operand::Indirect(reg1) => {
-                                let reg_id = reg1.to_reg4().unwrap();
+                                let reg_id = encode_reg4(isn, 2, reg1)?;
                                 if reg_id > 3 {
-                                    return Err("EncodingType::reg4_or_data3: This op only works with GPR 0-3, should catch this in the parser")
+                                    // This op only works with GPR 0-3
+                                    return Err(invalid_register(isn, 2, reg1))
                                 }
                                 Ok(vec![isn.id, (reg << 4) | (0b10 << 2) | reg_id & 0b11])
                             },
                             Operand::IndirectPostIncrement(reg1) => {
-                                let reg_id = reg1.to_reg4().unwrap();
+                                let reg_id = encode_reg4(isn, 2, reg1)?;
                                 if reg_id > 3 {
-                                    return Err("EncodingType::reg4_or_data3: This op only works with GPR 0-3, should catch this in the parser")
+                                    return Err(invalid_register(isn, 2, reg1))
                                 }
                                 Ok(vec![isn.id, (reg << 4) | (0b11 << 2) | reg_id & 0b11])
                             },
-                            _ => return Err("EncodingType::reg4_or_data3: Second operand must be immediate, indirect, or indirect++")
+                            _ => Err(invalid_operand(isn, 2, "Second operand must be immediate, indirect, or indirect++"))
                         }
                     },
                     decode: |isn, buf| {
+                        require_length(buf, 2)?;
+
                         let register0 : u8 = (buf[1] & 0b11110000) >> 4;
                         let sub_op : u8 = (buf[1] & 0b00001100) >> 2;
 
-                        let op1 = get_reg4(&isn.op1.unwrap(), register0, register0);
+                        let op1 = get_reg4(isn, &get_op_type(isn, 1)?, 1, register0, register0)?;
 
                         let op2 = match sub_op {
                             0b10 => {
                                 let register1 : u8 = buf[1] & 0b00000011;
-                                get_reg4(&OperandType::Indirect(1), register0, register1)
+                                get_reg4(isn, &OperandType::Indirect(1), 2, register0, register1)?
                             },
                             0b11 => {
                                 let register1 : u8 = buf[1] & 0b00000011;
-                                get_reg4(&OperandType::IndirectPostIncrement(1), register0, register1)
+                                get_reg4(isn, &OperandType::IndirectPostIncrement(1), 2, register0, register1)?
                             },
                             _ => {
                                 let data : u8 = buf[1] & 0b00000111;
@@ -517,32 +703,27 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::op_d1 => {
                 Encoding {
-                    name: "atomic_extr",
-                    length: 2,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let irange = match args.op1.unwrap() {
-                            Operand::Immediate(immed, _width) => {
-                                match immed {
-                                    1..=4 => {},
-                                    _ => return Err("EncodingType::op_d1: #irange2 value must be 1..=4")
-                                };
-
-                                (immed - 1) as u8
-                            },
-                            _ => unreachable!()
+                        let irange = match get_operand(isn, args, 1)? {
+                            Operand::Immediate(immed, _width) => encode_irange(isn, 1, *immed)?,
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be #irange2"))
                         };
 
-                        match args.mnemonic.as_ref().unwrap().as_str() {
+                        match get_sub_mnemonic(isn, args) {
                             "atomic" => {
                                 Ok(vec![isn.id, 0b00000000 | (irange & 0b00000011) << 4])
                             },
                             "extr" => {
                                 Ok(vec![isn.id, 0b10000000 | (irange & 0b00000011) << 4])
                             },
-                            _ => return Err("EncodingType::op_d1: This encoding is for atomic and extr only")
+                            _ => Err(invalid_mnemonic(isn, args))
                         }
                     },
-                    decode: |_isn, buf| {
+                    decode: |isn, buf| {
+                        require_length(buf, 2)?;
+
                         match buf[1] & 0b00001111 {
                             0 => {
                                 let irange : u8 = ((buf[1] & 0b00110000) >> 4) + 1;
@@ -562,7 +743,7 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
                                 Ok(values)
                             },
-                            _ => Err("EncodingType::op_d1: Instruction was invalid")
+                            _ => Err(invalid_encoding(isn, 1, "Lower nibble must be zero"))
                         }
                     }
                 }
@@ -570,22 +751,24 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::_f_reg4_data16 => {
                 Encoding {
-                    name: "Fn_II_II",
-                    length: 4,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let reg0 = match args.op1.unwrap() {
-                            Operand::Register(reg) => reg.to_reg4().unwrap(),
-                            _ => return Err("EncodingType::_f_reg4_data16: First operand must be register")
+                        let reg0 = match get_operand(isn, args, 1)? {
+                            Operand::Register(reg) => encode_reg4(isn, 1, reg)?,
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be register"))
                         };
 
-                        let immed = match args.op2.unwrap() {
+                        let immed = match get_operand(isn, args, 2)? {
                             Operand::Immediate(immed, _width) => immed,
-                            _ => return Err("EncodingType::_f_reg4_data16: Second operand must be immediate value")
+                            _ => return Err(invalid_operand(isn, 2, "Second operand must be immediate value"))
                         };
 
                         Ok(vec![isn.id, 0xF0 | (reg0 & 0x0F), (immed & 0x00FF) as u8, ((immed & 0xFF00) >> 8) as u8 ])
                     },
                     decode: |isn, buf| {
+                        require_length(buf, 4)?;
+
                         match (buf[1] & 0b11110000) >> 4 {
                             0x0F => {
                                 let register0 : u8 = buf[1] & 0b00001111;
@@ -593,10 +776,10 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
                                 let slice = &buf[2..4];
                                 let data : u16 = LittleEndian::read_u16(slice);
 
-                                let reg_op = get_reg_op_position(&isn);
-                                let reg = get_reg4(reg_op.as_ref().unwrap(), register0, register0);
+                                let (reg_pos, reg_op) = get_reg_op_position(&isn)?;
+                                let reg = get_reg4(isn, &reg_op, reg_pos, register0, register0)?;
 
-                                if reg_op == isn.op1 {
+                                if reg_pos == 1 {
                                     Ok(InstructionArguments {
                                         op1: Some(reg),
                                         op2: Some(Operand::Immediate(data, 16)),
@@ -610,7 +793,7 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
                                     })
                                 }
                            },
-                            _ => Err("EncodingType::_f_reg4_data16: Instruction was invalid")
+                            _ => Err(invalid_encoding(isn, 1, "Upper nibble must be Fh"))
                         }
                     }
                 }
@@ -618,25 +801,27 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::_f_reg4_mem16 => {
                 Encoding {
-                    name: "Fn_MM_MM",
-                    length: 4,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let reg0 = match args.op1.as_ref().unwrap() {
+                        let reg0 = match get_operand(isn, args, 1)? {
                             Operand::Indirect(reg) |
                             Operand::IndirectPostIncrement(reg) |
                             Operand::IndirectPreDecrement(reg) |
-                            Operand::Register(reg) => reg.to_reg4().expect("EncodingType::_f_reg4_mem16: Invalid register"),
-                            _ => return Err("EncodingType::_f_reg4_mem16: First operand must be indirect or register")
+                            Operand::Register(reg) => encode_reg4(isn, 1, reg)?,
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be indirect or register"))
                         };
 
-                        let memory = match args.op2.as_ref().unwrap() {
-                            Operand::Direct(mem, _) => mem,
-                            _ => return Err("EncodingType::_f_reg4_mem16: Second operand must be direct value")
+                        let memory = match get_operand(isn, args, 2)? {
+                            Operand::Mem16(mem) => mem,
+                            _ => return Err(invalid_operand(isn, 2, "Second operand must be direct value"))
                         };
 
                         Ok(vec![isn.id, 0xF0 | reg0, (memory & 0x00FF) as u8, ((memory & 0xFF00) >> 8) as u8 ])
                     },
                     decode: |isn, buf| {
+                        require_length(buf, 4)?;
+
                         match (buf[1] & 0b11110000) >> 4 {
                             0x0F => {
                                 let register0 : u8 = buf[1] & 0b00001111;
@@ -644,24 +829,24 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
                                 let slice = &buf[2..4];
                                 let memory : u16 = LittleEndian::read_u16(slice);
 
-                                let reg_op = get_reg_op_position(&isn);
-                                let reg = get_reg4(reg_op.as_ref().unwrap(), register0, register0);
+                                let (reg_pos, reg_op) = get_reg_op_position(&isn)?;
+                                let reg = get_reg4(isn, &reg_op, reg_pos, register0, register0)?;
 
-                                if reg_op == isn.op1 {
+                                if reg_pos == 1 {
                                     Ok(InstructionArguments {
                                         op1: Some(reg),
-                                        op2: Some(Operand::Direct(memory, 16)),
+                                        op2: Some(Operand::Mem16(memory)),
                                         ..Default::default()
                                     })
                                 } else {
                                     Ok(InstructionArguments {
-                                        op1: Some(Operand::Direct(memory, 16)),
+                                        op1: Some(Operand::Mem16(memory)),
                                         op2: Some(reg),
                                         ..Default::default()
                                     })
                                 }
                             },
-                            _ => Err("EncodingType::_f_reg4_mem16: Instruction was invalid")
+                            _ => Err(invalid_encoding(isn, 1, "Upper nibble must be Fh"))
                         }
                     }
                 }
@@ -670,21 +855,27 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
             EncodingType::bitopcode4_e_bitaddr8 |
             EncodingType::bitopcode4_f_bitaddr8 => {
                 Encoding {
-                    name: "q_QQ",
-                    length: 2,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let (bitoff, bit) = match args.op1.unwrap() {
-                            Operand::BitAddr(bitoff, bit) => (bitoff, bit),
-                            _ => unreachable!()
+                        let (bitoff, bit) = match get_operand(isn, args, 1)? {
+                            Operand::BitAddr(bitoff, bit) => (*bitoff, *bit),
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be a bit address"))
                         };
 
-                        match isn.encoding {
-                            EncodingType::bitopcode4_e_bitaddr8 => Ok(vec![(bit << 4) | 0x0E, bitoff]),
-                            EncodingType::bitopcode4_f_bitaddr8 => Ok(vec![(bit << 4) | 0x0F, bitoff]),
-                            _ => unreachable!()
+                        if bit > 0x0F {
+                            return Err(out_of_range(isn, 1, bit as u16, 0x0F));
+                        }
+
+                        if isn.encoding == EncodingType::bitopcode4_e_bitaddr8 {
+                            Ok(vec![(bit << 4) | 0x0E, bitoff])
+                        } else {
+                            Ok(vec![(bit << 4) | 0x0F, bitoff])
                         }
                     },
                     decode: |_isn, buf| {
+                        require_length(buf, 2)?;
+
                         let bit0 : u8 = (buf[0] & 0b11110000) >> 4;
                         let bitoff0 : u8 = buf[1];
 
@@ -698,45 +889,41 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::bitoff8_mask8_data8 => {
                 Encoding {
-                    name: "QQ_AA_II",
-                    length: 4,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let bitoff = match args.op1.unwrap() {
-                            Operand::BitAddr(bitoff, _bit) => bitoff,
-                            Operand::Register(reg) => {
-                                let reg_short_addr = reg.to_reg8().unwrap();
-                                reg_short_addr
-                            },
-                            unknown @ _ => {
-                                eprintln!("EncodingType::bitoff8_mask8_data8: Got: {:X?}", unknown);
-                                return Err("EncodingType::bitoff8_mask8_data8: Expect op1 to be a bitaddr/bitoff or a register");
-                            }
+                        let bitoff = match get_operand(isn, args, 1)? {
+                            Operand::BitAddr(bitoff, _bit) => *bitoff,
+                            Operand::Register(reg) => encode_reg8(isn, 1, reg)?,
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be a bitaddr/bitoff or a register"))
                         };
 
-                        let mask8 = match args.op2.unwrap() {
+                        let mask8 = match get_operand(isn, args, 2)? {
                             Operand::Immediate(immed, _width) => {
-                                if immed > <u8>::max_value() as u16 {
-                                    return Err("EncodingType::bitoff8_mask8_data8: And mask must fit into an 8-bit field")
+                                if *immed > <u8>::max_value() as u16 {
+                                    return Err(out_of_range(isn, 2, *immed, <u8>::max_value() as u16))
                                 }
-                                immed as u8
+                                *immed as u8
                             },
-                            _ => return Err("EncodingType::bitoff8_mask8_data8: Second operand must be an immediate value")
+                            _ => return Err(invalid_operand(isn, 2, "Second operand must be an immediate value"))
                         };
 
-                        let data8 = match args.op3.unwrap() {
+                        let data8 = match get_operand(isn, args, 3)? {
                             Operand::Immediate(immed, _width) => {
-                                if immed > <u8>::max_value() as u16 {
-                                    return Err("EncodingType::bitoff8_mask8_data8: Or mask must fit into an 8-bit field")
+                                if *immed > <u8>::max_value() as u16 {
+                                    return Err(out_of_range(isn, 3, *immed, <u8>::max_value() as u16))
                                 }
 
-                                immed as u8
+                                *immed as u8
                             },
-                            _ => return Err("EncodingType::bitoff8_mask8_data8: Third operand must be an immediate value")
+                            _ => return Err(invalid_operand(isn, 3, "Third operand must be an immediate value"))
                         };
 
                         Ok(vec![isn.id, bitoff, mask8, data8])
                     },
                     decode: |_isn, buf| {
+                        require_length(buf, 4)?;
+
                         let mask : u8 = buf[2];
                         let data : u8 = buf[3];
                         let bitoff0 : u8 = buf[1];
@@ -753,30 +940,36 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::bitaddr8_bitaddr8_bit4_bit4 => {
                 Encoding {
-                    name: "QQ_ZZ_qz",
-                    length: 4,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let (offset0, bit0) = match args.op1.unwrap() {
-                            Operand::BitAddr(offset, bit) => (offset, bit),
-                            _ => unreachable!()
+                        let (offset0, bit0) = match get_operand(isn, args, 1)? {
+                            Operand::BitAddr(offset, bit) => (*offset, *bit),
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be a bit address"))
                         };
 
-                        let (offset1, bit1) = match args.op2.unwrap() {
-                            Operand::BitAddr(offset, bit) => (offset, bit),
-                            _ => unreachable!()
-                        };
-                        let pos = match isn.op1.unwrap() {
-                            OperandType::BitAddr(pos) => pos,
-                            _ => unreachable!()
+                        let (offset1, bit1) = match get_operand(isn, args, 2)? {
+                            Operand::BitAddr(offset, bit) => (*offset, *bit),
+                            _ => return Err(invalid_operand(isn, 2, "Second operand must be a bit address"))
                         };
 
-                        if pos == 0 {
-                            Ok(vec![isn.id, offset0, offset1, ((bit0 & 0x0F) << 4) | (bit1 & 0x0F)])
+                        if bit0 > 0x0F {
+                            return Err(out_of_range(isn, 1, bit0 as u16, 0x0F));
+                        }
+
+                        if bit1 > 0x0F {
+                            return Err(out_of_range(isn, 2, bit1 as u16, 0x0F));
+                        }
+
+                        if let Some(OperandType::BitAddr(0)) = isn.op1 {
+                            Ok(vec![isn.id, offset0, offset1, (bit0 << 4) | bit1])
                         } else {
-                            Ok(vec![isn.id, offset1, offset0, ((bit1 & 0x0F) << 4) | (bit0 & 0x0F) ] )
+                            Ok(vec![isn.id, offset1, offset0, (bit1 << 4) | bit0])
                         }
                     },
                     decode: |_isn, buf| {
+                        require_length(buf, 4)?;
+
                         let bit1 : u8 = (buf[3] & 0b11110000) >> 4;
                         let bit0 : u8 = buf[3] & 0b00001111;
 
@@ -794,27 +987,28 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::bitaddr8_rel8_bit4_0 => {
                 Encoding {
-                    name: "QQ_rr_q0",
-                    length: 4,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let (bitoff, bit) = match args.op1.unwrap() {
-                            Operand::BitAddr(bitoff, bit) => (bitoff, bit),
-                            _ => unreachable!()
-                        };
-
-                        let rel = match args.op2.unwrap() {
-                            Operand::Direct(direct, _) => direct,
-                            _ => return Err("EncodingType::bitaddr8_rel8_bit4_0: Second operand must be a direct value")
+                        let (bitoff, bit) = match get_operand(isn, args, 1)? {
+                            Operand::BitAddr(bitoff, bit) => (*bitoff, *bit),
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be a bit address"))
                         };
 
-                        if rel > <i8>::max_value() as u16 {
-                            return Err("EncodingType::bitaddr8_rel8_bit4_0: Relative address must be a signed 8 bit value");
+                        if bit > 0x0F {
+                            return Err(out_of_range(isn, 1, bit as u16, 0x0F));
                         }
 
-                        // TODO: properly validate relative addresses
-                        Ok(vec![isn.id, bitoff, ((rel & 0x00FF) / 2) as u8, bit << 4])
+                        let rel = match get_operand(isn, args, 2)? {
+                            Operand::Rel8(rel) => *rel as u8,
+                            _ => return Err(invalid_operand(isn, 2, "Second operand must be a relative address"))
+                        };
+
+                        Ok(vec![isn.id, bitoff, rel, bit << 4])
                     },
-                    decode: |_isn, buf| {
+                    decode: |isn, buf| {
+                        require_length(buf, 4)?;
+
                         match buf[3] & 0b00001111 {
                             0 => {
                                 let bitoff0 : u8 = buf[1];
@@ -823,11 +1017,11 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
                                 Ok(InstructionArguments {
                                     op1: Some(Operand::BitAddr(bitoff0, bit0)),
-                                    op2: Some(Operand::Direct(relative as u16, 8)),
+                                    op2: Some(Operand::Rel8(relative as i8)),
                                     ..Default::default()
                                 })
                             },
-                            _ => Err("EncodingType::bitaddr8_rel8_bit4_0: Invalid instruction")
+                            _ => Err(invalid_encoding(isn, 3, "Lower nibble must be zero"))
                         }
                     }
                 }
@@ -835,25 +1029,27 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::reg8 => {
                 Encoding {
-                    name: "RR",
-                    length: 2,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        match args.op1.as_ref().unwrap() {
-                            Operand::Register(reg) => Ok(vec![isn.id, reg.to_reg8().expect("EncodingType::reg8 Invalid register")]),
-                            _ => Err("EncodingType::reg8: Invalid Operand")
+                        match get_operand(isn, args, 1)? {
+                            Operand::Register(reg) => Ok(vec![isn.id, encode_reg8(isn, 1, reg)?]),
+                            _ => Err(invalid_operand(isn, 1, "Operand must be a register"))
                         }
                     },
                     decode: |isn, buf| {
+                        require_length(buf, 2)?;
+
                         let reg_addr: u8 = buf[1];
 
-                        match Reg::from_reg8(reg_addr, isn.op1.as_ref().unwrap()) {
+                        match Reg::from_reg8(reg_addr, &get_op_type(isn, 1)?) {
                             Ok(reg) => {
                                 Ok(InstructionArguments {
                                     op1: Some(Operand::Register(reg)),
                                    ..Default::default()
                                 })
                             },
-                            Err(_) => Err("EncodingType::reg8: Invalid register value")
+                            Err(_) => Err(invalid_decoded_operand(isn, 1, 1, "Invalid register value"))
                         }
                     }
                 }
@@ -861,35 +1057,24 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::reg8_data16 => {
                 Encoding {
-                    name: "RR_II_II",
-                    length: 4,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let mut reg: Option<u8> = None;
-                        let mut data: Option<u16> = None;
-                        for op in [args.op1, args.op2].iter() {
-                            match op.as_ref().unwrap() {
-                                Operand::Register(r) => {
-                                    reg = Some(r.to_reg8().expect("EncodingType::reg8_data16: Invalid register"));
-                                },
-                                Operand::Immediate(imm, _width) => {
-                                    data = Some(*imm);
-                                }
-                                _ => unreachable!()
-                            }
-                        }
+                        let (reg, data) = get_reg8_and_value(isn, args, immediate_value)?;
 
-                        Ok(vec![isn.id, reg.unwrap(), (data.unwrap() & 0x00FF) as u8, ((data.unwrap() & 0xFF00) >> 8) as u8])
+                        Ok(vec![isn.id, reg, (data & 0x00FF) as u8, ((data & 0xFF00) >> 8) as u8])
                     },
                     decode: |isn, buf| {
+                        require_length(buf, 4)?;
+
                         let reg_addr : u8 = buf[1];
 
                         let slice = &buf[2..4];
                         let data : u16 = LittleEndian::read_u16(slice);
 
-                        let reg_op = get_reg_op_position(&isn);
-                        let reg8 = Reg::from_reg8(reg_addr, reg_op.as_ref().unwrap());
+                        let (reg_pos, reg_op) = get_reg_op_position(&isn)?;
 
-                        match reg8 {
+                        match Reg::from_reg8(reg_addr, &reg_op) {
                             Ok(register) => {
                                 Ok(InstructionArguments {
                                     op1: Some(Operand::Register(register)),
@@ -897,7 +1082,7 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
                                     ..Default::default()
                                 })
                             },
-                            Err(_) => return Err("EncodingType::reg8_data16 Invalid register value")
+                            Err(_) => Err(invalid_decoded_operand(isn, reg_pos, 1, "Invalid register value"))
                         }
                     }
                 }
@@ -905,51 +1090,43 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::reg8_data8_nop8 => {
                 Encoding {
-                    name: "RR_II_xx",
-                    length: 4,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let mut reg: Option<u8> = None;
-                        let mut data: Option<u16> = None;
-                        for op in [args.op1, args.op2].iter() {
-                            match op.as_ref().unwrap() {
-                                Operand::Register(r) => {
-                                    reg = Some(r.to_reg8().expect("EncodingType::reg8_data8_nop8: Invalid register"));
-                                },
-                                Operand::Immediate(imm, _width) => {
-                                    data = Some(*imm);
-                                }
-                                _ => unreachable!()
-                            }
+                        let (reg, data) = get_reg8_and_value(isn, args, immediate_value)?;
+
+                        if data > <u8>::max_value() as u16 {
+                            return Err(out_of_range(isn, 2, data, <u8>::max_value() as u16));
                         }
 
-                        Ok(vec![isn.id, reg.unwrap(), (data.unwrap() & 0x00FF) as u8, 0x42])
+                        Ok(vec![isn.id, reg, data as u8, NOP8])
                     },
                     decode: |isn, buf| {
+                        require_length(buf, 4)?;
+
                         let reg_addr : u8 = buf[1];
                         let data : u8 = buf[2];
 
-                        let reg_op = get_reg_op_position(&isn);
+                        let (reg_pos, reg_op) = get_reg_op_position(&isn)?;
 
-                        let reg = match reg_op.unwrap() {
+                        let reg = match reg_op {
                             OperandType::ByteRegister(_) |
-                            OperandType::WordRegister(_) => Reg::from_reg8(reg_addr, reg_op.as_ref().unwrap()),
-                            _ => return Err("EncodingType::reg8_data8_nop8: At least one register required")
+                            OperandType::WordRegister(_) => Reg::from_reg8(reg_addr, &reg_op),
+                            _ => return Err(invalid_decoded_operand(isn, reg_pos, 1, "At least one register required"))
                         };
 
-                        if let Err(_) = reg {
-                            return Err("EncodingType::reg8_data8_nop8; Invalid register value");
-                        }
+                        let reg = reg.map_err(|_| invalid_decoded_operand(isn, reg_pos, 1, "Invalid register value"))?;
 
-                        if reg_op == isn.op2 {
+                        if reg_pos == 2 {
                             Ok(InstructionArguments {
                                 op1: Some(Operand::Immediate(data as u16, 8)),
-                                op2: Some(Operand::Register(reg.unwrap())),
+                                op2: Some(Operand::Register(reg)),
                                 ..Default::default()
                             })
 
                         } else {
                             Ok(InstructionArguments {
-                                op1: Some(Operand::Register(reg.unwrap())),
+                                op1: Some(Operand::Register(reg)),
                                 op2: Some(Operand::Immediate(data as u16, 8)),
                                 ..Default::default()
                             })
@@ -960,57 +1137,60 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::reg8_mem16 => {
                 Encoding {
-                    name: "RR_MM_MM",
-                    length: 4,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let mut reg: Option<u8> = None;
-                        let mut data: Option<u16> = None;
-                        for op in [args.op1, args.op2].iter() {
-                            match op.as_ref().unwrap() {
-                                Operand::Register(r) => {
-                                    reg = Some(r.to_reg8().expect("EncodingType::reg8_mem16: Invalid register"));
-                                },
-                                Operand::Direct(direct, _width) => {
-                                    data = Some(*direct);
-                                }
-                                _ => unreachable!()
-                            }
-                        }
-                        Ok(vec![isn.id, reg.unwrap(), (data.unwrap() & 0x00FF) as u8, ((data.unwrap() & 0xFF00) >> 8) as u8])
+                        // pcall takes a code address where the movs take memory
+                        let get_value: fn(&Operand) -> Option<u16> = match isn.op2 {
+                            Some(OperandType::DirectCaddr16) => code_address_value,
+                            _ => memory_value
+                        };
+                        let (reg, data) = get_reg8_and_value(isn, args, get_value)?;
+
+                        Ok(vec![isn.id, reg, (data & 0x00FF) as u8, ((data & 0xFF00) >> 8) as u8])
                     },
                     decode: |isn, buf| {
+                        require_length(buf, 4)?;
+
                         let reg_addr: u8 = buf[1];
 
                         let slice = &buf[2..4];
                         let memory : u16 = LittleEndian::read_u16(slice);
 
-                        let reg_op = get_reg_op_position(&isn);
+                        let (reg_pos, reg_op) = get_reg_op_position(&isn)?;
 
-                        let reg = match reg_op.unwrap() {
+                        let reg = match reg_op {
                             OperandType::ByteRegister(_) |
                             OperandType::WordRegister(_) => {
-                                Reg::from_reg8(reg_addr, reg_op.as_ref().unwrap())
+                                Reg::from_reg8(reg_addr, &reg_op)
                             },
-                            _ => unreachable!()
+                            _ => return Err(invalid_decoded_operand(isn, reg_pos, 1, "At least one register required"))
                         };
 
-                        if let Err(_) = reg {
-                            return Err("EncodingType::reg8_mem16: Invalid register value");
-                        }
-
-                        if let OperandType::DirectMemory16 = isn.op1.unwrap() {
-                            Ok(InstructionArguments {
-                                op1: Some(Operand::Direct(memory, 16)),
-                                op2: Some(Operand::Register(reg.unwrap())),
-                                ..Default::default()
-                            })
+                        let reg = reg.map_err(|_| invalid_decoded_operand(isn, reg_pos, 1, "Invalid register value"))?;
 
-                        } else {
-                            Ok(InstructionArguments {
-                                op1: Some(Operand::Register(reg.unwrap())),
-                                op2: Some(Operand::Direct(memory, 16)),
-                                ..Default::default()
-                            })
+                        match (isn.op1, isn.op2) {
+                            (Some(OperandType::DirectMemory16), _) => {
+                                Ok(InstructionArguments {
+                                    op1: Some(Operand::Mem16(memory)),
+                                    op2: Some(Operand::Register(reg)),
+                                    ..Default::default()
+                                })
+                            },
+                            (_, Some(OperandType::DirectCaddr16)) => {
+                                Ok(InstructionArguments {
+                                    op1: Some(Operand::Register(reg)),
+                                    op2: Some(Operand::CodeAddr16(memory)),
+                                    ..Default::default()
+                                })
+                            },
+                            _ => {
+                                Ok(InstructionArguments {
+                                    op1: Some(Operand::Register(reg)),
+                                    op2: Some(Operand::Mem16(memory)),
+                                    ..Default::default()
+                                })
+                            }
                         }
                     }
                 }
@@ -1018,30 +1198,32 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::seg8_mem16 => {
                 Encoding {
-                    name: "SS_MM_MM",
-                    length: 4,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let seg: u8 = match args.op1.expect("EncodingType::seg8_mem16: Needed op1 = segment") {
-                            Operand::Direct(d, _width) => d as u8,
-                            _ => return Err("EncodingType::seg8_mem16: Needed op1=segment")
+                        let seg: u8 = match get_operand(isn, args, 1)? {
+                            Operand::Segment8(seg) => *seg,
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be a segment"))
                         };
 
-                        let mem: u16 = match args.op2.expect("needed op2=caddr16") {
-                            Operand::Direct(d, _width) => d,
-                            _ => return Err("EncodingType::seg8_mem16: Needed op2 = caddr16")
+                        let mem: u16 = match get_operand(isn, args, 2)? {
+                            Operand::CodeAddr16(caddr) => *caddr,
+                            _ => return Err(invalid_operand(isn, 2, "Second operand must be a caddr16"))
                         };
 
                         Ok(vec![isn.id, seg, (mem & 0x00FF) as u8, ((mem & 0x0FF00) >> 8) as u8])
                     },
                     decode: |_isn, buf| {
+                        require_length(buf, 4)?;
+
                         let segment : u8 = buf[1];
 
                         let slice = &buf[2..4];
                         let memory : u16 = LittleEndian::read_u16(slice);
 
                         Ok(InstructionArguments {
-                            op1: Some(Operand::Direct(segment as u16, 8)),
-                            op2: Some(Operand::Direct(memory, 16)),
+                            op1: Some(Operand::Segment8(segment)),
+                            op2: Some(Operand::CodeAddr16(memory)),
                             ..Default::default()
                         })
                     }
@@ -1050,22 +1232,23 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::cond4_0_mem16 => {
                 Encoding {
-                    name: "c0_MM_MM",
-                    length: 4,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        // foo
-                        let cond = match args.op1.unwrap() {
-                            Operand::Condition(cond) => cond.to_u8().unwrap(),
-                            _ => unreachable!()
+                        let cond = match get_operand(isn, args, 1)? {
+                            Operand::Condition(cond) => *cond as u8,
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be a condition code"))
                         };
-                        let mem = match args.op2.unwrap() {
-                            Operand::Direct(mem, _) => mem,
-                            _ => unreachable!()
+                        let mem = match get_operand(isn, args, 2)? {
+                            Operand::CodeAddr16(caddr) => *caddr,
+                            _ => return Err(invalid_operand(isn, 2, "Second operand must be a caddr16"))
                         };
 
                         Ok(vec![isn.id, cond << 4, (mem & 0x00FF) as u8, ((mem & 0xFF00) >> 8) as u8])
                     },
-                    decode: |_isn, buf| {
+                    decode: |isn, buf| {
+                        require_length(buf, 4)?;
+
                         match buf[1] & 0b00001111 {
                             0 => {
                                 let condition : u8 = (buf[1] & 0b11110000) >> 4;
@@ -1074,12 +1257,12 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
                                 let memory : u16 = LittleEndian::read_u16(slice);
 
                                 Ok(InstructionArguments {
-                                    op1: Some(Operand::Condition(OpCondition::from_u8(condition).unwrap())),
-                                    op2: Some(Operand::Direct(memory, 16)),
+                                    op1: Some(Operand::Condition(get_condition(isn, 1, 1, condition)?)),
+                                    op2: Some(Operand::CodeAddr16(memory)),
                                     ..Default::default()
                                 })
                             },
-                            _ => Err("Invalid instruction")
+                            _ => Err(invalid_encoding(isn, 1, "Lower nibble must be zero"))
                         }
                     }
                 }
@@ -1087,27 +1270,29 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::cond4_reg4 => {
                 Encoding {
-                    name: "cn",
-                    length: 2,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let cond = match args.op1.unwrap() {
-                            Operand::Condition(cond) => cond,
-                            _ => unreachable!()
+                        let cond = match get_operand(isn, args, 1)? {
+                            Operand::Condition(cond) => *cond as u8,
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be a condition code"))
                         };
 
-                        let reg = match args.op2.unwrap() {
-                            Operand::Indirect(reg) => reg.to_reg4().unwrap(),
-                            _ => unreachable!()
+                        let reg = match get_operand(isn, args, 2)? {
+                            Operand::Indirect(reg) => encode_reg4(isn, 2, reg)?,
+                            _ => return Err(invalid_operand(isn, 2, "Second operand must be indirect"))
                         };
 
-                        Ok(vec![isn.id, (cond.to_u8().unwrap() << 4) | reg])
+                        Ok(vec![isn.id, (cond << 4) | reg])
                     },
                     decode: |isn, buf| {
+                        require_length(buf, 2)?;
+
                         let condition : u8 = (buf[1] & 0b11110000) >> 4;
                         let register0 = buf[1] & 0b00001111;
-                        let reg = get_reg4(&isn.op2.unwrap(), register0, register0);
+                        let reg = get_reg4(isn, &get_op_type(isn, 2)?, 2, register0, register0)?;
                         Ok(InstructionArguments {
-                            op1: Some(Operand::Condition(OpCondition::from_u8(condition).unwrap())),
+                            op1: Some(Operand::Condition(get_condition(isn, 1, 1, condition)?)),
                             op2: Some(reg),
                             ..Default::default()
                         })
@@ -1117,23 +1302,25 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::reg4_0 => {
                 Encoding {
-                    name: "n0",
-                    length: 2,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let reg0 = match args.op1.as_ref().unwrap() {
-                            Operand::Register(reg) => reg.to_reg4().expect("Invalid GPR"),
-                            _ => return Err("Invalid Operand 1")
+                        let reg0 = match get_operand(isn, args, 1)? {
+                            Operand::Register(reg) => encode_reg4(isn, 1, reg)?,
+                            _ => return Err(invalid_operand(isn, 1, "Operand must be a register"))
                         };
 
                         Ok(vec![isn.id, reg0 << 4])
 
                     },
                     decode: |isn, buf| {
+                        require_length(buf, 2)?;
+
                         match buf[1] & 0b00001111 {
                             0 => {
                                 let register0 = (buf[1] & 0b11110000) >> 4;
 
-                                let op1 = get_reg4(isn.op1.as_ref().unwrap(), register0, register0);
+                                let op1 = get_reg4(isn, &get_op_type(isn, 1)?, 1, register0, register0)?;
 
                                 Ok(InstructionArguments {
                                     op1: Some(op1),
@@ -1141,7 +1328,7 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
                                     ..Default::default()
                                 })
                             },
-                            _ => Err("Instruction was invalid, lower nibble must be zero")
+                            _ => Err(invalid_encoding(isn, 1, "Lower nibble must be zero"))
                         }
                     }
                 }
@@ -1149,32 +1336,32 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::reg4_reg4 => {
                 Encoding {
-                    name: "nm",
-                    length: 2,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let op1_pos = match isn.op1.as_ref().unwrap() {
-                            OperandType::ByteRegister(p) |
-                            OperandType::WordRegister(p) |
-                            OperandType::Indirect(p) |
-                            OperandType::IndirectPostIncrement(p) |
-                            OperandType::IndirectPreDecrement(p) => *p,
-                            _ => unreachable!()
+                        let op1_pos: u8 = match isn.op1 {
+                            Some(OperandType::ByteRegister(p)) |
+                            Some(OperandType::WordRegister(p)) |
+                            Some(OperandType::Indirect(p)) |
+                            Some(OperandType::IndirectPostIncrement(p)) |
+                            Some(OperandType::IndirectPreDecrement(p)) => p,
+                            _ => return Err(invalid_operand(isn, 1, "Instruction doesn't take a register as its first operand"))
                         };
 
-                        let reg0 = match args.op1.as_ref().unwrap() {
+                        let reg0 = match get_operand(isn, args, 1)? {
                             Operand::Indirect(reg) |
                             Operand::IndirectPostIncrement(reg) |
                             Operand::IndirectPreDecrement(reg) |
-                            Operand::Register(reg) => reg.to_reg4().expect("Invalid register"),
-                            _ => return Err("Invalid Operand 1")
+                            Operand::Register(reg) => encode_reg4(isn, 1, reg)?,
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be indirect or register"))
                         };
 
-                        let reg1 = match args.op2.as_ref().unwrap() {
+                        let reg1 = match get_operand(isn, args, 2)? {
                             Operand::Indirect(reg) |
                             Operand::IndirectPostIncrement(reg) |
                             Operand::IndirectPreDecrement(reg) |
-                            Operand::Register(reg) => reg.to_reg4().expect("Invalid register"),
-                            _ => return Err("Invalid Operand 2")
+                            Operand::Register(reg) => encode_reg4(isn, 2, reg)?,
+                            _ => return Err(invalid_operand(isn, 2, "Second operand must be indirect or register"))
                         };
 
                         if op1_pos == 0 {
@@ -1184,11 +1371,13 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
                         }
                     },
                     decode: |isn, buf| {
+                        require_length(buf, 2)?;
+
                         let register0 : u8 = (buf[1] & 0b11110000) >> 4;
                         let register1 : u8 = buf[1] & 0b00001111;
 
-                        let op1 = get_reg4(isn.op1.as_ref().unwrap(), register0, register1);
-                        let op2 = get_reg4(isn.op2.as_ref().unwrap(), register0, register1);
+                        let op1 = get_reg4(isn, &get_op_type(isn, 1)?, 1, register0, register1)?;
+                        let op2 = get_reg4(isn, &get_op_type(isn, 2)?, 2, register0, register1)?;
 
                         Ok(InstructionArguments {
                             op1: Some(op1),
@@ -1201,30 +1390,32 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::reg4_reg4_data16 => {
                 Encoding {
-                    name: "nm_II_II",
-                    length: 4,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let reg0 = match args.op1.unwrap() {
-                            Operand::Register(reg) => reg.to_reg4().unwrap(),
-                            _ => unreachable!()
+                        let reg0 = match get_operand(isn, args, 1)? {
+                            Operand::Register(reg) => encode_reg4(isn, 1, reg)?,
+                            _ => return Err(invalid_operand(isn, 1, "First operand must be a register"))
                         };
 
-                        let (reg1, immed) = match args.op2.unwrap() {
-                            Operand::IndirectAndImmediate(reg, immed) => (reg.to_reg4().unwrap(), immed),
-                            _ => unreachable!()
+                        let (reg1, immed) = match get_operand(isn, args, 2)? {
+                            Operand::IndirectAndImmediate(reg, immed) => (encode_reg4(isn, 2, reg)?, *immed),
+                            _ => return Err(invalid_operand(isn, 2, "Second operand must be [Rw + #data16]"))
                         };
 
                         Ok(vec![isn.id, (reg0 << 4) | (reg1 & 0x0F), (immed & 0x00FF) as u8, ((immed & 0xFF00) >> 8) as u8 ])
                     },
                     decode: |isn, buf| {
+                        require_length(buf, 4)?;
+
                         let register0 : u8 = (buf[1] & 0b11110000) >> 4;
                         let register1 : u8 = buf[1] & 0b00001111;
 
                         let slice = &buf[2..4];
                         let data : u16 = LittleEndian::read_u16(slice);
 
-                        let val1 = get_reg4(isn.op1.as_ref().unwrap(), register0, register1);
-                        let val2 = get_reg4(isn.op2.as_ref().unwrap(), register0, register1);
+                        let val1 = get_reg4(isn, &get_op_type(isn, 1)?, 1, register0, register1)?;
+                        let val2 = get_reg4(isn, &get_op_type(isn, 2)?, 2, register0, register1)?;
 
                         let op1 = match val1 {
                             Operand::IndirectAndImmediate(r, _) => {
@@ -1254,22 +1445,24 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::reg4_dup => {
                 Encoding {
-                    name: "nn",
-                    length: 2,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let reg0 = match args.op1.as_ref().unwrap() {
-                            Operand::Register(reg) => reg.to_reg4().expect("Invalid GPR"),
-                            _ => return Err("Invalid Operand 1")
+                        let reg0 = match get_operand(isn, args, 1)? {
+                            Operand::Register(reg) => encode_reg4(isn, 1, reg)?,
+                            _ => return Err(invalid_operand(isn, 1, "Operand must be a register"))
                         };
 
                         Ok(vec![isn.id, (reg0 << 4) | (reg0 & 0x0F) ])
                     },
                     decode: |isn, buf| {
+                        require_length(buf, 2)?;
+
                         let lower : u8 = buf[1] & 0b00001111;
                         let upper : u8 = (buf[1] & 0b11110000) >> 4;
 
                         if lower == upper {
-                            let op1 = get_reg4(isn.op1.as_ref().unwrap(), lower, upper);
+                            let op1 = get_reg4(isn, &get_op_type(isn, 1)?, 1, lower, upper)?;
 
                             Ok(InstructionArguments {
                                 op1: Some(op1),
@@ -1277,7 +1470,7 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
                                 ..Default::default()
                             })
                         } else {
-                            Err("Instruction was invalid. Encoding requires upper and lower nibbles must be equal.")
+                            Err(invalid_encoding(isn, 1, "Upper and lower nibbles must be equal"))
                         }
                     }
                 }
@@ -1285,30 +1478,23 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::rel8s => {
                 Encoding {
-                    name: "rr",
-                    length: 2,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        let relative = match args.op1.unwrap() {
-                            // TODO: This should be based on PC or something
-                            Operand::Direct(rel, width) => {
-                                if width != 8 {
-                                    eprintln!("Relative address should be an i8, got {} bits", width);
-                                }
-                                if rel > <i8>::max_value() as u16 {
-                                    return Err("rel8s encoding is an i8, got value which exceeds 8 bits")
-                                }
-                                rel as u8
-                            },
-                            _ => return Err("rel8s encoding needs a relative address operand")
+                        let relative = match get_operand(isn, args, 1)? {
+                            Operand::Rel8(rel) => *rel as u8,
+                            _ => return Err(invalid_operand(isn, 1, "Operand must be a relative address"))
                         };
 
-                        Ok(vec![isn.id, relative / 2 ])
+                        Ok(vec![isn.id, relative])
                     },
                     decode: |_isn, buf| {
+                        require_length(buf, 2)?;
+
                         let relative : u8 = buf[1];
 
                         Ok(InstructionArguments {
-                            op1: Some(Operand::Direct(relative as u16, 8)),
+                            op1: Some(Operand::Rel8(relative as i8)),
                             op2: None,
                             ..Default::default()
                         })
@@ -1318,25 +1504,229 @@ impl<'a> From<&'a EncodingType> for Encoding<'a> {
 
             EncodingType::trap7 => {
                 Encoding {
-                    name: "trap7",
-                    length: 2,
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
                     encode: |isn, args| {
-                        match args.op1.as_ref().unwrap() {
-                            Operand::Immediate(imm, _width) => Ok(vec![isn.id, (*imm as u8) << 1]),
-                            _ => Err("Invalid Operand")
+                        match get_operand(isn, args, 1)? {
+                            Operand::Trap7(trap) => {
+                                if *trap > 0x7F {
+                                    return Err(out_of_range(isn, 1, *trap as u16, 0x7F));
+                                }
+                                Ok(vec![isn.id, *trap << 1])
+                            },
+                            _ => Err(invalid_operand(isn, 1, "Operand must be #trap7"))
                         }
 
                     },
                     decode: |_isn, buf| {
+                        require_length(buf, 2)?;
+
                         let trap : u8 = (buf[1] & 0b11111110) >> 1;
 
                         Ok(InstructionArguments {
-                            op1: Some(Operand::Immediate(trap as u16, 7)),
+                            op1: Some(Operand::Trap7(trap)),
                             op2: None,
                             ..Default::default()
                         })
                     }
                 }
+            },
+
+            EncodingType::co_reg4_reg4 => {
+                Encoding {
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
+                    encode: |isn, args| {
+                        let mac_op = get_mac_op(isn, args, None)?;
+
+                        let byte: u8 = match mac_op.shape {
+                            MacShape::Binary => (encode_gpr(isn, args, 1)? << 4) | encode_gpr(isn, args, 2)?,
+                            MacShape::Shift => {
+                                match get_operand(isn, args, 1)? {
+                                    Operand::Immediate(data, _width) if *data > 0x0F => return Err(out_of_range(isn, 1, *data, 0x0F)),
+                                    Operand::Immediate(data, _width) => *data as u8,
+                                    _ => return Err(invalid_operand(isn, 1, "Operand must be #data4"))
+                                }
+                            },
+                            MacShape::Bare => 0x00
+                        };
+
+                        Ok(vec![isn.id, byte, mac_op.id, 0x00])
+                    },
+                    decode: |isn, buf| {
+                        require_length(buf, 4)?;
+
+                        if buf[3] != 0 {
+                            return Err(invalid_encoding(isn, 3, "Fourth byte must be zero"));
+                        }
+
+                        let mac_op = decode_mac_op(isn, buf[2], None)?;
+                        let upper : u8 = (buf[1] & 0b11110000) >> 4;
+                        let lower : u8 = buf[1] & 0b00001111;
+
+                        let mut values = InstructionArguments {
+                            mnemonic: Some(mac_op.mnemonic.to_string()),
+                            sub_op: Some(mac_op.id),
+                            ..Default::default()
+                        };
+
+                        match mac_op.shape {
+                            MacShape::Binary => {
+                                values.op1 = Some(Operand::Register(decode_gpr(isn, 1, 1, upper)?));
+                                values.op2 = Some(Operand::Register(decode_gpr(isn, 2, 1, lower)?));
+                            },
+                            MacShape::Shift => {
+                                if upper != 0 {
+                                    return Err(invalid_encoding(isn, 1, "Upper nibble must be zero"));
+                                }
+                                values.op1 = Some(Operand::Immediate(lower as u16, 4));
+                            },
+                            MacShape::Bare => {
+                                if buf[1] != 0 {
+                                    return Err(invalid_encoding(isn, 1, "Second byte must be zero"));
+                                }
+                            }
+                        }
+
+                        Ok(values)
+                    }
+                }
+            },
+
+            EncodingType::co_reg4_ind => {
+                Encoding {
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
+                    encode: |isn, args| {
+                        let mac_op = get_mac_op(isn, args, Some(MacShape::Binary))?;
+                        let reg0 = encode_gpr(isn, args, 1)?;
+                        let (reg1, mode) = encode_gpr_pointer(isn, args, 2)?;
+
+                        Ok(vec![isn.id, (reg0 << 4) | reg1, mac_op.id, mode])
+                    },
+                    decode: |isn, buf| {
+                        require_length(buf, 4)?;
+
+                        if (buf[3] & 0b11110000) != 0 {
+                            return Err(invalid_encoding(isn, 3, "Upper nibble must be zero"));
+                        }
+
+                        let mac_op = decode_mac_op(isn, buf[2], Some(MacShape::Binary))?;
+                        let reg0 = decode_gpr(isn, 1, 1, (buf[1] & 0b11110000) >> 4)?;
+                        let reg1 = decode_gpr(isn, 2, 1, buf[1] & 0b00001111)?;
+
+                        Ok(InstructionArguments {
+                            op1: Some(Operand::Register(reg0)),
+                            op2: Some(decode_pointer(isn, 2, 3, reg1, buf[3] & 0b00001111)?),
+                            mnemonic: Some(mac_op.mnemonic.to_string()),
+                            sub_op: Some(mac_op.id),
+                            ..Default::default()
+                        })
+                    }
+                }
+            },
+
+            EncodingType::co_idx_ind |
+            EncodingType::co_mov => {
+                Encoding {
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
+                    encode: |isn, args| {
+                        // CoMOV has no sub-op
+                        let sub_op = match isn.encoding {
+                            EncodingType::co_idx_ind => get_mac_op(isn, args, Some(MacShape::Binary))?.id,
+                            _ => 0x00
+                        };
+
+                        let (index, mode0) = encode_index_pointer(isn, args, 1)?;
+                        let (reg1, mode1) = encode_gpr_pointer(isn, args, 2)?;
+
+                        Ok(vec![isn.id, (index << 4) | reg1, sub_op, (mode0 << 4) | mode1])
+                    },
+                    decode: |isn, buf| {
+                        require_length(buf, 4)?;
+
+                        let mut values = InstructionArguments {
+                            ..Default::default()
+                        };
+
+                        if isn.encoding == EncodingType::co_idx_ind {
+                            let mac_op = decode_mac_op(isn, buf[2], Some(MacShape::Binary))?;
+                            values.mnemonic = Some(mac_op.mnemonic.to_string());
+                            values.sub_op = Some(mac_op.id);
+                        } else if buf[2] != 0 {
+                            return Err(invalid_encoding(isn, 2, "Third byte must be zero"));
+                        }
+
+                        let index = decode_index_pointer(isn, 1, (buf[1] & 0b11110000) >> 4)?;
+                        let reg1 = decode_gpr(isn, 2, 1, buf[1] & 0b00001111)?;
+
+                        values.op1 = Some(decode_pointer(isn, 1, 3, index, (buf[3] & 0b11110000) >> 4)?);
+                        values.op2 = Some(decode_pointer(isn, 2, 3, reg1, buf[3] & 0b00001111)?);
+
+                        Ok(values)
+                    }
+                }
+            },
+
+            EncodingType::co_store_reg4 => {
+                Encoding {
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
+                    encode: |isn, args| {
+                        let reg0 = encode_gpr(isn, args, 1)?;
+                        let mac_reg = encode_mac_reg(isn, args, 2)?;
+
+                        Ok(vec![isn.id, (reg0 << 4) | mac_reg, 0x00, 0x00])
+                    },
+                    decode: |isn, buf| {
+                        require_length(buf, 4)?;
+
+                        if buf[2] != 0 || buf[3] != 0 {
+                            return Err(invalid_encoding(isn, 2, "Third and fourth bytes must be zero"));
+                        }
+
+                        let reg0 = decode_gpr(isn, 1, 1, (buf[1] & 0b11110000) >> 4)?;
+
+                        Ok(InstructionArguments {
+                            op1: Some(Operand::Register(reg0)),
+                            op2: Some(decode_mac_reg(isn, 2, buf[1] & 0b00001111)?),
+                            ..Default::default()
+                        })
+                    }
+                }
+            },
+
+            EncodingType::co_store_ind => {
+                Encoding {
+                    name: encoding_type.format(),
+                    length: encoding_type.length() as i32,
+                    encode: |isn, args| {
+                        let (reg0, mode) = encode_gpr_pointer(isn, args, 1)?;
+                        let mac_reg = encode_mac_reg(isn, args, 2)?;
+
+                        Ok(vec![isn.id, (reg0 << 4) | mac_reg, 0x00, mode << 4])
+                    },
+                    decode: |isn, buf| {
+                        require_length(buf, 4)?;
+
+                        if buf[2] != 0 {
+                            return Err(invalid_encoding(isn, 2, "Third byte must be zero"));
+                        }
+
+                        if (buf[3] & 0b00001111) != 0 {
+                            return Err(invalid_encoding(isn, 3, "Lower nibble must be zero"));
+                        }
+
+                        let reg0 = decode_gpr(isn, 1, 1, (buf[1] & 0b11110000) >> 4)?;
+
+                        Ok(InstructionArguments {
+                            op1: Some(decode_pointer(isn, 1, 3, reg0, (buf[3] & 0b11110000) >> 4)?),
+                            op2: Some(decode_mac_reg(isn, 2, buf[1] & 0b00001111)?),
+                            ..Default::default()
+                        })
+                    }
+                }
             }
         }
     }
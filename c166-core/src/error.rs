@@ -0,0 +1,276 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::error::Error;
+use std::fmt;
+
+use ::cpu::CpuModel;
+use ::encoding::EncodingType;
+use ::instruction::OperandType;
+use ::reg::Reg;
+
+// Operand indices are 1-based to line up with op1/op2/op3, byte offsets are
+// relative to the first byte of the instruction.
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DecodeError {
+    // The input ends partway through an instruction, `needed` more bytes are required to decode it
+    Incomplete { needed: usize },
+    // No instruction is defined for this opcode byte
+    UnknownOpcode { opcode: u8 },
+    // A fixed field of the encoding doesn't hold the value it's required to
+    InvalidEncoding { opcode: u8, encoding: EncodingType, offset: usize, reason: &'static str },
+    // The field for an operand couldn't be turned into an operand
+    InvalidOperand { opcode: u8, encoding: EncodingType, operand: u8, offset: usize, reason: &'static str },
+    // The opcode is valid for the family but the selected CPU doesn't have it
+    UnsupportedInstruction { opcode: u8, cpu: CpuModel },
+    UnsupportedRegister { opcode: u8, operand: u8, reg: Reg, cpu: CpuModel },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EncodeError {
+    UnknownMnemonic { mnemonic: String },
+    // The mnemonic exists but none of its forms take the given operands
+    NoMatchingEncoding { mnemonic: String },
+    // The mnemonic doesn't fit the encoding (e.g. extp for an atomic/extr op)
+    InvalidMnemonic { opcode: u8, encoding: EncodingType, mnemonic: String },
+    MissingOperand { opcode: u8, encoding: EncodingType, operand: u8 },
+    InvalidOperand { opcode: u8, encoding: EncodingType, operand: u8, reason: &'static str },
+    InvalidRegister { opcode: u8, encoding: EncodingType, operand: u8, reg: Reg },
+    OutOfRange { opcode: u8, encoding: EncodingType, operand: u8, value: u32, max: u32 },
+    // Outside pc's segment, or for a relative branch more than -128..=127 words from the following
+    // instruction or an odd address
+    UnreachableTarget { opcode: u8, target: u32, pc: u32 },
+    UnsupportedInstruction { mnemonic: String, cpu: CpuModel },
+    UnsupportedRegister { mnemonic: String, operand: u8, reg: Reg, cpu: CpuModel },
+}
+
+// Lines are 1-based
+#[derive(Clone, Debug, PartialEq)]
+pub enum AsmError {
+    // The line isn't a label, an operation or both
+    Syntax { line: usize },
+    UndefinedSymbol { line: usize, name: String },
+    DuplicateSymbol { line: usize, name: String },
+    Encode { line: usize, error: EncodeError },
+    // An expression's value doesn't fit where it's used
+    OutOfRange { line: usize, value: i64, min: i64, max: u32 },
+    DivideByZero { line: usize },
+    // ENDS for a section that isn't the innermost open one
+    UnbalancedSection { line: usize, name: String },
+    // Label addresses still moved after the last pass
+    Unsettled { passes: usize },
+    // What r2 hands the assembler isn't text
+    NotUtf8 { reason: String },
+    // The code was placed by ORG somewhere other than where it's asked for
+    Misplaced { origin: u32, pc: u32 },
+    // More bytes than the output has room for
+    TooLong { length: usize, max: usize },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RegError {
+    NotAGpr(Reg),
+    NoShortAddress(Reg),
+    NoPhysicalAddress(Reg),
+    InvalidNumber(u8),
+    InvalidBit(u8),
+    UnknownAddress(u16),
+    UnknownName(String),
+    WrongOperandType(OperandType),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CpuError {
+    UnknownCpu(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SfrError {
+    // The description couldn't be read
+    Io(String),
+    // The description isn't valid TOML
+    Syntax(String),
+    // A key is missing or holds the wrong kind of value, register is empty for the device keys
+    InvalidEntry { register: String, key: &'static str, reason: &'static str },
+    // Two registers share a name or an address
+    Duplicate { register: String },
+    // extends names a device that isn't built in
+    UnknownDevice(String),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::Incomplete { needed } => {
+                write!(f, "Truncated instruction, needs {} more byte(s)", needed)
+            },
+            DecodeError::UnknownOpcode { opcode } => {
+                write!(f, "Unknown opcode {:02X}h", opcode)
+            },
+            DecodeError::InvalidEncoding { opcode, encoding, offset, reason } => {
+                write!(f, "Opcode {:02X}h ({:?}): invalid byte at offset {}: {}", opcode, encoding, offset, reason)
+            },
+            DecodeError::InvalidOperand { opcode, encoding, operand, offset, reason } => {
+                write!(f, "Opcode {:02X}h ({:?}): invalid operand {} at offset {}: {}", opcode, encoding, operand, offset, reason)
+            },
+            DecodeError::UnsupportedInstruction { opcode, cpu } => {
+                write!(f, "Opcode {:02X}h is not available on the {}", opcode, cpu)
+            },
+            DecodeError::UnsupportedRegister { opcode, operand, reg, cpu } => {
+                write!(f, "Opcode {:02X}h: register {} (operand {}) is not available on the {}", opcode, reg, operand, cpu)
+            },
+        }
+    }
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EncodeError::UnknownMnemonic { mnemonic } => {
+                write!(f, "Unknown mnemonic \"{}\"", mnemonic)
+            },
+            EncodeError::NoMatchingEncoding { mnemonic } => {
+                write!(f, "No encoding of \"{}\" takes these operands", mnemonic)
+            },
+            EncodeError::InvalidMnemonic { opcode, encoding, mnemonic } => {
+                write!(f, "Opcode {:02X}h ({:?}) can't encode \"{}\"", opcode, encoding, mnemonic)
+            },
+            EncodeError::MissingOperand { opcode, encoding, operand } => {
+                write!(f, "Opcode {:02X}h ({:?}): operand {} is required", opcode, encoding, operand)
+            },
+            EncodeError::InvalidOperand { opcode, encoding, operand, reason } => {
+                write!(f, "Opcode {:02X}h ({:?}): invalid operand {}: {}", opcode, encoding, operand, reason)
+            },
+            EncodeError::InvalidRegister { opcode, encoding, operand, reg } => {
+                write!(f, "Opcode {:02X}h ({:?}): register {} can't be used as operand {}", opcode, encoding, reg, operand)
+            },
+            EncodeError::OutOfRange { opcode, encoding, operand, value, max } => {
+                write!(f, "Opcode {:02X}h ({:?}): operand {} value {:X}h is out of range (max {:X}h)", opcode, encoding, operand, value, max)
+            },
+            EncodeError::UnreachableTarget { opcode, target, pc } => {
+                write!(f, "Opcode {:02X}h: {:X}h can't be reached from {:X}h", opcode, target, pc)
+            },
+            EncodeError::UnsupportedInstruction { mnemonic, cpu } => {
+                write!(f, "\"{}\" is not available on the {}", mnemonic, cpu)
+            },
+            EncodeError::UnsupportedRegister { mnemonic, operand, reg, cpu } => {
+                write!(f, "\"{}\": register {} (operand {}) is not available on the {}", mnemonic, reg, operand, cpu)
+            },
+        }
+    }
+}
+
+fn signed_hex(value: i64) -> String {
+    match value < 0 {
+        true => format!("-{:X}h", -value),
+        false => format!("{:X}h", value)
+    }
+}
+
+impl AsmError {
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            AsmError::Syntax { line } |
+            AsmError::UndefinedSymbol { line, .. } |
+            AsmError::DuplicateSymbol { line, .. } |
+            AsmError::Encode { line, .. } |
+            AsmError::OutOfRange { line, .. } |
+            AsmError::DivideByZero { line } |
+            AsmError::UnbalancedSection { line, .. } => Some(*line),
+            AsmError::Unsettled { .. } |
+            AsmError::NotUtf8 { .. } |
+            AsmError::Misplaced { .. } |
+            AsmError::TooLong { .. } => None,
+        }
+    }
+
+    // What went wrong, without the line
+    pub fn message(&self) -> String {
+        match self {
+            AsmError::Syntax { .. }                     => "syntax error".to_string(),
+            AsmError::UndefinedSymbol { name, .. }      => format!("undefined symbol \"{}\"", name),
+            AsmError::DuplicateSymbol { name, .. }      => format!("\"{}\" is already defined", name),
+            AsmError::Encode { error, .. }              => error.to_string(),
+            AsmError::OutOfRange { value, min, max, .. } => {
+                format!("value {} is out of range ({}..{:X}h)", signed_hex(*value), signed_hex(*min), max)
+            },
+            AsmError::DivideByZero { .. }               => "division by zero".to_string(),
+            AsmError::UnbalancedSection { name, .. }    => format!("\"{}\" isn't the innermost open section", name),
+            AsmError::Unsettled { passes }              => format!("Label addresses didn't settle after {} passes", passes),
+            AsmError::NotUtf8 { reason }                => format!("The source isn't UTF-8: {}", reason),
+            AsmError::Misplaced { origin, pc }          => format!("Assembled to {:X}h rather than {:X}h", origin, pc),
+            AsmError::TooLong { length, max }           => format!("{:X}h bytes don't fit, max len is {:X}h", length, max),
+        }
+    }
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line() {
+            Some(line) => write!(f, "Line {}: {}", line, self.message()),
+            None => write!(f, "{}", self.message())
+        }
+    }
+}
+
+impl fmt::Display for RegError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegError::NotAGpr(reg)              => write!(f, "{} is not a GPR", reg),
+            RegError::NoShortAddress(reg)       => write!(f, "{} has no 8-bit short address", reg),
+            RegError::NoPhysicalAddress(reg)    => write!(f, "{} has no physical address", reg),
+            RegError::InvalidNumber(number)     => write!(f, "No register numbered {:02X}h", number),
+            RegError::InvalidBit(bit)           => write!(f, "Bit position {} must be 0..=15", bit),
+            RegError::UnknownAddress(address)   => write!(f, "No register at {:04X}h", address),
+            RegError::UnknownName(name)         => write!(f, "Unknown register \"{}\"", name),
+            RegError::WrongOperandType(op_type) => write!(f, "{:?} is not a register operand", op_type),
+        }
+    }
+}
+
+impl fmt::Display for CpuError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CpuError::UnknownCpu(name) => write!(f, "Unknown CPU \"{}\"", name),
+        }
+    }
+}
+
+impl fmt::Display for SfrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SfrError::Io(reason)                => write!(f, "Unable to read the register description: {}", reason),
+            SfrError::Syntax(reason)            => write!(f, "Invalid register description: {}", reason),
+            SfrError::InvalidEntry { register, key, reason } => {
+                match register.is_empty() {
+                    true => write!(f, "Invalid \"{}\": {}", key, reason),
+                    false => write!(f, "{}: invalid \"{}\": {}", register, key, reason),
+                }
+            },
+            SfrError::Duplicate { register }    => write!(f, "{} clashes with another register", register),
+            SfrError::UnknownDevice(name)       => write!(f, "Unknown device \"{}\"", name),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+impl Error for EncodeError {}
+impl Error for AsmError {}
+impl Error for RegError {}
+impl Error for CpuError {}
+impl Error for SfrError {}
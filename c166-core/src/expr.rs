@@ -0,0 +1,109 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+// Values the assembler works out once the symbols they name are known.  The arithmetic is done
+// on 64 bits so intermediate results don't wrap, whatever ends up in an operand or directive is
+// range checked there.
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnaryOp {
+    Neg,
+    Not,
+    // The segment (address bits 16..23) and the offset within it
+    Seg,
+    Sof,
+    // The 16K data page (address bits 14..23) and the offset within it
+    Pag,
+    Pof,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BinaryOp {
+    Mul,
+    Div,
+    Mod,
+    Add,
+    Sub,
+    Shl,
+    Shr,
+    And,
+    Or,
+    Xor,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr<'a> {
+    Number(u32),
+    // A label or constant, $ is the address of the line it's on
+    Symbol(&'a str),
+    Unary(UnaryOp, Box<Expr<'a>>),
+    Binary(BinaryOp, Box<Expr<'a>>, Box<Expr<'a>>),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExprError<'a> {
+    UndefinedSymbol(&'a str),
+    DivideByZero,
+}
+
+impl UnaryOp {
+    fn apply(&self, value: i64) -> i64 {
+        match *self {
+            UnaryOp::Neg => value.wrapping_neg(),
+            UnaryOp::Not => !value,
+            UnaryOp::Seg => value >> 16,
+            UnaryOp::Sof => value & 0xFFFF,
+            UnaryOp::Pag => value >> 14,
+            UnaryOp::Pof => value & 0x3FFF,
+        }
+    }
+}
+
+impl BinaryOp {
+    fn apply<'a>(&self, lhs: i64, rhs: i64) -> Result<i64, ExprError<'a>> {
+        // Shifting everything out leaves 0 (or -1) rather than wrapping the count around
+        let shift = match rhs {
+            0..=63 => Some(rhs as u32),
+            _ => None
+        };
+
+        match *self {
+            BinaryOp::Mul => Ok(lhs.wrapping_mul(rhs)),
+            BinaryOp::Div => lhs.checked_div(rhs).ok_or(ExprError::DivideByZero),
+            BinaryOp::Mod => lhs.checked_rem(rhs).ok_or(ExprError::DivideByZero),
+            BinaryOp::Add => Ok(lhs.wrapping_add(rhs)),
+            BinaryOp::Sub => Ok(lhs.wrapping_sub(rhs)),
+            BinaryOp::Shl => Ok(shift.map_or(0, |shift| lhs << shift)),
+            BinaryOp::Shr => Ok(lhs >> shift.unwrap_or(63)),
+            BinaryOp::And => Ok(lhs & rhs),
+            BinaryOp::Or => Ok(lhs | rhs),
+            BinaryOp::Xor => Ok(lhs ^ rhs),
+        }
+    }
+}
+
+impl<'a> Expr<'a> {
+    pub fn evaluate<F>(&self, lookup: &F) -> Result<i64, ExprError<'a>>
+        where F: Fn(&str) -> Option<i64> {
+        match *self {
+            Expr::Number(value) => Ok(value as i64),
+            Expr::Symbol(name) => lookup(name).ok_or(ExprError::UndefinedSymbol(name)),
+            Expr::Unary(op, ref operand) => Ok(op.apply(operand.evaluate(lookup)?)),
+            Expr::Binary(op, ref lhs, ref rhs) => op.apply(lhs.evaluate(lookup)?, rhs.evaluate(lookup)?),
+        }
+    }
+}
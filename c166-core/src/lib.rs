@@ -23,17 +23,44 @@ extern crate enum_primitive_derive;
 #[macro_use]
 extern crate nom;
 
+#[macro_use]
+extern crate lazy_static;
+
 extern crate num_traits;
 extern crate byteorder;
+extern crate toml;
 
+pub mod cpu;
+pub mod decoder;
+pub mod diagnostic;
+pub mod effects;
 pub mod encoding;
+pub mod error;
+pub mod expr;
 pub mod instruction;
+pub mod mac;
+pub mod memory;
+pub mod opcodes;
 pub mod opformat;
 pub mod reg;
+pub mod sfr;
+pub mod timing;
 pub mod bitaddr;
 pub mod parser;
+pub mod assembler;
 
+#[cfg(feature = "r2")]
 pub mod r2;
 
+pub use assembler::{assemble, assemble_listing, assemble_source, Assembly, Block, SymbolTable};
+pub use cpu::CpuModel;
+pub use decoder::{decode, decode_all, decode_all_for_cpu, decode_all_in_mode, decode_for_cpu, decode_in_context, decode_with_registers, DecodeContext, DecodeMode, DecodedInstruction, FlowKind};
+pub use diagnostic::{Diagnostic, Span};
+pub use effects::Effects;
+pub use memory::{MemoryModel, RegisterBank};
+pub use opformat::FormatOptions;
+pub use sfr::RegisterMap;
+pub use timing::StateTimes;
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,187 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use ::instruction::{OpKind, OpType, Operand};
+use ::reg::Reg;
+
+// Operands taken by a MAC operation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MacShape {
+    // Rwn, Rwm / Rwn, [Rwm⊗] / [IDXi⊗], [Rwm⊗]
+    Binary,
+    // #data4
+    Shift,
+    // No operands
+    Bare,
+}
+
+// An operation of the MAC unit, selected by the third byte of the CoXXX opcodes
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MacOp {
+    pub id: u8,
+    pub mnemonic: &'static str,
+    pub shape: MacShape,
+    pub kind: OpKind,
+}
+
+include!(concat!(env!("OUT_DIR"), "/mac_ops.rs"));
+
+impl MacOp {
+    pub fn from_sub_op(sub_op: u8) -> Option<&'static MacOp> {
+        MAC_OPS.iter().find(|mac_op| mac_op.id == sub_op)
+    }
+
+    pub fn from_mnemonic(mnemonic: &str) -> Option<&'static MacOp> {
+        let mnemonic = mnemonic.to_lowercase();
+        MAC_OPS.iter().find(|mac_op| mac_op.mnemonic == mnemonic)
+    }
+
+    pub fn op_type(&self) -> OpType {
+        OpType { kind: self.kind, reg: self.shape == MacShape::Binary }
+    }
+}
+
+// Post-modification applied to a MAC pointer operand, the ⊗ nibble
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PointerMode {
+    None,
+    Increment,
+    Decrement,
+    AddQ0,
+    SubQ0,
+    AddQ1,
+    SubQ1,
+}
+
+impl PointerMode {
+    pub fn from_nibble(nibble: u8) -> Option<PointerMode> {
+        match nibble {
+            0 => Some(PointerMode::None),
+            1 => Some(PointerMode::Increment),
+            2 => Some(PointerMode::Decrement),
+            3 => Some(PointerMode::AddQ0),
+            4 => Some(PointerMode::SubQ0),
+            5 => Some(PointerMode::AddQ1),
+            6 => Some(PointerMode::SubQ1),
+            _ => None
+        }
+    }
+
+    pub fn to_nibble(&self) -> u8 {
+        match self {
+            PointerMode::None       => 0,
+            PointerMode::Increment  => 1,
+            PointerMode::Decrement  => 2,
+            PointerMode::AddQ0      => 3,
+            PointerMode::SubQ0      => 4,
+            PointerMode::AddQ1      => 5,
+            PointerMode::SubQ1      => 6,
+        }
+    }
+
+    // The offset register, QXn for the IDX pointers and QRn for the GPRs
+    pub fn offset_reg(&self, pointer: Reg) -> Option<Reg> {
+        let idx = is_index_pointer(pointer);
+        match (self, idx) {
+            (PointerMode::AddQ0, true)  | (PointerMode::SubQ0, true)  => Some(Reg::QX0),
+            (PointerMode::AddQ1, true)  | (PointerMode::SubQ1, true)  => Some(Reg::QX1),
+            (PointerMode::AddQ0, false) | (PointerMode::SubQ0, false) => Some(Reg::QR0),
+            (PointerMode::AddQ1, false) | (PointerMode::SubQ1, false) => Some(Reg::QR1),
+            _ => None
+        }
+    }
+}
+
+fn is_index_pointer(reg: Reg) -> bool {
+    reg == Reg::IDX0 || reg == Reg::IDX1
+}
+
+// Builds the operand for a pointer and its ⊗ nibble
+pub fn pointer_operand(pointer: Reg, mode: PointerMode) -> Operand {
+    match (mode, mode.offset_reg(pointer)) {
+        (PointerMode::Increment, _)                     => Operand::IndirectPostIncrement(pointer),
+        (PointerMode::Decrement, _)                     => Operand::IndirectPostDecrement(pointer),
+        (PointerMode::AddQ0, Some(q)) |
+        (PointerMode::AddQ1, Some(q))                   => Operand::IndirectPostAdd(pointer, q),
+        (PointerMode::SubQ0, Some(q)) |
+        (PointerMode::SubQ1, Some(q))                   => Operand::IndirectPostSub(pointer, q),
+        _                                               => Operand::Indirect(pointer),
+    }
+}
+
+// The reverse of pointer_operand.  None if the operand isn't a MAC pointer or the offset
+// register doesn't go with the pointer (QXj for the IDX pointers, QRj for the GPRs).
+pub fn pointer_mode(op: &Operand) -> Option<(Reg, PointerMode)> {
+    let (pointer, mode) = match *op {
+        Operand::Indirect(r)                => (r, PointerMode::None),
+        Operand::IndirectPostIncrement(r)   => (r, PointerMode::Increment),
+        Operand::IndirectPostDecrement(r)   => (r, PointerMode::Decrement),
+        Operand::IndirectPostAdd(r, Reg::QX0) |
+        Operand::IndirectPostAdd(r, Reg::QR0) => (r, PointerMode::AddQ0),
+        Operand::IndirectPostAdd(r, Reg::QX1) |
+        Operand::IndirectPostAdd(r, Reg::QR1) => (r, PointerMode::AddQ1),
+        Operand::IndirectPostSub(r, Reg::QX0) |
+        Operand::IndirectPostSub(r, Reg::QR0) => (r, PointerMode::SubQ0),
+        Operand::IndirectPostSub(r, Reg::QX1) |
+        Operand::IndirectPostSub(r, Reg::QR1) => (r, PointerMode::SubQ1),
+        _ => return None
+    };
+
+    if !is_index_pointer(pointer) && (pointer.to_reg4().is_err() || !pointer.is_word_register()) {
+        return None;
+    }
+
+    match *op {
+        Operand::IndirectPostAdd(_, q) |
+        Operand::IndirectPostSub(_, q) if mode.offset_reg(pointer) != Some(q) => None,
+        _ => Some((pointer, mode))
+    }
+}
+
+// IDX0 or IDX1 as the X nibble of the CoXXX encodings
+pub fn index_number(pointer: Reg) -> Option<u8> {
+    match pointer {
+        Reg::IDX0 => Some(0),
+        Reg::IDX1 => Some(1),
+        _ => None
+    }
+}
+
+pub fn index_pointer(number: u8) -> Option<Reg> {
+    match number {
+        0 => Some(Reg::IDX0),
+        1 => Some(Reg::IDX1),
+        _ => None
+    }
+}
+
+// The MAC unit registers as numbered in the CoSTORE encodings
+pub fn mac_reg(number: u8) -> Option<Reg> {
+    match number {
+        0 => Some(Reg::MSW),
+        1 => Some(Reg::MAH),
+        2 => Some(Reg::MAL),
+        3 => Some(Reg::MAS),
+        4 => Some(Reg::MRW),
+        5 => Some(Reg::MCW),
+        _ => None
+    }
+}
+
+pub fn mac_reg_number(reg: Reg) -> Option<u8> {
+    (0..6).find(|number| mac_reg(*number) == Some(reg))
+}
@@ -0,0 +1,143 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+// How 16-bit data addresses turn into 24-bit physical ones.  The top two bits of a data
+// address pick a DPP, which supplies the page the lower 14 bits are an offset into.  An
+// EXTP*/EXTS* window replaces the DPPs with its own page or segment for a few instructions.
+
+use ::instruction::OperandType;
+use ::reg::Reg;
+
+// The page or segment an EXTP*/EXTS* window forces, None when it comes from a GPR
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Override {
+    Page(Option<u16>),
+    Segment(Option<u16>),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MemoryModel {
+    // The 10-bit page each DPP holds, None once it's been loaded with something unknown
+    pub dpp: [Option<u16>; 4],
+    // SYSCON.SGTDIS clear.  Without segmentation only the low two bits of a page reach the bus.
+    pub segmented: bool,
+    pub ext: Option<Override>,
+    // Left unknown rather than assuming the reset value, startup code always moves it
+    pub cp: Option<u16>,
+}
+
+// Whether the mem operand at index accesses a byte, the operand types can't tell (movb's reg8 is a
+// WordRegister).  movbz/movbs read a byte from mem but write a word to it.
+pub fn byte_access(mnemonic: &str, index: usize) -> bool {
+    match mnemonic {
+        "addb" | "addcb" | "andb" | "cmpb" | "movb" | "orb" | "subb" | "subcb" | "xorb" => true,
+        "movbz" | "movbs" => index == 1,
+        _ => false
+    }
+}
+
+// The sixteen GPRs as they sit in RAM at CP, R0 first
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RegisterBank {
+    pub cp: u16,
+}
+
+impl RegisterBank {
+    // CP is always word aligned
+    pub fn new(cp: u16) -> RegisterBank {
+        RegisterBank {
+            cp: cp & 0xFFFE
+        }
+    }
+
+    pub fn contains(&self, address: u16) -> bool {
+        address >= self.cp && (address as u32) < self.cp as u32 + 0x20
+    }
+
+    // Where a GPR lives, RLn and RHn are the two halves of Rn.  None if a CP near the top of the
+    // 64K puts it past the end.
+    pub fn address(&self, reg: Reg) -> Option<u16> {
+        let number = reg.to_reg4().ok()? as u16;
+        match reg.is_word_register() {
+            true => self.cp.checked_add(number * 2),
+            false => self.cp.checked_add(number),
+        }
+    }
+
+    // The GPR at address, a byte register when byte is set
+    pub fn register(&self, address: u16, byte: bool) -> Option<Reg> {
+        if !self.contains(address) {
+            return None;
+        }
+
+        let offset = (address - self.cp) as u8;
+        match byte {
+            true => Reg::from_reg4(offset, &OperandType::ByteRegister(0)).ok(),
+            false if offset & 1 == 0 => Reg::from_reg4(offset / 2, &OperandType::WordRegister(0)).ok(),
+            false => None
+        }
+    }
+}
+
+impl Default for MemoryModel {
+    // As the part comes out of reset, DPPn = n so data addresses map straight through
+    fn default() -> MemoryModel {
+        MemoryModel {
+            dpp: [Some(0), Some(1), Some(2), Some(3)],
+            segmented: true,
+            ext: None,
+            cp: None,
+        }
+    }
+}
+
+impl MemoryModel {
+    pub fn new() -> MemoryModel {
+        MemoryModel::default()
+    }
+
+    // The DPP a data address goes through
+    pub fn dpp_number(address: u16) -> usize {
+        (address >> 14) as usize
+    }
+
+    pub fn bank(&self) -> Option<RegisterBank> {
+        self.cp.map(RegisterBank::new)
+    }
+
+    pub fn set_dpp(&mut self, dpp: usize, page: Option<u16>) {
+        self.dpp[dpp] = page.map(|page| page & 0x03FF);
+    }
+
+    // The 24-bit physical address a 16-bit data address refers to, None when that
+    // depends on something that isn't known
+    pub fn translate(&self, address: u16) -> Option<u32> {
+        let offset = address as u32 & 0x3FFF;
+
+        match self.ext {
+            Some(Override::Page(page)) => return page.map(|page| ((page as u32 & 0x03FF) << 14) | offset),
+            Some(Override::Segment(segment)) => return segment.map(|segment| ((segment as u32 & 0xFF) << 16) | address as u32),
+            None => {}
+        }
+
+        let page = self.dpp[MemoryModel::dpp_number(address)]? as u32;
+        match self.segmented {
+            true => Some((page << 14) | offset),
+            false => Some(((page & 0b11) << 14) | offset),
+        }
+    }
+}
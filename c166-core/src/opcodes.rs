@@ -0,0 +1,67 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use ::cpu::CpuModel;
+use ::encoding::EncodingType;
+use ::instruction::*;
+use ::mac::MacOp;
+
+// Opcodes shared by several mnemonics are filed under a combined name
+pub fn table_mnemonic(mnemonic: &str) -> &str {
+    match mnemonic {
+        "extp" | "extpr" | "exts" | "extsr" => "ext*",
+        "atomic" | "extr" => "atomic_extr",
+        _ if MacOp::from_mnemonic(mnemonic).is_some() => "co*",
+        _ => mnemonic
+    }
+}
+
+pub fn lookup(opcode: u8) -> Option<&'static Instruction<'static>> {
+    OPCODES[opcode as usize].as_ref()
+}
+
+// Every defined opcode, in opcode order
+pub fn instructions() -> impl Iterator<Item = &'static Instruction<'static>> {
+    OPCODES.iter().filter_map(|isn| isn.as_ref())
+}
+
+// Every form of a mnemonic
+pub fn forms(mnemonic: &str) -> impl Iterator<Item = &'static Instruction<'static>> {
+    let mnemonic: String = table_mnemonic(mnemonic).to_string();
+    instructions().filter(move |isn| isn.mnemonic == mnemonic)
+}
+
+pub fn by_encoding(encoding: EncodingType) -> impl Iterator<Item = &'static Instruction<'static>> {
+    instructions().filter(move |isn| isn.encoding == encoding)
+}
+
+// The distinct operand signatures a mnemonic accepts
+pub fn signatures(mnemonic: &str) -> Vec<Signature> {
+    let mut signatures: Vec<Signature> = vec![];
+
+    for isn in forms(mnemonic) {
+        let signature = isn.signature();
+        if !signatures.contains(&signature) {
+            signatures.push(signature);
+        }
+    }
+
+    signatures
+}
+
+// The table and the OP_XX entries are generated by build.rs from isa/c166.toml
+include!(concat!(env!("OUT_DIR"), "/opcodes.rs"));
@@ -19,85 +19,261 @@ use std::fmt;
 
 use ::instruction::*;
 use ::bitaddr::*;
+use ::cpu::CpuModel;
+use ::decoder::relative_target;
+use ::reg::Reg;
+use ::sfr::RegisterMap;
 
-impl<'a> fmt::Display for Operand {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match &self {
+// The assembler a listing is meant for.  Beyond picking the defaults for the other options it
+// decides the spacing of [Rw+#data16].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Dialect {
+    R2,
+    KeilA166,
+    Tasking,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HexStyle {
+    // FF30h
+    Suffix,
+    // 0FF30h, the leading digit keeps assemblers from taking it for a symbol
+    LeadingZero,
+    // 0xFF30
+    Prefix,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Case {
+    // Lower case mnemonics and GPRs, SFRs as the manual names them
+    Mixed,
+    Upper,
+    Lower,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FormatOptions {
+    pub dialect: Dialect,
+    pub hex: HexStyle,
+    pub case: Case,
+    // #data rather than data
+    pub immediate_hash: bool,
+    // cc_UC rather than UC
+    pub condition_prefix: bool,
+    // Where the names of bit operands come from
+    pub registers: &'static RegisterMap,
+}
+
+impl Default for FormatOptions {
+    fn default() -> FormatOptions {
+        FormatOptions {
+            dialect: Dialect::R2,
+            hex: HexStyle::Suffix,
+            case: Case::Mixed,
+            immediate_hash: true,
+            condition_prefix: true,
+            registers: CpuModel::default().registers(),
+        }
+    }
+}
+
+impl FormatOptions {
+    pub fn keil() -> FormatOptions {
+        FormatOptions {
+            dialect: Dialect::KeilA166,
+            hex: HexStyle::LeadingZero,
+            case: Case::Upper,
+            ..FormatOptions::default()
+        }
+    }
+
+    pub fn tasking() -> FormatOptions {
+        FormatOptions {
+            dialect: Dialect::Tasking,
+            hex: HexStyle::LeadingZero,
+            case: Case::Lower,
+            ..FormatOptions::default()
+        }
+    }
+
+    // A comma separated list as r2's asm.features holds it, e.g. "keil,0x".  A preset comes first
+    // and the rest adjust it, anything unknown is left for someone else.
+    pub fn from_features(features: &str) -> FormatOptions {
+        let mut options = FormatOptions::default();
+
+        for feature in features.split(',').map(|feature| feature.trim().to_lowercase()) {
+            match feature.as_str() {
+                "r2" => options = FormatOptions::default(),
+                "keil" | "a166" => options = FormatOptions::keil(),
+                "tasking" => options = FormatOptions::tasking(),
+                "0x" => options.hex = HexStyle::Prefix,
+                "h" => options.hex = HexStyle::Suffix,
+                "0h" => options.hex = HexStyle::LeadingZero,
+                "mixed" => options.case = Case::Mixed,
+                "upper" => options.case = Case::Upper,
+                "lower" => options.case = Case::Lower,
+                "nohash" => options.immediate_hash = false,
+                "nocc" => options.condition_prefix = false,
+                _ => {}
+            }
+        }
+
+        options
+    }
+
+    // value with at least digits hex digits, the digits themselves are always upper case
+    pub fn hex(&self, value: u32, digits: usize) -> String {
+        let hex = format!("{:0width$X}", value, width = digits);
+        let suffix = match self.case {
+            Case::Upper => "H",
+            _ => "h"
+        };
+
+        match self.hex {
+            HexStyle::Suffix => format!("{}{}", hex, suffix),
+            HexStyle::LeadingZero if hex.starts_with(|c: char| c.is_ascii_alphabetic()) => format!("0{}{}", hex, suffix),
+            HexStyle::LeadingZero => format!("{}{}", hex, suffix),
+            HexStyle::Prefix => format!("0x{}", hex),
+        }
+    }
+
+    // Mnemonics, registers, bits and conditions
+    pub fn name(&self, name: &str) -> String {
+        match self.case {
+            Case::Mixed => name.to_string(),
+            Case::Upper => name.to_uppercase(),
+            Case::Lower => name.to_lowercase(),
+        }
+    }
+
+    fn immediate(&self, value: String) -> String {
+        match self.immediate_hash {
+            true => format!("#{}", value),
+            false => value
+        }
+    }
+
+    // Registers without a name are shown by address
+    pub fn reg(&self, reg: &Reg) -> String {
+        match *reg {
+            Reg::SFR(_) | Reg::ESFR(_) => match reg.to_phys16() {
+                Ok(address) => self.hex(address as u32, 4),
+                Err(_) => self.name(&format!("{}", reg))
+            },
+            _ => self.name(&format!("{}", reg))
+        }
+    }
 
+    fn condition(&self, condition: &OpCondition) -> String {
+        let name = format!("{}", condition);
+
+        match self.condition_prefix {
+            true => self.name(&name),
+            false => self.name(name.trim_start_matches("cc_"))
+        }
+    }
+}
+
+impl Operand {
+    pub fn format(&self, options: &FormatOptions) -> String {
+        match &self {
             Operand::BitAddr(offset, bit)               => {
-                let mnem = bitoff_to_string(*offset as u8, false).unwrap();
-                match bit {
-                    0xFF        => write!(f, "{}", mnem),
+                let formatted = match bit {
+                    0xFF        => bitoff_to_string(*offset, false, options),
                     0x10..=0xFF  =>  panic!("BitAddr requires a bit offset 0x00..=0x0F"),
-                    _           => write!(f, "{}.{}", mnem, bit)
-                }
+                    _           => bitaddr_to_string(*offset, *bit, false, options)
+                };
+
+                // Not every offset is a register the map knows
+                formatted.unwrap_or_else(|_| options.hex(*offset as u32, 2))
             },
-            Operand::Register(r)                    => { write!(f, "{}", r) },                  // SFR, ESFR, GPR
-            Operand::Direct(d, width)               => {
-                match width {
-                    2 => write!(f, "{:X}", d),
-                    4 |
-                    8 => write!(f, "{:02X}h", d),
-                    _ => write!(f, "{:04X}h", d)
+            Operand::Register(r)                    => options.reg(r),                     // SFR, ESFR, GPR
+            Operand::Mem16(m)                       => options.hex(*m as u32, 4),
+            Operand::CodeAddr16(c)                  => options.hex(*c as u32, 4),
+            Operand::Segment8(s)                    => options.hex(*s as u32, 2),
+            // Without the instruction's address all there is to show is the encoded offset
+            Operand::Rel8(r)                        => options.hex(*r as u8 as u32, 2),
+            Operand::Indirect(r)                    => format!("[{}]", options.reg(r)), // [GPR],
+            Operand::IndirectPostIncrement(r)       => format!("[{}+]", options.reg(r)), // [GPR+]
+            Operand::IndirectPreDecrement(r)        => format!("[-{}]", options.reg(r)), // [-GPR]
+            Operand::IndirectAndImmediate(ind, imm) => { // [GPR+DATA16],
+                let offset = options.immediate(options.hex(*imm as u32, 1));
+                match options.dialect {
+                    Dialect::R2 => format!("[{} + {}]", options.reg(ind), offset),
+                    _ => format!("[{}+{}]", options.reg(ind), offset)
                 }
-            }, // mem, caddr, seg, rel
-            Operand::Indirect(r)                    => { write!(f, "[{}]", r) }, // [GPR],
-            Operand::IndirectPostIncrement(r)       => { write!(f, "[{}+]", r) }, // [GPR+]
-            Operand::IndirectPreDecrement(r)        => { write!(f, "[-{}]", r) }, // [-GPR]
-            Operand::IndirectAndImmediate(ind, imm) => { write!(f, "[{} + #{:X}h]", ind, imm) }, // [GPR+DATA16],
-            Operand::Immediate(imm, width)          => { // #data3, #data4, #data8, #data16, #mask8, #trap7, #pag10, #seg8, #irang2
+            },
+            Operand::IndirectPostDecrement(r)       => format!("[{}-]", options.reg(r)), // [GPR-]
+            Operand::IndirectPostAdd(r, q)          => format!("[{}+{}]", options.reg(r), options.reg(q)), // [GPR+QRj]
+            Operand::IndirectPostSub(r, q)          => format!("[{}-{}]", options.reg(r), options.reg(q)), // [GPR-QRj]
+            Operand::Immediate(imm, width)          => { // #data3, #data4, #data8, #data16, #mask8, #seg8, #irang2
                 match width {
-                    2 => write!(f, "#{:X}", imm),
+                    // 1 to 4 reads the same in any base
+                    2 => options.immediate(format!("{:X}", imm)),
                     3 |
                     4 |
-                    7 |
-                    8 => write!(f, "#{:02X}h", imm),
-                    10 |
-                    16 => write!(f, "#{:04X}h", imm),
-                    _ => write!(f, "{:X}h", imm),
+                    8 => options.immediate(options.hex(*imm as u32, 2)),
+                    16 => options.immediate(options.hex(*imm as u32, 4)),
+                    _ => options.hex(*imm as u32, 1),
                 }
             },
-            Operand::Condition(c)                   => { write!(f, "{}", c) },
+            Operand::Page10(page)                   => options.immediate(options.hex(*page as u32, 4)),
+            Operand::Trap7(trap)                    => options.immediate(options.hex(*trap as u32, 2)),
+            Operand::Condition(c)                   => options.condition(c),
         }
     }
 }
 
-pub fn expand_op(op: &Operand, op_type: &OperandType, pc: u32) -> String {
-    if let Operand::Direct(d, _) = op {
-        if let OperandType::DirectRelative8S = *op_type {
-            let direct: u16 = *d;
-            return format!("{}", Operand::Direct((direct * 2) + (pc as u16), 16));
+impl<'a> fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.format(&FormatOptions::default()))
+    }
+}
+
+pub fn expand_op(op: &Operand, pc: u32, length: usize, extended: bool, options: &FormatOptions) -> String {
+    // Relative operands are shown as the IP they go to
+    if let Operand::Rel8(rel) = op {
+        let target = relative_target(*rel, pc, length);
+        return Operand::CodeAddr16(target as u16).format(options);
+    }
+
+    // Bit operands only know they're ESFR bits from the surrounding EXT* window
+    if let Operand::BitAddr(offset, bit) = op {
+        if extended {
+            let formatted = match bit {
+                0xFF => bitoff_to_string(*offset, true, options),
+                _ => bitaddr_to_string(*offset, *bit, true, options)
+            };
+
+            if let Ok(formatted) = formatted {
+                return formatted;
+            }
         }
     }
 
-    format!("{}", op)
+    op.format(options)
 }
 
 pub fn format_op(isn: &Instruction, values: &InstructionArguments, pc: u32) -> String {
+    format_op_with(isn, values, pc, &FormatOptions::default())
+}
+
+pub fn format_op_with(isn: &Instruction, values: &InstructionArguments, pc: u32, options: &FormatOptions) -> String {
     let mnemonic = match values.mnemonic.as_ref() {
         Some(mnem) => mnem,
         _ => isn.mnemonic,
     };
 
-     match (&isn.op1, &isn.op2, &isn.op3) {
-         (None, None, None) => {
-             format!("{}", mnemonic)
-         }
-         (Some(ref op1), None, None) => {
-             let val1 = expand_op(values.op1.as_ref().unwrap(), &op1, pc);
-             format!("{} {}", mnemonic, val1)
-         },
-         (Some(ref op1), Some(ref op2), None) => {
-             let val1 = expand_op(values.op1.as_ref().unwrap(), &op1, pc);
-             let val2 = expand_op(values.op2.as_ref().unwrap(), &op2, pc);
-             format!("{} {}, {}", mnemonic, val1, val2)
-         },
-         (Some(ref op1), Some(ref op2), Some(ref op3)) => {
-             let val1 = expand_op(values.op1.as_ref().unwrap(), &op1, pc);
-             let val2 = expand_op(values.op2.as_ref().unwrap(), &op2, pc);
-             let val3 = expand_op(values.op3.as_ref().unwrap(), &op3, pc);
-             format!("{} {}, {}, {}", mnemonic, val1, val2, val3)
-         }
-         _ => format!("")
-     }
+    // Some encodings leave trailing operands out (e.g. the MAC ops that don't take both)
+    let operands: Vec<String> = [(isn.op1, values.op1), (isn.op2, values.op2), (isn.op3, values.op3)].iter()
+        .filter_map(|operand| match operand {
+            (Some(_), Some(op)) => Some(expand_op(op, pc, isn.encoding.length(), values.extended, options)),
+            _ => None
+        })
+        .collect();
+
+    match operands.is_empty() {
+        true => options.name(mnemonic),
+        false => format!("{} {}", options.name(mnemonic), operands.join(", "))
+    }
 }
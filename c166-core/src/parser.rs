@@ -18,12 +18,19 @@
 use nom::*;
 use std::str;
 use std::str::FromStr;
-use std::convert::TryFrom;
 use std::collections::HashMap;
+use std::num::ParseIntError;
 
+use ::cpu::CpuModel;
+use ::error::{EncodeError, RegError};
+use ::expr::{BinaryOp, Expr, UnaryOp};
 use ::instruction::*;
 use ::reg::*;
 use ::encoding::*;
+use ::mac::{index_number, mac_reg_number, pointer_mode, MacOp, MacShape};
+use ::memory::{byte_access, RegisterBank};
+use ::opcodes::{instructions, table_mnemonic};
+use ::sfr::{BitField, RegisterInfo, RegisterMap};
 
 #[derive(Debug)]
 pub struct AsmOperation<'a> {
@@ -50,6 +57,10 @@ fn is_alphanumeric_s(chr: char) -> bool {
   is_alphabetic_s(chr) || is_digit_s(chr)
 }
 
+fn from_hex(digits: &str) -> Result<u16, ParseIntError> {
+    u16::from_str_radix(digits, 16)
+}
+
 // Per the nom source since we don't want to trim newlines
 //  (you never know, r2 could pass in a whole buffer at once)
 named!(pub space(&str) -> &str, eat_separator!(&" \t"[..]));
@@ -74,102 +85,163 @@ macro_rules! sp (
 );
 
 // TODO: Validate list of instruction mnemonics
+// Some of the MAC ops end in a '-' (e.g. comul-)
 named!(mnemonic(&str) -> &str,
     do_parse!(
-        mnem: take_while1!(is_alphanumeric_s) >>
+        mnem: recognize!(
+            pair!(
+                take_while1!(is_alphanumeric_s),
+                opt!(tag_s!("-"))
+            )
+        ) >>
         (mnem)
     )
 );
 
 named!(op_condition(&str) -> Operand,
     do_parse!(
-        cond: recognize!(
-            preceded!(
-                tag_s!("cc_"),
-                take_while1!(is_alphanumeric_s)
-            )
+        cond: map_res!(
+            recognize!(
+                preceded!(
+                    tag_s!("cc_"),
+                    take_while1!(is_alphanumeric_s)
+                )
+            ),
+            OpCondition::from_str
         ) >>
         (
-            Operand::Condition(OpCondition::from_str(cond).unwrap())
+            Operand::Condition(cond)
         )
     )
 );
 
-
-named!(op_word_gpr(&str) -> Operand,
-    do_parse!(
-        register: recognize!(
+named!(word_gpr(&str) -> Reg,
+    map_res!(
+        recognize!(
             preceded!(
                 tag_s!("r"),
                 take_while_m_n!(1, 2, is_digit_s)
             )
-        ) >>
-        (
-            Operand::Register(Reg::from_str(register).unwrap())
-        )
+        ),
+        Reg::from_str
     )
 );
 
-named!(op_byte_gpr(&str) -> Operand,
-    do_parse!(
-        register: recognize!(
+named!(byte_gpr(&str) -> Reg,
+    map_res!(
+        recognize!(
             preceded!(
                 alt!(tag_s!("rl") | tag_s!("rh")),
                 take_while_m_n!(1, 1, is_digit_s)
             )
-        ) >>
-        (
-            Operand::Register(Reg::from_str(register).unwrap())
-        )
+        ),
+        Reg::from_str
     )
 );
 
-// TODO: Validate list of register mnemonics
-named!(op_reg(&str) -> Operand,
+// MAC pointers and their offset registers
+named!(index_reg(&str) -> Reg,
+    map_res!(
+        recognize!(
+            preceded!(
+                alt!(tag_s!("idx") | tag_s!("IDX")),
+                take_while_m_n!(1, 1, is_digit_s)
+            )
+        ),
+        Reg::from_str
+    )
+);
+
+named!(offset_reg(&str) -> Reg,
+    map_res!(
+        recognize!(
+            preceded!(
+                alt!(tag_s!("qx") | tag_s!("qr") | tag_s!("QX") | tag_s!("QR")),
+                take_while_m_n!(1, 1, is_digit_s)
+            )
+        ),
+        Reg::from_str
+    )
+);
+
+named!(op_word_gpr(&str) -> Operand,
     do_parse!(
-        register: take_while1!(is_alphanumeric_s) >>
+        register: word_gpr >>
         (
-            Operand::Register(Reg::from_str(register).unwrap())
+            Operand::Register(register)
         )
     )
 );
 
-named!(op_direct(&str) -> Operand,
+named!(op_byte_gpr(&str) -> Operand,
     do_parse!(
-        direct: terminated!(take_while1!(is_hex_digit_s), tag_s!("h")) >>
+        register: byte_gpr >>
         (
-            Operand::Direct(u16::from_str_radix(direct, 16).unwrap(), 0)
+            Operand::Register(register)
         )
     )
 );
 
-named!(op_immediate_hex(&str) -> Operand,
+// TODO: Validate list of register mnemonics
+named!(op_reg(&str) -> Operand,
     do_parse!(
-        immed: delimited!(
-            tag_s!("#"),
-            take_while1!(is_hex_digit_s),
-            tag_s!("h")
-        ) >>
+        register: map_res!(take_while1!(is_alphanumeric_s), Reg::from_str) >>
         (
-            Operand::Immediate(u16::from_str_radix(immed, 16).unwrap(), 0)
+            Operand::Register(register)
         )
     )
 );
 
-named!(op_immediate_dec(&str) -> Operand,
+fn is_binary_digit_s(chr: char) -> bool {
+    chr == '0' || chr == '1'
+}
+
+// 'A' or 'AB', the first character in the high byte as A166 has it
+fn char_value(text: &str) -> Option<u32> {
+    match text.len() {
+        1 | 2 => Some(text.bytes().fold(0, |value, byte| (value << 8) | byte as u32)),
+        _ => None
+    }
+}
+
+// 1234, 1234d, 4D2h, 0x4D2, 10011010010b or a character
+named!(literal(&str) -> u32,
+    alt!(
+        map_res!(preceded!(tag_no_case!("0x"), take_while1!(is_hex_digit_s)), |digits| u32::from_str_radix(digits, 16)) |
+        map_res!(terminated!(take_while1!(is_hex_digit_s), tag_no_case!("h")), |digits| u32::from_str_radix(digits, 16)) |
+        map_res!(terminated!(take_while1!(is_binary_digit_s), tag_no_case!("b")), |digits| u32::from_str_radix(digits, 2)) |
+        map_res!(terminated!(take_while1!(is_digit_s), opt!(tag_no_case!("d"))), u32::from_str) |
+        map_opt!(delimited!(tag!("'"), take_until!("'"), tag!("'")), char_value)
+    )
+);
+
+// A literal that doesn't run on into a name (FF30h, not FF30hx)
+named!(number(&str) -> u32,
+    terminated!(literal, not!(take_while_m_n!(1, 1, is_symbol_char)))
+);
+
+named!(number16(&str) -> u16,
+    map_opt!(number, |value: u32| match value <= 0xFFFF {
+        true => Some(value as u16),
+        false => None
+    })
+);
+
+// A bare address reads as memory, typed_operands makes it whatever the encoding's slot takes
+named!(op_direct(&str) -> Operand,
     do_parse!(
-        immed: preceded!(tag_s!("#"), take_while_m_n!(1, 1, is_digit_s)) >>
+        direct: number16 >>
         (
-            Operand::Immediate(immed.parse::<u8>().unwrap() as u16, 4)
+            Operand::Mem16(direct)
         )
     )
 );
 
 named!(op_immediate(&str) -> Operand,
     do_parse!(
-        immed: alt!(op_immediate_hex | op_immediate_dec) >>
+        immed: preceded!(tag_s!("#"), number16) >>
         (
-            immed
+            Operand::Immediate(immed, 0)
         )
     )
 );
@@ -178,14 +250,11 @@ named!(op_indirect(&str) -> Operand,
     do_parse!(
         register: delimited!(
             tag_s!("["),
-            sp!(alt!(op_word_gpr | op_byte_gpr)),
+            sp!(alt!(word_gpr | byte_gpr | index_reg)),
             tag_s!("]")
         ) >>
         (
-            match register {
-                Operand::Register(r) => Operand::Indirect(r),
-                _ => unreachable!()
-            }
+            Operand::Indirect(register)
         )
     )
 );
@@ -194,13 +263,39 @@ named!(op_indirect_inc(&str) -> Operand,
     do_parse!(
         register: delimited!(
             tag_s!("["),
-            sp!(alt!(op_word_gpr | op_byte_gpr)),
+            sp!(alt!(word_gpr | byte_gpr | index_reg)),
             tag_s!("+]")
         ) >>
         (
-            match register {
-                Operand::Register(r) => Operand::IndirectPostIncrement(r),
-                _ => unreachable!()
+            Operand::IndirectPostIncrement(register)
+        )
+    )
+);
+
+named!(op_indirect_post_dec(&str) -> Operand,
+    do_parse!(
+        register: delimited!(
+            tag_s!("["),
+            sp!(alt!(word_gpr | index_reg)),
+            tag_s!("-]")
+        ) >>
+        (
+            Operand::IndirectPostDecrement(register)
+        )
+    )
+);
+
+named!(op_indirect_post_offset(&str) -> Operand,
+    do_parse!(
+        tag_s!("[") >>
+        register: sp!(alt!(word_gpr | index_reg)) >>
+        sign: sp!(alt!(tag_s!("+") | tag_s!("-"))) >>
+        offset: sp!(offset_reg) >>
+        tag_s!("]") >>
+        (
+            match sign {
+                "+" => Operand::IndirectPostAdd(register, offset),
+                _   => Operand::IndirectPostSub(register, offset)
             }
         )
     )
@@ -210,14 +305,11 @@ named!(op_indirect_dec(&str) -> Operand,
     do_parse!(
         register: delimited!(
             tag_s!("[-"),
-            sp!(alt!(op_word_gpr | op_byte_gpr)),
+            sp!(alt!(word_gpr | byte_gpr)),
             tag_s!("]")
         ) >>
         (
-            match register {
-                Operand::Register(r) => Operand::IndirectPreDecrement(r),
-                _ => unreachable!()
-            }
+            Operand::IndirectPreDecrement(register)
         )
     )
 );
@@ -225,79 +317,106 @@ named!(op_indirect_dec(&str) -> Operand,
 named!(op_indirect_imm(&str) -> Operand,
     do_parse!(
         tag_s!("[") >>
-        register: sp!(alt!(op_word_gpr | op_byte_gpr)) >>
+        register: sp!(alt!(word_gpr | byte_gpr)) >>
         sp!(tag_s!("+")) >>
-        immediate: sp!(op_immediate) >>
+        immediate: sp!(preceded!(tag_s!("#"), number16)) >>
         tag_s!("]") >>
         (
-            match register {
-                Operand::Register(r) => {
-                    Operand::IndirectAndImmediate(
-                        r,
-                        match immediate {
-                            Operand::Immediate(d, _width) => d,
-                            _ => unreachable!()
-                        }
-                    )
-                },
-                _ => unreachable!()
-            }
+            Operand::IndirectAndImmediate(register, immediate)
         )
     )
 );
 
-fn get_reg8(op: Operand) -> u8 {
-    match op {
-        Operand::Register(reg) => reg.to_reg8().unwrap(),
-        _ => unreachable!()
+// A single bit field of a bit-addressable SFR, as the bit instructions see it
+fn named_bit(register: &RegisterInfo, field: &BitField) -> Result<Operand, RegError> {
+    match (register.bit_offset(), field.width) {
+        (Some(offset), 1) => Ok(Operand::BitAddr(offset, field.bit)),
+        _ => Err(RegError::UnknownName(format!("{}.{}", register.name, field.name)))
+    }
+}
+
+// REG.FIELD, e.g. PSW.IEN
+fn get_field_bitaddr(bitname: &str, field_name: &str) -> Result<Operand, RegError> {
+    let register = RegisterMap::builtins()
+        .filter_map(|map| map.by_name(bitname))
+        .next()
+        .ok_or_else(|| RegError::UnknownName(bitname.to_string()))?;
+
+    match register.field(field_name) {
+        Some(field) => named_bit(register, field),
+        None => Err(RegError::UnknownName(format!("{}.{}", bitname, field_name)))
     }
 }
 
-fn get_bitaddr(bitname: &str, bitpos: &str) -> Operand {
-    let pos: u8 = bitpos.parse::<u8>().expect("Bit position must be a valid base-10 number");
-    match u16::from_str_radix(&bitname[0..bitname.len()-1], 16) {
-        Ok(addr) => {
+fn get_bitaddr((bitname, bitpos): (&str, &str)) -> Result<Operand, RegError> {
+    if !bitpos.chars().all(is_digit_s) {
+        return get_field_bitaddr(bitname, bitpos);
+    }
+
+    let pos: u8 = bitpos.parse::<u8>().unwrap_or(0xFF);
+    if pos > 15 {
+        return Err(RegError::InvalidBit(pos));
+    }
+
+    let address = match bitname.ends_with("h") {
+        true => from_hex(&bitname[0..bitname.len()-1]).ok(),
+        false => None
+    };
+
+    match address {
+        Some(addr) => {
             match addr {
                 0xFD00..=0xFDFE => {
                     // RAM
                     let short_addr: u8 = ((addr - 0xFD00) / 2) as u8;
-                    Operand::BitAddr(short_addr, pos)
+                    Ok(Operand::BitAddr(short_addr, pos))
                 },
                 0xFF00..=0xFFDE => {
                     // SFR
-                    eprintln!("Maybe consider referencing SFR by mnemonic?");
                     let short_addr: u8 = (((addr - 0xFF00)/2) + 0x80) as u8;
-                    Operand::BitAddr(short_addr, pos)
+                    Ok(Operand::BitAddr(short_addr, pos))
                 },
                 0xF100..=0xF1DE => {
                     // ESFR
-                    eprintln!("Maybe consider referencing ESFR by mnemonic?");
                     let short_addr: u8 = (((addr - 0xF100)/2) + 0x80) as u8;
-                    Operand::BitAddr(short_addr, pos)
+                    Ok(Operand::BitAddr(short_addr, pos))
                 },
-                // TODO: Grab CP and see if we're attempting to frob GPRs directly
-                _ => unreachable!()
+                // GPRs move with CP, so their bits have to be written as Rn.x
+                _ => Err(RegError::UnknownAddress(addr))
             }
         },
-        Err(_) => {
-            let register = Operand::Register(Reg::from_str(bitname).unwrap());
-            Operand::BitAddr(get_reg8(register), pos)
+        None => {
+            let register = Reg::from_str(bitname)?;
+            Ok(Operand::BitAddr(register.to_reg8()?, pos))
         }
     }
 }
 
 // TODO: Handle numeric bitoffs
 named!(op_bitaddr(&str) -> Operand,
-    do_parse!(
-        bitname: take_while1!(is_alphanumeric_s) >>
-        tag_s!(".") >>
-        bit: take_while_m_n!(1, 2, is_digit_s) >>
-        (
-            get_bitaddr(bitname, bit)
-        )
+    map_res!(
+        do_parse!(
+            bitname: take_while1!(is_alphanumeric_s) >>
+            tag_s!(".") >>
+            bit: take_while1!(is_alphanumeric_s) >>
+            ((bitname, bit))
+        ),
+        get_bitaddr
     )
 );
 
+// A bit by its own name, e.g. IEN
+fn get_bit_name(name: &str) -> Result<Operand, RegError> {
+    match RegisterMap::builtins().filter_map(|map| map.by_bit_name(name)).next() {
+        Some((register, field)) => named_bit(register, field),
+        None => Err(RegError::UnknownName(name.to_string()))
+    }
+}
+
+named!(op_bit_name(&str) -> Operand,
+    map_res!(take_while1!(is_alphanumeric_s), get_bit_name)
+);
+
 named!(operand(&str) -> Operand,
     alt!(
         op_bitaddr      |
@@ -307,10 +426,13 @@ named!(operand(&str) -> Operand,
         op_indirect_inc |
         op_indirect_dec |
         op_indirect_imm |
+        op_indirect_post_dec    |
+        op_indirect_post_offset |
         op_condition    |
         op_byte_gpr     |
         op_word_gpr     |
-        op_reg
+        op_reg          |
+        op_bit_name
     )
 );
 
@@ -329,12 +451,370 @@ named!(pub asm_line(&str) -> AsmOperation,
     )
 );
 
+fn is_symbol_start(chr: char) -> bool {
+    is_alphabetic_s(chr) || chr == '_'
+}
+
+fn is_symbol_char(chr: char) -> bool {
+    is_alphanumeric_s(chr) || chr == '_'
+}
+
+// An operand written as an expression, its slot in the operation holds a placeholder until the
+// symbols in it are known
+#[derive(Clone, Debug, PartialEq)]
+pub enum Symbolic<'a> {
+    // label, SOF label + 2, $
+    Address(Expr<'a>),
+    // #label, #(BASE + 4) * 2
+    Immediate(Expr<'a>),
+    // [Rw + #label]
+    Offset(Expr<'a>),
+}
+
+impl<'a> Symbolic<'a> {
+    pub fn expr(&self) -> &Expr<'a> {
+        match *self {
+            Symbolic::Address(ref expr) | Symbolic::Immediate(ref expr) | Symbolic::Offset(ref expr) => expr
+        }
+    }
+}
+
+// Data for DB and DW
+#[derive(Clone, Debug, PartialEq)]
+pub enum DataItem<'a> {
+    Value(Expr<'a>),
+    // 'text' or "text", a byte or word per character
+    Text(&'a str),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Directive<'a> {
+    Org(Expr<'a>),
+    Db(Vec<DataItem<'a>>),
+    Dw(Vec<DataItem<'a>>),
+    // Reserves a number of bytes
+    Ds(Expr<'a>),
+    // The statement's label is the name for EQU and SET, only SET can be repeated
+    Equ(Expr<'a>),
+    Set(Expr<'a>),
+    Align(Expr<'a>),
+    Even,
+    // name SECTION type [AT address], the type doesn't change how it's assembled
+    Section(Option<Expr<'a>>),
+    Ends,
+    // Nothing after it is read
+    End,
+}
+
+// One line of a source file: a label, an operation or directive, both or neither
+#[derive(Debug)]
+pub struct Statement<'a> {
+    pub label: Option<&'a str>,
+    pub operation: Option<AsmOperation<'a>>,
+    // By index into the operation's operands
+    pub symbols: Vec<(usize, Symbolic<'a>)>,
+    pub directive: Option<Directive<'a>>,
+    // The operation or directive as written, and each of its operands or arguments, for
+    // diagnostics to point at
+    pub text: &'a str,
+    pub arguments: Vec<&'a str>,
+}
+
+named!(symbol_name(&str) -> &str,
+    recognize!(
+        pair!(
+            take_while_m_n!(1, 1, is_symbol_start),
+            take_while!(is_symbol_char)
+        )
+    )
+);
+
+// What a parser read along with what it made of it
+fn spanned<'a, O, F>(input: &'a str, parser: F) -> IResult<&'a str, (&'a str, O)>
+    where F: Fn(&'a str) -> IResult<&'a str, O> {
+    let (rest, output) = parser(input)?;
+    Ok((rest, (&input[..input.len() - rest.len()], output)))
+}
+
+named!(operand_end(&str) -> &str,
+    peek!(preceded!(space, alt!(tag!(",") | tag!(";") | line_ending | tag!("\0"))))
+);
+
+// FF30h as r2 writes it, A166 would want a leading 0 to tell it from a name
+pub fn suffixed_hex(text: &str) -> Option<u32> {
+    let digits = match text.len() > 1 && (text.ends_with('h') || text.ends_with('H')) {
+        true => &text[..text.len() - 1],
+        false => return None
+    };
+
+    match digits.chars().all(is_hex_digit_s) {
+        true => u32::from_str_radix(digits, 16).ok(),
+        false => None
+    }
+}
+
+// An operand the operand grammar reads whole, unless a name in it only looks like hex (dach,
+// each) and could be a symbol.  Those are expressions, which in r2's listings fall back on the hex
+// value when no symbol has the name.  Bit addresses stay as they are, a symbol can't stand in for
+// one.
+fn literal_operand(input: &str) -> IResult<&str, Operand> {
+    let (rest, (text, op)) = spanned(input, |input| terminated!(input, operand, operand_end))?;
+    let names_hex = text.split(|c| !is_symbol_char(c))
+        .any(|word| word.starts_with(is_symbol_start) && suffixed_hex(word).is_some());
+
+    match op {
+        Operand::BitAddr(_, _) => Ok((rest, op)),
+        _ if names_hex => Err(::nom::Err::Error(error_position!(input, ErrorKind::Verify))),
+        _ => Ok((rest, op))
+    }
+}
+
+// Anything the operand grammar reads that runs on (r2d2, beach, #1 + 2) is an expression
+named!(asm_operand(&str) -> (Operand, Option<Symbolic>),
+    alt!(
+        literal_operand => { |op| (op, None) } |
+        do_parse!(
+            tag_s!("[") >>
+            register: sp!(alt!(word_gpr | byte_gpr)) >>
+            sp!(tag_s!("+")) >>
+            tag_s!("#") >>
+            offset: sp!(expr) >>
+            tag_s!("]") >>
+            (Operand::IndirectAndImmediate(register, 0), Some(Symbolic::Offset(offset)))
+        ) |
+        preceded!(tag_s!("#"), expr) => { |value| (Operand::Immediate(0, 0), Some(Symbolic::Immediate(value))) } |
+        expr => { |value| (Operand::Mem16(0), Some(Symbolic::Address(value))) }
+    )
+);
+
+named!(operation(&str) -> (&str, Vec<(&str, (Operand, Option<Symbolic>))>),
+    pair!(
+        mnemonic,
+        separated_list_complete!(
+            tag!(","),
+            sp!(call!(spanned, asm_operand))
+        )
+    )
+);
+
+named!(primary(&str) -> Expr,
+    alt!(
+        delimited!(tag_s!("("), sp!(expr), tag_s!(")")) |
+        // Before number, a name can look like r2's hex (dach)
+        symbol_name => { Expr::Symbol } |
+        number => { Expr::Number } |
+        tag_s!("$") => { Expr::Symbol }
+    )
+);
+
+named!(unary_op(&str) -> UnaryOp,
+    alt!(
+        tag_s!("-") => { |_| UnaryOp::Neg } |
+        tag_s!("~") => { |_| UnaryOp::Not } |
+        call!(keyword, "not") => { |_| UnaryOp::Not } |
+        call!(keyword, "seg") => { |_| UnaryOp::Seg } |
+        call!(keyword, "sof") => { |_| UnaryOp::Sof } |
+        call!(keyword, "pag") => { |_| UnaryOp::Pag } |
+        call!(keyword, "pof") => { |_| UnaryOp::Pof }
+    )
+);
+
+fn unary(input: &str) -> IResult<&str, Expr> {
+    match terminated!(input, unary_op, space) {
+        Ok((input, op)) => {
+            let (input, operand) = unary(input)?;
+            Ok((input, Expr::Unary(op, Box::new(operand))))
+        },
+        Err(_) => primary(input)
+    }
+}
+
+// Loosest binding first, the words are the A166 spellings
+const BINARY_OPS: [&[(&str, BinaryOp)]; 6] = [
+    &[("|", BinaryOp::Or), ("or", BinaryOp::Or)],
+    &[("^", BinaryOp::Xor), ("xor", BinaryOp::Xor)],
+    &[("&", BinaryOp::And), ("and", BinaryOp::And)],
+    &[("<<", BinaryOp::Shl), (">>", BinaryOp::Shr), ("shl", BinaryOp::Shl), ("shr", BinaryOp::Shr)],
+    &[("+", BinaryOp::Add), ("-", BinaryOp::Sub)],
+    &[("*", BinaryOp::Mul), ("/", BinaryOp::Div), ("%", BinaryOp::Mod), ("mod", BinaryOp::Mod)],
+];
+
+fn binary_op<'a>(input: &'a str, ops: &[(&'static str, BinaryOp)]) -> Option<(&'a str, BinaryOp)> {
+    ops.iter()
+        .filter_map(|&(token, op)| {
+            let parsed = match token.chars().all(is_alphabetic_s) {
+                true => keyword(input, token),
+                false => tag_s!(input, token)
+            };
+            parsed.ok().map(|(input, _)| (input, op))
+        })
+        .next()
+}
+
+// Operators of the same precedence group to the left
+fn binary(input: &str, level: usize) -> IResult<&str, Expr> {
+    if level == BINARY_OPS.len() {
+        return unary(input);
+    }
+
+    let (mut input, mut lhs) = binary(input, level + 1)?;
+    loop {
+        let (rest, _) = space(input)?;
+        match binary_op(rest, BINARY_OPS[level]) {
+            Some((rest, op)) => {
+                let (rest, _) = space(rest)?;
+                let (rest, rhs) = binary(rest, level + 1)?;
+                lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+                input = rest;
+            },
+            None => return Ok((input, lhs))
+        }
+    }
+}
+
+pub fn expr(input: &str) -> IResult<&str, Expr> {
+    binary(input, 0)
+}
+
+named!(text(&str) -> &str,
+    alt!(
+        delimited!(tag!("'"), take_until!("'"), tag!("'")) |
+        delimited!(tag!("\""), take_until!("\""), tag!("\""))
+    )
+);
+
+named!(data_item(&str) -> DataItem,
+    alt!(
+        terminated!(text, operand_end) => { DataItem::Text } |
+        expr => { DataItem::Value }
+    )
+);
+
+named!(data_list(&str) -> Vec<(&str, DataItem)>,
+    separated_list_complete!(
+        tag!(","),
+        sp!(call!(spanned, data_item))
+    )
+);
+
+// The directive with the text of each of its arguments
+type DirectiveArguments<'a> = (Directive<'a>, Vec<&'a str>);
+
+fn data_directive<'a>(items: Vec<(&'a str, DataItem<'a>)>, directive: fn(Vec<DataItem<'a>>) -> Directive<'a>) -> DirectiveArguments<'a> {
+    let (arguments, items): (Vec<&str>, Vec<DataItem>) = items.into_iter().unzip();
+    (directive(items), arguments)
+}
+
+fn expr_directive<'a>((argument, value): (&'a str, Expr<'a>), directive: fn(Expr<'a>) -> Directive<'a>) -> DirectiveArguments<'a> {
+    (directive(value), vec![argument])
+}
+
+// Directives are case insensitive and have to stand on their own (EVEN, not EVENT)
+fn keyword<'a>(input: &'a str, word: &'static str) -> IResult<&'a str, &'a str> {
+    terminated!(input, tag_no_case!(word), not!(take_while_m_n!(1, 1, is_symbol_char)))
+}
+
+named!(directive(&str) -> DirectiveArguments,
+    alt!(
+        preceded!(call!(keyword, "org"), sp!(call!(spanned, expr))) => { |value| expr_directive(value, Directive::Org) } |
+        preceded!(call!(keyword, "db"), data_list) => { |items| data_directive(items, Directive::Db) } |
+        preceded!(call!(keyword, "dw"), data_list) => { |items| data_directive(items, Directive::Dw) } |
+        preceded!(call!(keyword, "ds"), sp!(call!(spanned, expr))) => { |value| expr_directive(value, Directive::Ds) } |
+        preceded!(call!(keyword, "align"), sp!(call!(spanned, expr))) => { |value| expr_directive(value, Directive::Align) } |
+        call!(keyword, "even") => { |_| (Directive::Even, Vec::new()) } |
+        call!(keyword, "end") => { |_| (Directive::End, Vec::new()) }
+    )
+);
+
+// The ones that follow a name, without a colon
+named!(named_directive(&str) -> DirectiveArguments,
+    alt!(
+        preceded!(call!(keyword, "equ"), sp!(call!(spanned, expr))) => { |value| expr_directive(value, Directive::Equ) } |
+        preceded!(call!(keyword, "set"), sp!(call!(spanned, expr))) => { |value| expr_directive(value, Directive::Set) } |
+        do_parse!(
+            call!(keyword, "section") >>
+            space >>
+            opt!(terminated!(verify!(symbol_name, |kind: &str| !kind.eq_ignore_ascii_case("at")), space)) >>
+            at: opt!(preceded!(sp!(call!(keyword, "at")), call!(spanned, expr))) >>
+            (match at {
+                Some((argument, at)) => (Directive::Section(Some(at)), vec![argument]),
+                None => (Directive::Section(None), Vec::new())
+            })
+        ) |
+        call!(keyword, "ends") => { |_| (Directive::Ends, Vec::new()) }
+    )
+);
+
+pub fn is_line_end(chr: char) -> bool {
+    chr == '\n' || chr == '\r' || chr == '\0'
+}
+
+named!(comment(&str) -> &str,
+    preceded!(tag!(";"), take_till!(is_line_end))
+);
+
+pub fn statement(input: &str) -> IResult<&str, Statement> {
+    let (input, _) = space(input)?;
+    let start = input;
+
+    let (input, label, directive) = match pair!(input, terminated!(symbol_name, space), named_directive) {
+        Ok((input, (name, directive))) => (input, Some(name), Some(directive)),
+        Err(_) => {
+            let (input, label) = opt!(input, terminated!(symbol_name, tag_s!(":")))?;
+            let (input, _) = space(input)?;
+            let (input, directive) = opt!(input, directive)?;
+            (input, label, directive)
+        }
+    };
+
+    let (input, operation) = match directive {
+        Some(_) => (input, None),
+        None => opt!(input, operation)?
+    };
+
+    let text = &start[..start.len() - input.len()];
+    let (input, _) = space(input)?;
+    let (input, _) = opt!(input, comment)?;
+    let (input, _) = alt!(input, line_ending | tag!("\0"))?;
+
+    let mut symbols = Vec::new();
+    let mut arguments = Vec::new();
+    let operation = operation.map(|(mnem, operands)| {
+        for (index, (_, (_, symbol))) in operands.iter().enumerate() {
+            if let Some(symbol) = symbol {
+                symbols.push((index, symbol.clone()));
+            }
+        }
+
+        arguments = operands.iter().map(|&(argument, _)| argument).collect();
+        AsmOperation {
+            mnem: mnem,
+            operands: operands.into_iter().map(|(_, (operand, _))| operand).collect()
+        }
+    });
+
+    let directive = directive.map(|(directive, directive_arguments)| {
+        arguments = directive_arguments;
+        directive
+    });
+
+    Ok((input, Statement {
+        label: label,
+        operation: operation,
+        symbols: symbols,
+        directive: directive,
+        text: text,
+        arguments: arguments
+    }))
+}
+
+// Operations only, up to the first line that doesn't parse, which is left in the remainder.
+// assemble() takes whole sources and reports every line that's wrong.
 pub fn asm_lines(input: &str) -> IResult<&str, Vec<AsmOperation>> {
     let mut buf = &input[..];
     let mut ops: Vec<AsmOperation> = Vec::new();
 
     while buf.len() > 0 {
-        // eprintln!("Buf: {:?}", buf);
         match asm_line(buf) {
             Ok((remainder, asmop)) => {
                 ops.push(asmop);
@@ -350,18 +830,223 @@ pub fn asm_lines(input: &str) -> IResult<&str, Vec<AsmOperation>> {
 pub type OpLookUpTable<'a> = HashMap<&'a str, Vec<Instruction<'a>>>;
 
 pub fn build_lut(lut: &mut OpLookUpTable) {
-    for id in 0..=0xFF {
-        match Instruction::try_from(id) {
-            Ok(op) => {
-                let mut op_table = &mut lut.entry(op.mnemonic).or_insert_with(|| Vec::new());
-                op_table.push(op);
+    for op in instructions() {
+        lut.entry(op.mnemonic).or_insert_with(|| Vec::new()).push(*op);
+    }
+}
+
+fn unknown_mnemonic(asm: &AsmOperation) -> EncodeError {
+    EncodeError::UnknownMnemonic { mnemonic: asm.mnem.to_string() }
+}
+
+// Whether a MAC operand goes in the given slot
+fn is_mac_operand(op_type: &OperandType, op: &Operand) -> bool {
+    match (op_type, op) {
+        (OperandType::WordRegister(_), Operand::Register(reg)) => reg.is_word_register() && reg.to_reg4().is_ok(),
+        (OperandType::MacRegister, Operand::Register(reg)) => mac_reg_number(*reg).is_some(),
+        (OperandType::IndexPointer, _) => pointer_mode(op).and_then(|(reg, _)| index_number(reg)).is_some(),
+        (OperandType::IndirectModified(_), _) => pointer_mode(op).and_then(|(reg, _)| reg.to_reg4().ok()).is_some(),
+        _ => false
+    }
+}
+
+// Whether the mnemonic is a MAC op of the given shape
+fn is_mac_shape(mnemonic: &str, shape: MacShape) -> bool {
+    match MacOp::from_mnemonic(mnemonic) {
+        Some(mac_op) => mac_op.shape == shape,
+        None => false
+    }
+}
+
+fn check_registers(asm: &AsmOperation, cpu: CpuModel) -> Result<(), EncodeError> {
+    for (n, operand) in asm.operands.iter().enumerate() {
+        match operand {
+            Operand::Register(reg) |
+            Operand::Indirect(reg) |
+            Operand::IndirectPostIncrement(reg) |
+            Operand::IndirectPreDecrement(reg) |
+            Operand::IndirectAndImmediate(reg, _) |
+            Operand::IndirectPostDecrement(reg) |
+            Operand::IndirectPostAdd(reg, _) |
+            Operand::IndirectPostSub(reg, _) if !cpu.has_reg(reg) => {
+                return Err(EncodeError::UnsupportedRegister { mnemonic: asm.mnem.to_string(), operand: n as u8 + 1, reg: *reg, cpu: cpu });
             },
             _ => {}
         }
     }
+
+    Ok(())
 }
 
-pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Result<Vec<u8>, &'a str> {
+// SFRs are looked up by name in the part's register map, which has the final say over where it
+// has them
+fn bind_registers<'a>(asm: &AsmOperation<'a>, cpu: CpuModel) -> Result<AsmOperation<'a>, EncodeError> {
+    let mut operands: Vec<Operand> = Vec::new();
+    for (n, operand) in asm.operands.iter().enumerate() {
+        let bound = match *operand {
+            Operand::Register(reg) => match cpu.register(&reg) {
+                Some(reg) => Operand::Register(reg),
+                None => return Err(EncodeError::UnsupportedRegister { mnemonic: asm.mnem.to_string(), operand: n as u8 + 1, reg: reg, cpu: cpu })
+            },
+            _ => *operand
+        };
+        operands.push(bound);
+    }
+
+    Ok(AsmOperation { mnem: asm.mnem, operands: operands })
+}
+
+pub fn operation_to_bytes(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Result<Vec<u8>, EncodeError> {
+    operation_to_bytes_for_cpu(asm, op_lut, CpuModel::default())
+}
+
+// An SFR can be written by name or by its physical address (PWMCON0 or FF30h).  Both spellings are
+// offered to the encoder, register first, so either one assembles to the same bytes.  ESFR
+// addresses stay addresses since the short form of an ESFR needs an EXT*R in front of it.  With
+// a known register bank an address inside it is offered as the GPR too.
+fn operand_spellings(operand: &Operand, bank: Option<RegisterBank>, byte: bool, cpu: CpuModel) -> Vec<Operand> {
+    match *operand {
+        Operand::Mem16(address) => {
+            let gpr = match bank {
+                Some(bank) => bank.register(address, byte),
+                None => None
+            };
+
+            match (gpr, Reg::from_address(address, cpu.registers())) {
+                (Some(gpr), _) => vec![Operand::Register(gpr), *operand],
+                (None, Ok(reg)) if !reg.is_extended() => vec![Operand::Register(reg), *operand],
+                _ => vec![*operand]
+            }
+        },
+        Operand::Register(reg) => {
+            match reg.to_phys16() {
+                Ok(address) => vec![*operand, Operand::Mem16(address)],
+                Err(_) => vec![*operand]
+            }
+        },
+        _ => vec![*operand]
+    }
+}
+
+// Where an operation is being assembled.  Relative branches are written as their target and need
+// the address of the operation, addresses inside a known register bank assemble as the GPRs there.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AsmContext {
+    pub pc: u32,
+    pub bank: Option<RegisterBank>,
+    // The segment of each operand that's an address known in full (a label, or a number too big
+    // for 16 bits), a 16-bit address on its own is in pc's.  Branches other than calls and jmps
+    // can't leave pc's segment, and data has to be in the first 64K.
+    pub segments: [Option<u8>; 3],
+}
+
+// As operation_to_bytes, rejecting instructions and registers the part doesn't have
+pub fn operation_to_bytes_for_cpu(asm: &AsmOperation, op_lut: &OpLookUpTable, cpu: CpuModel) -> Result<Vec<u8>, EncodeError> {
+    operation_to_bytes_in_context(asm, op_lut, cpu, &AsmContext::default())
+}
+
+// As operation_to_bytes_for_cpu, for an operation at context.pc
+pub fn operation_to_bytes_in_context(asm: &AsmOperation, op_lut: &OpLookUpTable, cpu: CpuModel, context: &AsmContext) -> Result<Vec<u8>, EncodeError> {
+    let bank = context.bank;
+
+    // A register the part lacks doesn't become fair game by spelling it as an address
+    let asm = &bind_registers(asm, cpu)?;
+    check_registers(asm, cpu)?;
+
+    let mut candidates: Vec<Vec<Operand>> = vec![Vec::new()];
+    for (index, operand) in asm.operands.iter().enumerate() {
+        let spellings = operand_spellings(operand, bank, byte_access(asm.mnem, index), cpu);
+        candidates = candidates.iter()
+            .flat_map(|prefix| spellings.iter().map(move |spelling| {
+                let mut operands = prefix.clone();
+                operands.push(*spelling);
+                operands
+            }))
+            .collect();
+    }
+
+    // Report the first failure, it's the one closest to what was written.  An encoding that took
+    // the operands but couldn't hold them says more than none taking them at all.
+    let mut first_err: Option<EncodeError> = None;
+    for operands in candidates {
+        let candidate = AsmOperation {
+            mnem: asm.mnem,
+            operands: operands
+        };
+
+        match (encode_operation(&candidate, op_lut, cpu, context), &first_err) {
+            (Ok(bytes), _) => return Ok(bytes),
+            (Err(e), &None) |
+            (Err(e), &Some(EncodeError::NoMatchingEncoding { .. })) => first_err = Some(e),
+            (Err(_), &Some(_)) => {}
+        }
+    }
+
+    Err(first_err.unwrap_or_else(|| EncodeError::NoMatchingEncoding { mnemonic: asm.mnem.to_string() }))
+}
+
+// Gives addresses and immediates the operand type of the slot they were matched to.  Relative
+// branches are written as their target and become the word offset the encoding stores.
+fn typed_operands(isn: &Instruction, args: &mut InstructionArguments, context: &AsmContext) -> Result<(), EncodeError> {
+    let pc = context.pc;
+    let next = (pc as u16).wrapping_add(isn.encoding.length() as u16);
+    // The whole address an operand stands for, a 16-bit one is in pc's segment
+    let address = |index: usize, offset: u16| match context.segments[index] {
+        Some(segment) => (segment as u32) << 16 | offset as u32,
+        None => pc & 0xFF0000 | offset as u32
+    };
+    let out_of_range = |operand: usize, value: u32, max: u32| {
+        EncodeError::OutOfRange { opcode: isn.id, encoding: isn.encoding, operand: operand as u8 + 1, value: value, max: max }
+    };
+
+    for (index, (op_type, operand)) in [(isn.op1, &mut args.op1), (isn.op2, &mut args.op2), (isn.op3, &mut args.op3)].iter_mut().enumerate() {
+        let typed = match (*op_type, **operand) {
+            (Some(OperandType::DirectCaddr16), Some(Operand::Mem16(caddr))) => {
+                let target = address(index, caddr);
+                if target >> 16 != pc >> 16 {
+                    return Err(EncodeError::UnreachableTarget { opcode: isn.id, target: target, pc: pc });
+                }
+                Operand::CodeAddr16(caddr)
+            },
+            // Data is reached through the DPPs, which only take 16 bits
+            (Some(OperandType::DirectMemory16), Some(Operand::Mem16(mem))) => {
+                match context.segments[index] {
+                    Some(segment) if segment != 0 => return Err(out_of_range(index, address(index, mem), 0xFFFF)),
+                    _ => continue
+                }
+            },
+            (Some(OperandType::DirectSegment8), Some(Operand::Mem16(seg))) => {
+                if seg > <u8>::max_value() as u16 {
+                    return Err(out_of_range(index, seg as u32, <u8>::max_value() as u32));
+                }
+                Operand::Segment8(seg as u8)
+            },
+            (Some(OperandType::DirectRelative8S), Some(Operand::Mem16(target))) => {
+                let offset = target.wrapping_sub(next) as i16;
+                let target = address(index, target);
+                if offset % 2 != 0 || !(-256..=254).contains(&offset) || target >> 16 != pc >> 16 {
+                    return Err(EncodeError::UnreachableTarget { opcode: isn.id, target: target, pc: pc });
+                }
+                Operand::Rel8((offset / 2) as i8)
+            },
+            (Some(OperandType::ImmediateTrap7), Some(Operand::Immediate(trap, _))) => {
+                if trap > 0x7F {
+                    return Err(out_of_range(index, trap as u32, 0x7F));
+                }
+                Operand::Trap7(trap as u8)
+            },
+            _ => continue
+        };
+
+        **operand = Some(typed);
+    }
+
+    Ok(())
+}
+
+fn encode_operation(asm: &AsmOperation, op_lut: &OpLookUpTable, cpu: CpuModel, context: &AsmContext) -> Result<Vec<u8>, EncodeError> {
+    check_registers(asm, cpu)?;
+
     let mut encode_op: Option<&Instruction> = None;
     let mut args = InstructionArguments {
         ..Default::default()
@@ -369,7 +1054,7 @@ pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Res
 
     match asm.operands.len() {
         0 => {
-            let lut = op_lut.get(asm.mnem).expect("Invalid mnemonic");
+            let lut = op_lut.get(table_mnemonic(asm.mnem)).ok_or_else(|| unknown_mnemonic(asm))?;
 
             args.mnemonic = Some(asm.mnem.to_string());
 
@@ -380,15 +1065,16 @@ pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Res
                         encode_op = Some(&isn);
                         break;
                     },
+                    EncodingType::co_reg4_reg4 if is_mac_shape(asm.mnem, MacShape::Bare) => {
+                        encode_op = Some(&isn);
+                        break;
+                    },
                     _ => {}
                 }
             }
         },
         1 => {
-            let lut = match asm.mnem {
-                "atomic" | "extr" => op_lut.get("atomic_extr").expect("Invalid mnemonic"),
-                _ => op_lut.get(asm.mnem).expect("Invalid mnemonic")
-            };
+            let lut = op_lut.get(table_mnemonic(asm.mnem)).ok_or_else(|| unknown_mnemonic(asm))?;
 
             args.mnemonic = Some(asm.mnem.to_string());
 
@@ -414,9 +1100,9 @@ pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Res
                         encode_op = Some(&isn);
                         break;
                     },
-                    (EncodingType::rel8s, Operand::Direct(_, _)) => {
-                        match isn.op1.as_ref().unwrap() {
-                            OperandType::DirectRelative8S => {
+                    (EncodingType::rel8s, Operand::Mem16(_)) => {
+                        match isn.op1 {
+                            Some(OperandType::DirectRelative8S) => {
                                 args.op1 = Some(op1);
                                 encode_op = Some(&isn);
                                 break;
@@ -429,6 +1115,11 @@ pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Res
                         encode_op = Some(&isn);
                         break;
                     },
+                    (EncodingType::co_reg4_reg4, Operand::Immediate(_, _)) if is_mac_shape(asm.mnem, MacShape::Shift) => {
+                        args.op1 = Some(op1);
+                        encode_op = Some(&isn);
+                        break;
+                    },
                     (EncodingType::bitopcode4_e_bitaddr8, Operand::BitAddr(_, _)) |
                     (EncodingType::bitopcode4_f_bitaddr8, Operand::BitAddr(_, _)) => {
                         args.op1 = Some(op1);
@@ -440,16 +1131,23 @@ pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Res
             }
         },
         2 => {
-            let lut = match asm.mnem {
-                "extp" | "extpr" | "exts" | "extsr" => op_lut.get("ext*").expect("Invalid mnemonic"),
-                _ => op_lut.get(asm.mnem).expect("Invalid mnemonic")
-            };
+            let lut = op_lut.get(table_mnemonic(asm.mnem)).ok_or_else(|| unknown_mnemonic(asm))?;
 
             args.mnemonic = Some(asm.mnem.to_string());
             for isn in lut {
-                match (&isn.encoding, isn.op1.as_ref().unwrap(), &asm.operands[0], isn.op2.as_ref().unwrap(), &asm.operands[1]) {
+                let (op_type1, op_type2) = match (isn.op1.as_ref(), isn.op2.as_ref()) {
+                    (Some(op_type1), Some(op_type2)) => (op_type1, op_type2),
+                    _ => continue
+                };
+
+                match (&isn.encoding, op_type1, &asm.operands[0], op_type2, &asm.operands[1]) {
                     (EncodingType::op_d7, &OperandType::ImmediateData4, immed @ &Operand::Immediate(_, _), &OperandType::ImmediateIrange2, irange @ &Operand::Immediate(_, _)) => {
-                        args.op1 = Some(*immed);
+                        // The page and segment forms share the opcode
+                        args.op1 = match (asm.mnem, immed) {
+                            ("extp", &Operand::Immediate(page, _)) |
+                            ("extpr", &Operand::Immediate(page, _)) => Some(Operand::Page10(page)),
+                            _ => Some(*immed)
+                        };
                         args.op2 = Some(*irange);
                         encode_op = Some(&isn);
                         break;
@@ -466,9 +1164,10 @@ pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Res
                     (EncodingType::reg4_or_data3, reg_type @ &OperandType::WordRegister(_), reg @ &Operand::Register(_), &OperandType::ImmediateData3, op2 @ &Operand::Indirect(_)) |
                     (EncodingType::reg4_or_data3, reg_type @ &OperandType::WordRegister(_), reg @ &Operand::Register(_), &OperandType::ImmediateData3, op2 @ &Operand::IndirectPostIncrement(_)) |
                     (EncodingType::reg4_or_data3, reg_type @ &OperandType::WordRegister(_), reg @ &Operand::Register(_), &OperandType::ImmediateData3, op2 @ &Operand::Immediate(_, _)) => {
+                        // Only a GPR has the 4-bit number, an SFR takes the form with reg and #data16
                         match (reg_type, reg) {
-                            (OperandType::ByteRegister(_), Operand::Register(reg)) if reg.is_byte_register() => {},
-                            (OperandType::WordRegister(_) , Operand::Register(reg)) if reg.is_word_register() => {},
+                            (OperandType::ByteRegister(_), Operand::Register(reg)) if reg.is_byte_register() && reg.to_reg4().is_ok() => {},
+                            (OperandType::WordRegister(_) , Operand::Register(reg)) if reg.is_word_register() && reg.to_reg4().is_ok() => {},
                             _ => continue
                         };
 
@@ -476,8 +1175,9 @@ pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Res
                             Operand::Register(r) |
                             Operand::Indirect(r) |
                             Operand::IndirectPostIncrement(r) => {
-                                if r.to_reg4().unwrap() > 0b11 {
-                                    continue;
+                                match r.to_reg4() {
+                                    Ok(reg4) if reg4 <= 0b11 => {},
+                                    _ => continue
                                 }
                             },
                             _ => {}
@@ -488,7 +1188,7 @@ pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Res
                         encode_op = Some(&isn);
                         break;
                     },
-                    (EncodingType::condopcode4_d_rel8s, &OperandType::Condition, cond @ &Operand::Condition(_), &OperandType::DirectRelative8S, rel @ &Operand::Direct(_, _)) => {
+                    (EncodingType::condopcode4_d_rel8s, &OperandType::Condition, cond @ &Operand::Condition(_), &OperandType::DirectRelative8S, rel @ &Operand::Mem16(_)) => {
                         args.op1 = Some(*cond);
                         args.op2 = Some(*rel);
                         encode_op =Some(&isn);
@@ -500,13 +1200,13 @@ pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Res
                         encode_op = Some(&isn);
                         break;
                     },
-                    (EncodingType::cond4_0_mem16, &OperandType::Condition, cond @ &Operand::Condition(_), &OperandType::DirectCaddr16, addr @ Operand::Direct(_, _)) => {
+                    (EncodingType::cond4_0_mem16, &OperandType::Condition, cond @ &Operand::Condition(_), &OperandType::DirectCaddr16, addr @ &Operand::Mem16(_)) => {
                         args.op1 = Some(*cond);
                         args.op2 = Some(*addr);
                         encode_op = Some(&isn);
                         break;
                     },
-                    (EncodingType::bitaddr8_rel8_bit4_0, &OperandType::BitAddr(_), bitaddr @ &Operand::BitAddr(_, _), &OperandType::DirectRelative8S, direct @ &Operand::Direct(_, _)) => {
+                    (EncodingType::bitaddr8_rel8_bit4_0, &OperandType::BitAddr(_), bitaddr @ &Operand::BitAddr(_, _), &OperandType::DirectRelative8S, direct @ &Operand::Mem16(_)) => {
                         args.op1 = Some(*bitaddr);
                         args.op2 = Some(*direct);
                         encode_op = Some(&isn);
@@ -517,7 +1217,7 @@ pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Res
                         args.op2 = Some(*bitaddr1);
                         encode_op = Some(&isn);
                     }
-                    (EncodingType::_f_reg4_mem16, reg_type @ &OperandType::WordRegister(_), reg @ &Operand::Register(_), &OperandType::DirectMemory16, direct @ &Operand::Direct(_, _)) => {
+                    (EncodingType::_f_reg4_mem16, reg_type @ &OperandType::WordRegister(_), reg @ &Operand::Register(_), &OperandType::DirectMemory16, direct @ &Operand::Mem16(_)) => {
                         match (reg_type, reg) {
                             (OperandType::ByteRegister(_), Operand::Register(reg)) if reg.is_byte_register() => {},
                             (OperandType::WordRegister(_) , Operand::Register(reg)) if reg.is_word_register() => {},
@@ -534,8 +1234,8 @@ pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Res
                     (EncodingType::reg4_data4, &OperandType::ImmediateData4, &Operand::Immediate(immed,_), reg_type @ &OperandType::ByteRegister(_), reg @ &Operand::Register(_)) |
                     (EncodingType::reg4_data4, &OperandType::ImmediateData4, &Operand::Immediate(immed,_), reg_type @ &OperandType::WordRegister(_), reg @ &Operand::Register(_)) => {
                         match (reg_type, reg) {
-                            (OperandType::ByteRegister(_), Operand::Register(reg)) if reg.is_byte_register() => {},
-                            (OperandType::WordRegister(_) , Operand::Register(reg)) if reg.is_word_register() => {},
+                            (OperandType::ByteRegister(_), Operand::Register(reg)) if reg.is_byte_register() && reg.to_reg4().is_ok() => {},
+                            (OperandType::WordRegister(_) , Operand::Register(reg)) if reg.is_word_register() && reg.to_reg4().is_ok() => {},
                             _ => continue
                         };
 
@@ -590,8 +1290,8 @@ pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Res
                         encode_op = Some(&isn);
                         break;
                     },
-                    (EncodingType::_0_reg4_mem16, &OperandType::Indirect(_), reg @ &Operand::Indirect(_), &OperandType::DirectMemory16, direct @ &Operand::Direct(_, _)) |
-                    (EncodingType::_0_reg4_mem16, &OperandType::DirectMemory16, direct @ &Operand::Direct(_, _), &OperandType::Indirect(_), reg @ &Operand::Indirect(_)) => {
+                    (EncodingType::_0_reg4_mem16, &OperandType::Indirect(_), reg @ &Operand::Indirect(_), &OperandType::DirectMemory16, direct @ &Operand::Mem16(_)) |
+                    (EncodingType::_0_reg4_mem16, &OperandType::DirectMemory16, direct @ &Operand::Mem16(_), &OperandType::Indirect(_), reg @ &Operand::Indirect(_)) => {
                         args.op1 = Some(*reg);
                         args.op2 = Some(*direct);
                         encode_op = Some(&isn);
@@ -631,13 +1331,15 @@ pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Res
                         match (reg_type, reg) {
                             (OperandType::ByteRegister(_), Operand::Register(reg)) if reg.is_byte_register() => {},
                             (OperandType::WordRegister(_) , Operand::Register(reg)) if reg.is_word_register() => {},
+                            // A byte op's reg8 can just as well be RLx or RHx
+                            (OperandType::WordRegister(_) , Operand::Register(reg)) if reg.is_byte_register() && byte_access(asm.mnem, 0) => {},
                             _ => continue
                         };
 
                         // TODO: Make this less gross
-                        // Hope for a data3 variant
-                        if let Operand::Immediate(imm, _width) = immed {
-                            if *imm <= 0b111 {
+                        // Hope for a data3 variant, which only a GPR has
+                        if let (Operand::Register(reg), Operand::Immediate(imm, _width)) = (reg, immed) {
+                            if *imm <= 0b111 && reg.to_reg4().is_ok() {
                                 continue;
                             }
                         }
@@ -647,11 +1349,11 @@ pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Res
                         encode_op = Some(&isn);
                         break;
                     },
-                    (EncodingType::reg8_mem16, &OperandType::DirectMemory16, dir @ &Operand::Direct(_, _), reg_type @ &OperandType::ByteRegister(_), reg @ &Operand::Register(_)) |
-                    (EncodingType::reg8_mem16, &OperandType::DirectMemory16, dir @ &Operand::Direct(_, _), reg_type @ &OperandType::WordRegister(_), reg @ &Operand::Register(_)) |
-                    (EncodingType::reg8_mem16, reg_type @ &OperandType::ByteRegister(_), reg @ &Operand::Register(_), &OperandType::DirectMemory16, dir @ &Operand::Direct(_, _)) |
-                    (EncodingType::reg8_mem16, reg_type @ &OperandType::WordRegister(_), reg @ &Operand::Register(_), &OperandType::DirectCaddr16, dir @ &Operand::Direct(_, _)) |
-                    (EncodingType::reg8_mem16, reg_type @ &OperandType::WordRegister(_), reg @ &Operand::Register(_), &OperandType::DirectMemory16, dir @ &Operand::Direct(_, _)) => {
+                    (EncodingType::reg8_mem16, &OperandType::DirectMemory16, dir @ &Operand::Mem16(_), reg_type @ &OperandType::ByteRegister(_), reg @ &Operand::Register(_)) |
+                    (EncodingType::reg8_mem16, &OperandType::DirectMemory16, dir @ &Operand::Mem16(_), reg_type @ &OperandType::WordRegister(_), reg @ &Operand::Register(_)) |
+                    (EncodingType::reg8_mem16, reg_type @ &OperandType::ByteRegister(_), reg @ &Operand::Register(_), &OperandType::DirectMemory16, dir @ &Operand::Mem16(_)) |
+                    (EncodingType::reg8_mem16, reg_type @ &OperandType::WordRegister(_), reg @ &Operand::Register(_), &OperandType::DirectCaddr16, dir @ &Operand::Mem16(_)) |
+                    (EncodingType::reg8_mem16, reg_type @ &OperandType::WordRegister(_), reg @ &Operand::Register(_), &OperandType::DirectMemory16, dir @ &Operand::Mem16(_)) => {
                         match (reg_type, reg) {
                             (OperandType::ByteRegister(_), Operand::Register(reg)) if reg.is_byte_register() => {},
                             (OperandType::WordRegister(_) , Operand::Register(reg)) if reg.is_word_register() => {},
@@ -663,18 +1365,35 @@ pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Res
                         encode_op = Some(&isn);
                         break;
                     },
-                    (EncodingType::seg8_mem16, &OperandType::DirectSegment8, seg @ &Operand::Direct(_, _), &OperandType::DirectCaddr16, caddr @ &Operand::Direct(_, _)) => {
+                    (EncodingType::seg8_mem16, &OperandType::DirectSegment8, seg @ &Operand::Mem16(_), &OperandType::DirectCaddr16, caddr @ &Operand::Mem16(_)) => {
                         args.op1 = Some(*seg);
                         args.op2 = Some(*caddr);
                         encode_op = Some(&isn);
                         break;
+                    },
+                    (EncodingType::co_reg4_reg4, op_type1, op1, op_type2, op2) |
+                    (EncodingType::co_reg4_ind, op_type1, op1, op_type2, op2) |
+                    (EncodingType::co_idx_ind, op_type1, op1, op_type2, op2) |
+                    (EncodingType::co_store_reg4, op_type1, op1, op_type2, op2) |
+                    (EncodingType::co_store_ind, op_type1, op1, op_type2, op2) |
+                    (EncodingType::co_mov, op_type1, op1, op_type2, op2) => {
+                        // Only the binary MAC ops take two operands, CoSTORE and CoMOV aren't MAC ops
+                        let binary = MacOp::from_mnemonic(asm.mnem).is_none() || is_mac_shape(asm.mnem, MacShape::Binary);
+                        if !binary || !is_mac_operand(op_type1, op1) || !is_mac_operand(op_type2, op2) {
+                            continue;
+                        }
+
+                        args.op1 = Some(*op1);
+                        args.op2 = Some(*op2);
+                        encode_op = Some(&isn);
+                        break;
                     }
                     _ => {}
                 }
             }
         },
         3 => {
-            let lut = op_lut.get(asm.mnem).expect("Invalid mnemonic");
+            let lut = op_lut.get(asm.mnem).ok_or_else(|| unknown_mnemonic(asm))?;
             for isn in lut {
                 match &isn.encoding {
                     EncodingType::bitoff8_mask8_data8 => {
@@ -684,18 +1403,23 @@ pub fn operation_to_bytes<'a>(asm: &AsmOperation, op_lut: &OpLookUpTable) -> Res
                         encode_op = Some(&isn);
                         break;
                     },
-                    _ => return Err("we should only have one ternary encoding")
+                    // We should only have one ternary encoding
+                    _ => {}
                 }
             }
         },
-        _ => unreachable!()
+        // Nothing takes more than three operands
+        _ => {}
     }
     match encode_op {
+        Some(isn) if !cpu.supports(isn) => {
+            Err(EncodeError::UnsupportedInstruction { mnemonic: asm.mnem.to_string(), cpu: cpu })
+        },
         Some(isn) => {
+            typed_operands(isn, &mut args, context)?;
             let encoding = Encoding::from(&isn.encoding);
-            let mut ret: Vec<u8> = (encoding.encode)(&isn, &args).expect("Encoding failure");
-            Ok(ret)
+            (encoding.encode)(&isn, &args)
         },
-        None => Err("No suitable encoding found")
+        None => Err(EncodeError::NoMatchingEncoding { mnemonic: asm.mnem.to_string() })
     }
 }
@@ -21,6 +21,38 @@
 #![allow(non_snake_case)]
 include!(concat!(env!("OUT_DIR"), "/ffi.rs"));
 
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::str::FromStr;
+
+use ::cpu::CpuModel;
+use ::instruction::{OpKind, OpType};
+use ::opformat::FormatOptions;
+
+// The part selected with asm.cpu, unset or unrecognized names get the default
+pub unsafe fn cpu_model(cpu: *const c_char) -> CpuModel {
+    match cpu.is_null() {
+        true => CpuModel::default(),
+        false => {
+            CStr::from_ptr(cpu).to_str().ok()
+                .and_then(|name| CpuModel::from_str(name).ok())
+                .unwrap_or_default()
+        }
+    }
+}
+
+// The listing style selected with asm.features, e.g. "keil" or "tasking,0x"
+pub unsafe fn format_options(features: *const c_char) -> FormatOptions {
+    match features.is_null() {
+        true => FormatOptions::default(),
+        false => {
+            CStr::from_ptr(features).to_str().ok()
+                .map(FormatOptions::from_features)
+                .unwrap_or_default()
+        }
+    }
+}
+
 impl _RAnalOpType {
     pub fn uint_value(&self) -> u32 {
        match *self {
@@ -36,3 +68,38 @@ impl _RAnalCond {
         }
     }
 }
+
+impl<'a> From<&'a OpType> for _RAnalOpType {
+    fn from(op_type: &OpType) -> _RAnalOpType {
+        let base = match op_type.kind {
+            OpKind::Null    => _RAnalOpType::R_ANAL_OP_TYPE_NULL,
+            OpKind::Nop     => _RAnalOpType::R_ANAL_OP_TYPE_NOP,
+            OpKind::Mov     => _RAnalOpType::R_ANAL_OP_TYPE_MOV,
+            OpKind::Add     => _RAnalOpType::R_ANAL_OP_TYPE_ADD,
+            OpKind::Sub     => _RAnalOpType::R_ANAL_OP_TYPE_SUB,
+            OpKind::Mul     => _RAnalOpType::R_ANAL_OP_TYPE_MUL,
+            OpKind::Div     => _RAnalOpType::R_ANAL_OP_TYPE_DIV,
+            OpKind::And     => _RAnalOpType::R_ANAL_OP_TYPE_AND,
+            OpKind::Or      => _RAnalOpType::R_ANAL_OP_TYPE_OR,
+            OpKind::Xor     => _RAnalOpType::R_ANAL_OP_TYPE_XOR,
+            OpKind::Cpl     => _RAnalOpType::R_ANAL_OP_TYPE_CPL,
+            OpKind::Cmp     => _RAnalOpType::R_ANAL_OP_TYPE_CMP,
+            OpKind::Shl     => _RAnalOpType::R_ANAL_OP_TYPE_SHL,
+            OpKind::Shr     => _RAnalOpType::R_ANAL_OP_TYPE_SHR,
+            OpKind::Rol     => _RAnalOpType::R_ANAL_OP_TYPE_ROL,
+            OpKind::Ror     => _RAnalOpType::R_ANAL_OP_TYPE_ROR,
+            OpKind::Push    => _RAnalOpType::R_ANAL_OP_TYPE_PUSH,
+            OpKind::Pop     => _RAnalOpType::R_ANAL_OP_TYPE_POP,
+            OpKind::Jmp     => _RAnalOpType::R_ANAL_OP_TYPE_JMP,
+            OpKind::CJmp    => _RAnalOpType::R_ANAL_OP_TYPE_JMP | _RAnalOpType::R_ANAL_OP_TYPE_COND,
+            OpKind::Call    => _RAnalOpType::R_ANAL_OP_TYPE_CALL,
+            OpKind::Ret     => _RAnalOpType::R_ANAL_OP_TYPE_RET,
+            OpKind::Trap    => _RAnalOpType::R_ANAL_OP_TYPE_TRAP,
+        };
+
+        match op_type.reg {
+            true => base | _RAnalOpType::R_ANAL_OP_TYPE_REG,
+            false => base
+        }
+    }
+}
@@ -16,7 +16,9 @@
 */
 
 use std::str::FromStr;
+use ::error::RegError;
 use ::instruction::*;
+use ::sfr::{RegisterInfo, RegisterMap};
 use std::fmt;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -272,12 +274,29 @@ pub enum Reg {
     XP2IC,
     XP3IC,
 
+    // MAC unit, ST10 and XC16x only
+    IDX0,
+    IDX1,
+    MAH,
+    MAL,
+    MAS,
+    MCW,
+    MRW,
+    MSW,
+    QR0,
+    QR1,
+    QX0,
+    QX1,
+
     SFR(u8),
     ESFR(u8),
+
+    // Described by a register map rather than known to the enum
+    Named(&'static RegisterInfo),
 }
 
 impl Reg {
-    pub fn to_reg4(&self) -> Result<u8, &'static str> {
+    pub fn to_reg4(&self) -> Result<u8, RegError> {
         match *self {
             Reg::R0 => Ok(0x0),
             Reg::R1 => Ok(0x1),
@@ -311,11 +330,11 @@ impl Reg {
             Reg::RH6 => Ok(0xD),
             Reg::RL7 => Ok(0xE),
             Reg::RH7 => Ok(0xF),
-            _ => Err("Register is not a GPR"),
+            _ => Err(RegError::NotAGpr(*self)),
         }
     }
 
-    pub fn to_reg8(&self) -> Result<u8, &'static str> {
+    pub fn to_reg8(&self) -> Result<u8, RegError> {
         match *self {
             Reg::R0 => Ok(0xF0),
             Reg::R1 => Ok(0xF1),
@@ -507,18 +526,105 @@ impl Reg {
             Reg::WDTCON => Ok(0xD7),
             Reg::ZEROS => Ok(0x8E),
 
+            Reg::ADDAT2 => Ok(0x50),
+            Reg::CC16IC => Ok(0xB0),
+            Reg::CC17IC => Ok(0xB1),
+            Reg::CC18IC => Ok(0xB2),
+            Reg::CC19IC => Ok(0xB3),
+            Reg::CC20IC => Ok(0xB4),
+            Reg::CC21IC => Ok(0xB5),
+            Reg::CC22IC => Ok(0xB6),
+            Reg::CC23IC => Ok(0xB7),
+            Reg::CC24IC => Ok(0xB8),
+            Reg::CC25IC => Ok(0xB9),
+            Reg::CC26IC => Ok(0xBA),
+            Reg::CC27IC => Ok(0xBB),
+            Reg::CC28IC => Ok(0xBC),
+            Reg::CC29IC => Ok(0xC2),
+            Reg::CC30IC => Ok(0xC6),
+            Reg::CC31IC => Ok(0xCA),
+            Reg::DP0L => Ok(0x80),
+            Reg::DP0H => Ok(0x81),
+            Reg::DP1L => Ok(0x82),
+            Reg::DP1H => Ok(0x83),
+            Reg::EXICON => Ok(0xE0),
+            Reg::ODP2 => Ok(0xE1),
+            Reg::ODP3 => Ok(0xE3),
+            Reg::ODP6 => Ok(0xE7),
+            Reg::ODP7 => Ok(0xE9),
+            Reg::ODP8 => Ok(0xEB),
+            Reg::PICON => Ok(0xE2),
+            Reg::PP0 => Ok(0x1C),
+            Reg::PP1 => Ok(0x1D),
+            Reg::PP2 => Ok(0x1E),
+            Reg::PP3 => Ok(0x1F),
+            Reg::PT0 => Ok(0x18),
+            Reg::PT1 => Ok(0x19),
+            Reg::PT2 => Ok(0x1A),
+            Reg::PT3 => Ok(0x1B),
+            Reg::PWMIC => Ok(0xBF),
+            Reg::RP0H => Ok(0x84),
+            Reg::S0TBIC => Ok(0xCE),
+            Reg::SSCBR => Ok(0x5A),
+            Reg::SSCRB => Ok(0x59),
+            Reg::SSCTB => Ok(0x58),
+            Reg::T7 => Ok(0x28),
+            Reg::T7IC => Ok(0xBD),
+            Reg::T7REL => Ok(0x2A),
+            Reg::T8 => Ok(0x29),
+            Reg::T8IC => Ok(0xBE),
+            Reg::T8REL => Ok(0x2B),
+            Reg::XP0IC => Ok(0xC3),
+            Reg::XP1IC => Ok(0xC7),
+            Reg::XP2IC => Ok(0xCB),
+            Reg::XP3IC => Ok(0xCF),
+
+            Reg::IDX0 => Ok(0x84),
+            Reg::IDX1 => Ok(0x85),
+            Reg::MAH => Ok(0x2F),
+            Reg::MAL => Ok(0x2E),
+            Reg::MCW => Ok(0xEE),
+            Reg::MRW => Ok(0xED),
+            Reg::MSW => Ok(0xEF),
+            Reg::QR0 => Ok(0x02),
+            Reg::QR1 => Ok(0x03),
+            Reg::QX0 => Ok(0x00),
+            Reg::QX1 => Ok(0x01),
+
             Reg::SFR(r) => Ok(r),
             Reg::ESFR(r) => Ok(r),
+            Reg::Named(info) => Ok(info.short_address()),
 
-            _ => Err("Unknown register"),
+            _ => Err(RegError::NoShortAddress(*self)),
         }
     }
 
-    pub fn to_phys16(&self) -> Result<u16, &'static str> {
-        Err("Not Yer")
+    // GPRs live wherever CP points, so only SFRs, ESFRs and the XBUS CAN registers have a fixed address
+    pub fn to_phys16(&self) -> Result<u16, RegError> {
+        match *self {
+            Reg::C1CSR => Ok(0xEF00),
+            Reg::C1IR => Ok(0xEF02),
+            Reg::C1BTR => Ok(0xEF04),
+            Reg::C1GMS => Ok(0xEF06),
+            Reg::C1UGML => Ok(0xEF08),
+            Reg::C1LGML => Ok(0xEF0A),
+            Reg::C1UMLM => Ok(0xEF0C),
+            Reg::C1LMLM => Ok(0xEF0E),
+
+            Reg::Named(info) => Ok(info.address),
+
+            _ if self.to_reg4().is_ok() => Err(RegError::NoPhysicalAddress(*self)),
+            _ => {
+                let short = self.to_reg8().map_err(|_| RegError::NoPhysicalAddress(*self))? as u16;
+                match self.is_extended() {
+                    true => Ok(0xF000 + (short * 2)),
+                    false => Ok(0xFE00 + (short * 2)),
+                }
+            }
+        }
     }
 
-    pub fn from_reg4(reg: u8, reg_type: &OperandType) -> Result<Reg, ()> {
+    pub fn from_reg4(reg: u8, reg_type: &OperandType) -> Result<Reg, RegError> {
         match reg_type {
             OperandType::ByteRegister(_) => {
                 match reg {
@@ -538,7 +644,7 @@ impl Reg {
                     0xD => Ok(Reg::RH6),
                     0xE => Ok(Reg::RL7),
                     0xF => Ok(Reg::RH7),
-                    _   => Err(())
+                    _   => Err(RegError::InvalidNumber(reg))
                 }
             },
             OperandType::WordRegister(_) => {
@@ -559,15 +665,15 @@ impl Reg {
                     0xD => Ok(Reg::R13),
                     0xE => Ok(Reg::R14),
                     0xF => Ok(Reg::R15),
-                    _   => Err(())
+                    _   => Err(RegError::InvalidNumber(reg))
                 }
             },
-            _ => Err(())
+            _ => Err(RegError::WrongOperandType(*reg_type))
         }
     }
 
 
-    pub fn from_reg8(reg: u8, reg_type: &OperandType) -> Result<Reg, ()> {
+    pub fn from_reg8(reg: u8, reg_type: &OperandType) -> Result<Reg, RegError> {
         match reg_type {
             &OperandType::ExtendedRegister => {
                 match reg {
@@ -623,6 +729,10 @@ impl Reg {
                     0xC7 => Ok(Reg::XP1IC),
                     0xCB => Ok(Reg::XP2IC),
                     0xCF => Ok(Reg::XP3IC),
+                    0x00 => Ok(Reg::QX0),
+                    0x01 => Ok(Reg::QX1),
+                    0x02 => Ok(Reg::QR0),
+                    0x03 => Ok(Reg::QR1),
                     r @ _ => Ok(Reg::ESFR(r)),
                 }
             },
@@ -784,6 +894,13 @@ impl Reg {
                     0x57 => Ok(Reg::WDT),
                     0xD7 => Ok(Reg::WDTCON),
                     0x8E => Ok(Reg::ZEROS),
+                    0x84 => Ok(Reg::IDX0),
+                    0x85 => Ok(Reg::IDX1),
+                    0x2F => Ok(Reg::MAH),
+                    0x2E => Ok(Reg::MAL),
+                    0xEE => Ok(Reg::MCW),
+                    0xED => Ok(Reg::MRW),
+                    0xEF => Ok(Reg::MSW),
                     _ => match reg_type {
                         OperandType::ByteRegister(_) |
                         OperandType::WordRegister(_) => {
@@ -792,196 +909,70 @@ impl Reg {
                                 r @ _ => Ok(Reg::SFR(r)),
                             }
                         },
-                        _ => Err(()) // "Invalid SFR register type and short address")
+                        _ => Err(RegError::WrongOperandType(*reg_type))
                     }
                 }
             },
-            _ => Err(())
+            _ => Err(RegError::WrongOperandType(*reg_type))
         }
     }
 
-    pub fn from_phys16(address: u16, reg_type: &OperandType) -> Result<Reg, ()> {
-        match reg_type {
-            &OperandType::ExtendedRegister => {
-                match address {
-                    0xFEA0 => Ok(Reg::ADDAT2),
-                    0xF160 => Ok(Reg::CC16IC),
-                    0xF162 => Ok(Reg::CC17IC),
-                    0xF164 => Ok(Reg::CC18IC),
-                    0xF166 => Ok(Reg::CC19IC),
-                    0xF168 => Ok(Reg::CC20IC),
-                    0xF16A => Ok(Reg::CC21IC),
-                    0xF16C => Ok(Reg::CC22IC),
-                    0xF16E => Ok(Reg::CC23IC),
-                    0xF170 => Ok(Reg::CC24IC),
-                    0xF172 => Ok(Reg::CC25IC),
-                    0xF174 => Ok(Reg::CC26IC),
-                    0xF176 => Ok(Reg::CC27IC),
-                    0xF178 => Ok(Reg::CC28IC),
-                    0xF184 => Ok(Reg::CC29IC),
-                    0xF18C => Ok(Reg::CC30IC),
-                    0xF194 => Ok(Reg::CC31IC),
-                    0xF100 => Ok(Reg::DP0L),
-                    0xF102 => Ok(Reg::DP0H),
-                    0xF104 => Ok(Reg::DP1L),
-                    0xF106 => Ok(Reg::DP1H),
-                    0xF1C0 => Ok(Reg::EXICON),
-                    0xF1C2 => Ok(Reg::ODP2),
-                    0xF1C6 => Ok(Reg::ODP3),
-                    0xF1CE => Ok(Reg::ODP6),
-                    0xF1D2 => Ok(Reg::ODP7),
-                    0xF1D6 => Ok(Reg::ODP8),
-                    0xF1C4 => Ok(Reg::PICON),
-                    0xF038 => Ok(Reg::PP0),
-                    0xF03A => Ok(Reg::PP1),
-                    0xF03C => Ok(Reg::PP2),
-                    0xF03E => Ok(Reg::PP3),
-                    0xF030 => Ok(Reg::PT0),
-                    0xF032 => Ok(Reg::PT1),
-                    0xF034 => Ok(Reg::PT2),
-                    0xF036 => Ok(Reg::PT3),
-                    0xF17E => Ok(Reg::PWMIC),
-                    0xF108 => Ok(Reg::RP0H),
-                    0xF19C => Ok(Reg::S0TBIC),
-                    0xF0B4 => Ok(Reg::SSCBR),
-                    0xF0B2 => Ok(Reg::SSCRB),
-                    0xF0B0 => Ok(Reg::SSCTB),
-                    0xF050 => Ok(Reg::T7),
-                    0xF17A => Ok(Reg::T7IC),
-                    0xF054 => Ok(Reg::T7REL),
-                    0xF052 => Ok(Reg::T8),
-                    0xF17C => Ok(Reg::T8IC),
-                    0xF056 => Ok(Reg::T8REL),
-                    0xF186 => Ok(Reg::XP0IC),
-                    0xF18E => Ok(Reg::XP1IC),
-                    0xF196 => Ok(Reg::XP2IC),
-                    0xF19E => Ok(Reg::XP3IC),
-                    _ => Err(())
-                }
+    // The inverse of to_phys16, addresses without a name come back as SFR(r)/ESFR(r)
+    pub fn from_phys16(address: u16) -> Result<Reg, RegError> {
+        match address {
+            0xEF00 => Ok(Reg::C1CSR),
+            0xEF02 => Ok(Reg::C1IR),
+            0xEF04 => Ok(Reg::C1BTR),
+            0xEF06 => Ok(Reg::C1GMS),
+            0xEF08 => Ok(Reg::C1UGML),
+            0xEF0A => Ok(Reg::C1LGML),
+            0xEF0C => Ok(Reg::C1UMLM),
+            0xEF0E => Ok(Reg::C1LMLM),
+
+            // Short addresses F0h..FFh select a GPR, so the top 32 bytes of each area can't be named by one
+            0xF000..=0xF1DE if address & 1 == 0 => {
+                Reg::from_reg8(((address - 0xF000) / 2) as u8, &OperandType::ExtendedRegister)
             },
-            &OperandType::ByteRegister(_) |
-            &OperandType::WordRegister(_) => {
-                match address {
-                    0xFF98 => Ok(Reg::ADCIC),
-                    0xFFA0 => Ok(Reg::ADCON),
-                    0xFEA0 => Ok(Reg::ADDAT),
-                    0xF0A0 => Ok(Reg::ADDAT2),
-                    0xFE18 => Ok(Reg::ADDRSEL1),
-                    0xFE1A => Ok(Reg::ADDRSEL2),
-                    0xFE1C => Ok(Reg::ADDRSEL3),
-                    0xFE1E => Ok(Reg::ADDRSEL4),
-                    0xFF9A => Ok(Reg::ADEIC),
-                    0xFF0C => Ok(Reg::BUSCON0),
-                    0xFF14 => Ok(Reg::BUSCON1),
-                    0xFF16 => Ok(Reg::BUSCON2),
-                    0xFF18 => Ok(Reg::BUSCON3),
-                    0xFF1A => Ok(Reg::BUSCON4),
-                    0xEF04 => Ok(Reg::C1BTR),
-                    0xEF00 => Ok(Reg::C1CSR),
-                    0xEF06 => Ok(Reg::C1GMS),
-                    0xEF02 => Ok(Reg::C1IR),
-                    0xEF0A => Ok(Reg::C1LGML),
-                    0xEF0E => Ok(Reg::C1LMLM),
-                    0xEF08 => Ok(Reg::C1UGML),
-                    0xEF0C => Ok(Reg::C1UMLM),
-                    0xFE4A => Ok(Reg::CAPREL),
-                    0xFE80 => Ok(Reg::CC0),
-                    0xFF78 => Ok(Reg::CC0IC),
-                    0xFFC2 => Ok(Reg::DP2),
-                    0xFFC6 => Ok(Reg::DP3),
-                    0xFFCA => Ok(Reg::DP4),
-                    0xFFCE => Ok(Reg::DP6),
-                    0xFFD2 => Ok(Reg::DP7),
-                    0xFFD6 => Ok(Reg::DP8),
-                    0xFE00 => Ok(Reg::DPP0),
-                    0xFE02 => Ok(Reg::DPP1),
-                    0xFE04 => Ok(Reg::DPP2),
-                    0xFE06 => Ok(Reg::DPP3),
-                    0xF1C0 => Ok(Reg::EXICON),
-                    0xFF0E => Ok(Reg::MDC),
-                    0xFE0C => Ok(Reg::MDH),
-                    0xFE0E => Ok(Reg::MDL),
-                    0xF1C2 => Ok(Reg::ODP2),
-                    0xF1C6 => Ok(Reg::ODP3),
-                    0xF1CE => Ok(Reg::ODP6),
-                    0xF1D2 => Ok(Reg::ODP7),
-                    0xF1D6 => Ok(Reg::ODP8),
-                    0xFF1E => Ok(Reg::ONES),
-                    0xFF00 => Ok(Reg::P0L),
-                    0xFF02 => Ok(Reg::P0H),
-                    0xFF04 => Ok(Reg::P1L),
-                    0xFF06 => Ok(Reg::P1H),
-                    0xFFC0 => Ok(Reg::P2),
-                    0xFFC4 => Ok(Reg::P3),
-                    0xFFC8 => Ok(Reg::P4),
-                    0xFFA2 => Ok(Reg::P5),
-                    0xFFCC => Ok(Reg::P6),
-                    0xFFD0 => Ok(Reg::P7),
-                    0xFFD4 => Ok(Reg::P8),
-                    0xFEC0 => Ok(Reg::PECC0),
-                    0xFEC2 => Ok(Reg::PECC1),
-                    0xFEC4 => Ok(Reg::PECC2),
-                    0xFEC6 => Ok(Reg::PECC3),
-                    0xFEC8 => Ok(Reg::PECC4),
-                    0xFECA => Ok(Reg::PECC5),
-                    0xFECC => Ok(Reg::PECC6),
-                    0xFECE => Ok(Reg::PECC7),
-                    0xF1C4 => Ok(Reg::PICON),
-                    0xF038 => Ok(Reg::PP0),
-                    0xF03A => Ok(Reg::PP1),
-                    0xF03C => Ok(Reg::PP2),
-                    0xF03E => Ok(Reg::PP3),
-                    0xFF10 => Ok(Reg::PSW),
-                    0xF030 => Ok(Reg::PT0),
-                    0xF032 => Ok(Reg::PT1),
-                    0xF034 => Ok(Reg::PT2),
-                    0xF036 => Ok(Reg::PT3),
-                    0xFE30 => Ok(Reg::PW0),
-                    0xFE32 => Ok(Reg::PW1),
-                    0xFE34 => Ok(Reg::PW2),
-                    0xFE36 => Ok(Reg::PW3),
-                    0xFF30 => Ok(Reg::PWMCON0),
-                    0xFF32 => Ok(Reg::PWMCON1),
-                    0xF17E => Ok(Reg::PWMIC),
-                    0xF108 => Ok(Reg::RP0H),
-                    0xFEB4 => Ok(Reg::S0BG),
-                    0xFFB0 => Ok(Reg::S0CON),
-                    0xFF70 => Ok(Reg::S0EIC),
-                    0xFEB2 => Ok(Reg::S0RBUF),
-                    0xFF6E => Ok(Reg::S0RIC),
-                    0xF19C => Ok(Reg::S0TBIC),
-                    0xFEB0 => Ok(Reg::S0TBUF),
-                    0xFF6C => Ok(Reg::S0TIC),
-                    0xFE12 => Ok(Reg::SP),
-                    0xF0B4 => Ok(Reg::SSCBR),
-                    0xFFB2 => Ok(Reg::SSCCON),
-                    0xFF76 => Ok(Reg::SSCEIC),
-                    0xF0B2 => Ok(Reg::SSCRB),
-                    0xFF74 => Ok(Reg::SSCRIC),
-                    0xF0B0 => Ok(Reg::SSCTB),
-                    0xFF72 => Ok(Reg::SSCTIC),
-                    0xFE14 => Ok(Reg::STKOV),
-                    0xFE16 => Ok(Reg::STKUN),
-                    0xFF12 => Ok(Reg::SYSCON),
-                    0xFE50 => Ok(Reg::T0),
-                    0xFF50 => Ok(Reg::T01CON),
-                    0xFF9C => Ok(Reg::T0IC),
-                    0xFE54 => Ok(Reg::T0REL),
-                    0xFE52 => Ok(Reg::T1),
-                    0xFF9E => Ok(Reg::T1IC),
-                    0xFE56 => Ok(Reg::T1REL),
-                    0xFFAC => Ok(Reg::TFR),
-                    0xFEAE => Ok(Reg::WDT),
-                    0xFFAE => Ok(Reg::WDTCON),
-                    0xF186 => Ok(Reg::XP0IC),
-                    0xF18E => Ok(Reg::XP1IC),
-                    0xF196 => Ok(Reg::XP2IC),
-                    0xF19E => Ok(Reg::XP3IC),
-                    0xFF1C => Ok(Reg::ZEROS),
-                    _ => Err(())
-                }
+            0xFE00..=0xFFDE if address & 1 == 0 => {
+                Reg::from_reg8(((address - 0xFE00) / 2) as u8, &OperandType::WordRegister(0))
             },
-            _ => Err(())
+            _ => Err(RegError::UnknownAddress(address))
+        }
+    }
+
+    // The enum names the C167CR's registers, anything a description puts elsewhere or calls
+    // something else is Named
+    pub fn from_info(info: &'static RegisterInfo) -> Reg {
+        match Reg::from_phys16(info.address) {
+            Ok(reg) if reg.to_string() == info.name => reg,
+            _ => Reg::Named(info)
+        }
+    }
+
+    // As from_phys16, going by what registers has at the address.  Addresses it doesn't describe
+    // come back as SFR(r)/ESFR(r).
+    pub fn from_address(address: u16, registers: &'static RegisterMap) -> Result<Reg, RegError> {
+        if let Some(info) = registers.by_address(address) {
+            return Ok(Reg::from_info(info));
+        }
+
+        match address {
+            0xF000..=0xF1DE if address & 1 == 0 => Ok(Reg::ESFR(((address - 0xF000) / 2) as u8)),
+            0xFE00..=0xFFDE if address & 1 == 0 => Ok(Reg::SFR(((address - 0xFE00) / 2) as u8)),
+            _ => Reg::from_phys16(address)
+        }
+    }
+
+    // The register registers has under this one's name, which needn't be where the C167CR has
+    // it, and None when the map doesn't have it.  GPRs, the CAN registers and SFRs only known by
+    // their address aren't up to the map.
+    pub fn named_by(&self, registers: &'static RegisterMap) -> Option<Reg> {
+        match (*self, self.to_phys16()) {
+            (Reg::SFR(_), _) |
+            (Reg::ESFR(_), _) => Some(*self),
+            (_, Ok(0xF000..=0xF1DE)) |
+            (_, Ok(0xFE00..=0xFFDE)) => registers.by_name(&self.to_string()).map(Reg::from_info),
+            _ => Some(*self)
         }
     }
 
@@ -1028,12 +1019,95 @@ impl Reg {
         }
     }
 
+    // Lives in the ESFR space, so it needs an EXT*R sequence and a C165/C167 class part
+    pub fn is_extended(&self) -> bool {
+        match *self {
+            Reg::ADDAT2 |
+            Reg::CC16IC |
+            Reg::CC17IC |
+            Reg::CC18IC |
+            Reg::CC19IC |
+            Reg::CC20IC |
+            Reg::CC21IC |
+            Reg::CC22IC |
+            Reg::CC23IC |
+            Reg::CC24IC |
+            Reg::CC25IC |
+            Reg::CC26IC |
+            Reg::CC27IC |
+            Reg::CC28IC |
+            Reg::CC29IC |
+            Reg::CC30IC |
+            Reg::CC31IC |
+            Reg::DP0L |
+            Reg::DP0H |
+            Reg::DP1L |
+            Reg::DP1H |
+            Reg::EXICON |
+            Reg::ODP2 |
+            Reg::ODP3 |
+            Reg::ODP6 |
+            Reg::ODP7 |
+            Reg::ODP8 |
+            Reg::PICON |
+            Reg::PP0 |
+            Reg::PP1 |
+            Reg::PP2 |
+            Reg::PP3 |
+            Reg::PT0 |
+            Reg::PT1 |
+            Reg::PT2 |
+            Reg::PT3 |
+            Reg::PWMIC |
+            Reg::RP0H |
+            Reg::S0TBIC |
+            Reg::SSCBR |
+            Reg::SSCRB |
+            Reg::SSCTB |
+            Reg::T7 |
+            Reg::T7IC |
+            Reg::T7REL |
+            Reg::T8 |
+            Reg::T8IC |
+            Reg::T8REL |
+            Reg::XP0IC |
+            Reg::XP1IC |
+            Reg::XP2IC |
+            Reg::XP3IC |
+            Reg::QR0 |
+            Reg::QR1 |
+            Reg::QX0 |
+            Reg::QX1 |
+            Reg::ESFR(_) => true,
+            Reg::Named(info) => info.is_extended(),
+            _            => false,
+        }
+    }
+
+    // Belongs to the MAC unit, so it needs an ST10 or XC16x
+    pub fn is_mac(&self) -> bool {
+        match *self {
+            Reg::IDX0 |
+            Reg::IDX1 |
+            Reg::MAH |
+            Reg::MAL |
+            Reg::MAS |
+            Reg::MCW |
+            Reg::MRW |
+            Reg::MSW |
+            Reg::QR0 |
+            Reg::QR1 |
+            Reg::QX0 |
+            Reg::QX1 => true,
+            _        => false,
+        }
+    }
 }
 
 impl FromStr for Reg {
-    type Err = ();
+    type Err = RegError;
 
-    fn from_str(s: &str) -> Result<Reg, ()> {
+    fn from_str(s: &str) -> Result<Reg, RegError> {
         let mnem = String::from(s).to_uppercase();
         match mnem.as_str() {
             "RL0" => Ok(Reg::RL0),
@@ -1226,7 +1300,87 @@ impl FromStr for Reg {
             "WDTCON"      => Ok(Reg::WDTCON),
             "ZEROS"       => Ok(Reg::ZEROS),
 
-            _ => Err(()),
+            "ADDAT2"      => Ok(Reg::ADDAT2),
+            "CC16IC"      => Ok(Reg::CC16IC),
+            "CC17IC"      => Ok(Reg::CC17IC),
+            "CC18IC"      => Ok(Reg::CC18IC),
+            "CC19IC"      => Ok(Reg::CC19IC),
+            "CC20IC"      => Ok(Reg::CC20IC),
+            "CC21IC"      => Ok(Reg::CC21IC),
+            "CC22IC"      => Ok(Reg::CC22IC),
+            "CC23IC"      => Ok(Reg::CC23IC),
+            "CC24IC"      => Ok(Reg::CC24IC),
+            "CC25IC"      => Ok(Reg::CC25IC),
+            "CC26IC"      => Ok(Reg::CC26IC),
+            "CC27IC"      => Ok(Reg::CC27IC),
+            "CC28IC"      => Ok(Reg::CC28IC),
+            "CC29IC"      => Ok(Reg::CC29IC),
+            "CC30IC"      => Ok(Reg::CC30IC),
+            "CC31IC"      => Ok(Reg::CC31IC),
+            "DP0L"        => Ok(Reg::DP0L),
+            "DP0H"        => Ok(Reg::DP0H),
+            "DP1L"        => Ok(Reg::DP1L),
+            "DP1H"        => Ok(Reg::DP1H),
+            "EXICON"      => Ok(Reg::EXICON),
+            "ODP2"        => Ok(Reg::ODP2),
+            "ODP3"        => Ok(Reg::ODP3),
+            "ODP6"        => Ok(Reg::ODP6),
+            "ODP7"        => Ok(Reg::ODP7),
+            "ODP8"        => Ok(Reg::ODP8),
+            "PICON"       => Ok(Reg::PICON),
+            "PP0"         => Ok(Reg::PP0),
+            "PP1"         => Ok(Reg::PP1),
+            "PP2"         => Ok(Reg::PP2),
+            "PP3"         => Ok(Reg::PP3),
+            "PT0"         => Ok(Reg::PT0),
+            "PT1"         => Ok(Reg::PT1),
+            "PT2"         => Ok(Reg::PT2),
+            "PT3"         => Ok(Reg::PT3),
+            "PWMIC"       => Ok(Reg::PWMIC),
+            "RP0H"        => Ok(Reg::RP0H),
+            "S0TBIC"      => Ok(Reg::S0TBIC),
+            "SSCBR"       => Ok(Reg::SSCBR),
+            "SSCRB"       => Ok(Reg::SSCRB),
+            "SSCTB"       => Ok(Reg::SSCTB),
+            "T7"          => Ok(Reg::T7),
+            "T7IC"        => Ok(Reg::T7IC),
+            "T7REL"       => Ok(Reg::T7REL),
+            "T8"          => Ok(Reg::T8),
+            "T8IC"        => Ok(Reg::T8IC),
+            "T8REL"       => Ok(Reg::T8REL),
+            "XP0IC"       => Ok(Reg::XP0IC),
+            "XP1IC"       => Ok(Reg::XP1IC),
+            "XP2IC"       => Ok(Reg::XP2IC),
+            "XP3IC"       => Ok(Reg::XP3IC),
+
+            "C1BTR"       => Ok(Reg::C1BTR),
+            "C1CSR"       => Ok(Reg::C1CSR),
+            "C1GMS"       => Ok(Reg::C1GMS),
+            "C1IR"        => Ok(Reg::C1IR),
+            "C1LGML"      => Ok(Reg::C1LGML),
+            "C1LMLM"      => Ok(Reg::C1LMLM),
+            "C1UGML"      => Ok(Reg::C1UGML),
+            "C1UMLM"      => Ok(Reg::C1UMLM),
+
+            "IDX0"        => Ok(Reg::IDX0),
+            "IDX1"        => Ok(Reg::IDX1),
+            "MAH"         => Ok(Reg::MAH),
+            "MAL"         => Ok(Reg::MAL),
+            "MAS"         => Ok(Reg::MAS),
+            "MCW"         => Ok(Reg::MCW),
+            "MRW"         => Ok(Reg::MRW),
+            "MSW"         => Ok(Reg::MSW),
+            "QR0"         => Ok(Reg::QR0),
+            "QR1"         => Ok(Reg::QR1),
+            "QX0"         => Ok(Reg::QX0),
+            "QX1"         => Ok(Reg::QX1),
+
+            // Registers only the device descriptions know about.  Which device doesn't matter here,
+            // the encoder looks the name up again in the selected part's map (see named_by).
+            _ => match RegisterMap::builtins().filter_map(|map| map.by_name(s)).next() {
+                Some(info) => Ok(Reg::Named(info)),
+                None => Err(RegError::UnknownName(s.to_string())),
+            }
         }
     }
 }
@@ -1271,6 +1425,7 @@ impl fmt::Display for Reg {
 
             Reg::SFR(r) => write!(f, "{:04X}h", 0xFE00 + (r as u16 * 2)),
             Reg::ESFR(r) => write!(f, "{:04X}h", 0xF000 + (r as u16 * 2)),
+            Reg::Named(info) => write!(f, "{}", info.name),
 
             _ => write!(f, "{:?}", self)
         }
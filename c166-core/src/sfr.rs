@@ -0,0 +1,305 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::fs;
+use std::path::Path;
+
+use toml::Value;
+use toml::value::Table;
+
+use ::error::SfrError;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Access {
+    ReadWrite,
+    ReadOnly,
+    WriteOnly,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BitField {
+    pub name: String,
+    // Position of the least significant bit
+    pub bit: u8,
+    pub width: u8,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegisterInfo {
+    pub name: String,
+    pub address: u16,
+    pub width: u8,
+    // None when it depends on the configuration (e.g. SYSCON)
+    pub reset: Option<u16>,
+    pub access: Access,
+    pub fields: Vec<BitField>,
+}
+
+impl RegisterInfo {
+    // Lives in the ESFR space at F000h rather than the SFR space at FE00h
+    pub fn is_extended(&self) -> bool {
+        self.address < 0xFE00
+    }
+
+    // The 8-bit reg operand, relative to FE00h or F000h
+    pub fn short_address(&self) -> u8 {
+        match self.is_extended() {
+            true => ((self.address - 0xF000) / 2) as u8,
+            false => ((self.address - 0xFE00) / 2) as u8,
+        }
+    }
+
+    // The upper half of each space can be reached by the bit instructions
+    pub fn is_bit_addressable(&self) -> bool {
+        (0xFF00..=0xFFDF).contains(&self.address) || (0xF100..=0xF1DF).contains(&self.address)
+    }
+
+    // The bitoff operand the bit instructions use for this register
+    pub fn bit_offset(&self) -> Option<u8> {
+        match self.is_bit_addressable() {
+            true => Some(0x80 + ((self.address & 0x00FF) / 2) as u8),
+            false => None
+        }
+    }
+
+    pub fn field(&self, name: &str) -> Option<&BitField> {
+        self.fields.iter().find(|field| field.name.eq_ignore_ascii_case(name))
+    }
+
+    // The single bit field at bit, if there is one
+    pub fn bit(&self, bit: u8) -> Option<&BitField> {
+        self.fields.iter().find(|field| field.width == 1 && field.bit == bit)
+    }
+
+    // Fields holding a non-zero value, as "NAME = value"
+    pub fn describe(&self, value: u16) -> Vec<String> {
+        self.fields.iter()
+            .filter_map(|field| {
+                let mask: u32 = (1 << field.width) - 1;
+                let field_value = (value as u32 >> field.bit) & mask;
+                match (field.width, field_value) {
+                    (_, 0) => None,
+                    (1, _) => Some(field.name.clone()),
+                    _ => Some(format!("{} = {}", field.name, field_value))
+                }
+            })
+            .collect()
+    }
+}
+
+// The SFRs and ESFRs of one device
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegisterMap {
+    pub device: String,
+    pub description: String,
+    pub registers: Vec<RegisterInfo>,
+}
+
+// The descriptions in sfr/, generated by build.rs
+include!(concat!(env!("OUT_DIR"), "/sfr_devices.rs"));
+
+lazy_static! {
+    static ref BUILTIN_MAPS: Vec<RegisterMap> = BUILTIN_DEVICES.iter()
+        .map(|(device, source)| {
+            let map = RegisterMap::from_toml(source).unwrap_or_else(|e| panic!("sfr/{}.toml: {}", device, e));
+            assert_eq!(map.device, *device, "sfr/{}.toml describes a different device", device);
+            map
+        })
+        .collect();
+}
+
+fn invalid(register: &str, key: &'static str, reason: &'static str) -> SfrError {
+    SfrError::InvalidEntry { register: register.to_string(), key: key, reason: reason }
+}
+
+fn get_str<'a>(table: &'a Table, key: &'static str, register: &str) -> Result<Option<&'a str>, SfrError> {
+    match table.get(key) {
+        Some(value) => value.as_str().map(Some).ok_or(invalid(register, key, "Must be a string")),
+        None => Ok(None)
+    }
+}
+
+fn get_int(table: &Table, key: &'static str, register: &str, max: i64) -> Result<Option<i64>, SfrError> {
+    match table.get(key) {
+        Some(value) => match value.as_integer() {
+            Some(int) if int >= 0 && int <= max => Ok(Some(int)),
+            Some(_) => Err(invalid(register, key, "Out of range")),
+            None => Err(invalid(register, key, "Must be an integer"))
+        },
+        None => Ok(None)
+    }
+}
+
+fn parse_field(value: &Value, register: &str, width: u8) -> Result<BitField, SfrError> {
+    let field = value.as_table().ok_or(invalid(register, "fields", "Each field must be a table"))?;
+
+    let name = get_str(field, "name", register)?.ok_or(invalid(register, "fields", "Each field needs a name"))?;
+    let bit = get_int(field, "bit", register, 15)?.ok_or(invalid(register, "fields", "Each field needs a bit"))? as u8;
+    let field_width = get_int(field, "width", register, 16)?.unwrap_or(1) as u8;
+
+    if field_width == 0 || bit + field_width > width {
+        return Err(invalid(register, "fields", "Field doesn't fit in the register"));
+    }
+
+    Ok(BitField { name: name.to_uppercase(), bit: bit, width: field_width })
+}
+
+fn parse_register(value: &Value) -> Result<RegisterInfo, SfrError> {
+    let table = value.as_table().ok_or(invalid("", "register", "Must be a table"))?;
+
+    let name = get_str(table, "name", "")?.ok_or(invalid("", "name", "Every register needs a name"))?;
+    let address = get_int(table, "address", name, 0xFFFF)?.ok_or(invalid(name, "address", "Every register needs an address"))? as u16;
+    match address {
+        0xF000..=0xF1FE |
+        0xFE00..=0xFFFE if address & 1 == 0 => {},
+        _ => return Err(invalid(name, "address", "Must be a word address in the SFR or ESFR space"))
+    }
+
+    let width = match get_int(table, "width", name, 16)? {
+        None | Some(16) => 16,
+        Some(8) => 8,
+        Some(_) => return Err(invalid(name, "width", "Must be 8 or 16"))
+    };
+
+    let access = match get_str(table, "access", name)? {
+        None | Some("rw") => Access::ReadWrite,
+        Some("r") => Access::ReadOnly,
+        Some("w") => Access::WriteOnly,
+        Some(_) => return Err(invalid(name, "access", "Must be \"rw\", \"r\" or \"w\""))
+    };
+
+    let fields = match table.get("fields") {
+        Some(fields) => {
+            let fields = fields.as_array().ok_or(invalid(name, "fields", "Must be an array"))?;
+            fields.iter().map(|field| parse_field(field, name, width)).collect::<Result<Vec<BitField>, SfrError>>()?
+        },
+        None => vec![]
+    };
+
+    Ok(RegisterInfo {
+        name: name.to_uppercase(),
+        address: address,
+        width: width,
+        reset: get_int(table, "reset", name, 0xFFFF)?.map(|reset| reset as u16),
+        access: access,
+        fields: fields,
+    })
+}
+
+impl RegisterMap {
+    // Parses a description in the format of the files in sfr/
+    pub fn from_toml(source: &str) -> Result<RegisterMap, SfrError> {
+        let value: Value = source.parse().map_err(|e: ::toml::de::Error| SfrError::Syntax(e.to_string()))?;
+        let table = value.as_table().ok_or(SfrError::Syntax("Expected a table".to_string()))?;
+
+        let device = get_str(table, "device", "")?.ok_or(invalid("", "device", "Every description needs a device"))?;
+        let description = get_str(table, "description", "")?.unwrap_or("");
+
+        // Inherited registers come first, the device's own can't clash with them
+        let mut registers: Vec<RegisterInfo> = match get_str(table, "extends", "")? {
+            Some(parent) => RegisterMap::builtin_source(parent)
+                .ok_or(SfrError::UnknownDevice(parent.to_string()))
+                .and_then(RegisterMap::from_toml)?
+                .registers,
+            None => vec![]
+        };
+
+        // Less any the device doesn't have
+        if let Some(names) = table.get("omit") {
+            let names = names.as_array().ok_or(invalid("", "omit", "Must be an array of register names"))?;
+            for name in names {
+                let name = name.as_str().ok_or(invalid("", "omit", "Must be an array of register names"))?;
+                let index = registers.iter()
+                    .position(|register| register.name.eq_ignore_ascii_case(name))
+                    .ok_or(invalid(name, "omit", "Not an inherited register"))?;
+                registers.remove(index);
+            }
+        }
+
+        if let Some(entries) = table.get("register") {
+            let entries = entries.as_array().ok_or(invalid("", "register", "Must be an array of tables"))?;
+            for entry in entries {
+                let register = parse_register(entry)?;
+                if registers.iter().any(|other| other.name == register.name || other.address == register.address) {
+                    return Err(SfrError::Duplicate { register: register.name });
+                }
+                registers.push(register);
+            }
+        }
+
+        Ok(RegisterMap {
+            device: device.to_string(),
+            description: description.to_string(),
+            registers: registers,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<RegisterMap, SfrError> {
+        let source = fs::read_to_string(path).map_err(|e| SfrError::Io(e.to_string()))?;
+        RegisterMap::from_toml(&source)
+    }
+
+    fn builtin_source(device: &str) -> Option<&'static str> {
+        BUILTIN_DEVICES.iter().find(|(name, _)| name.eq_ignore_ascii_case(device)).map(|(_, source)| *source)
+    }
+
+    // One of the descriptions embedded at build time
+    pub fn builtin(device: &str) -> Option<&'static RegisterMap> {
+        BUILTIN_MAPS.iter().find(|map| map.device.eq_ignore_ascii_case(device))
+    }
+
+    pub fn builtins() -> impl Iterator<Item = &'static RegisterMap> {
+        BUILTIN_MAPS.iter()
+    }
+
+    pub fn by_name(&self, name: &str) -> Option<&RegisterInfo> {
+        self.registers.iter().find(|register| register.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn by_address(&self, address: u16) -> Option<&RegisterInfo> {
+        self.registers.iter().find(|register| register.address == address)
+    }
+
+    // A single bit field by name, as long as the name only occurs once in the map
+    pub fn by_bit_name(&self, name: &str) -> Option<(&RegisterInfo, &BitField)> {
+        let mut bits = self.registers.iter()
+            .filter(|register| register.is_bit_addressable())
+            .flat_map(|register| register.fields.iter().map(move |field| (register, field)))
+            .filter(|(_, field)| field.width == 1 && field.name.eq_ignore_ascii_case(name));
+
+        match (bits.next(), bits.next()) {
+            (Some(bit), None) => Some(bit),
+            _ => None
+        }
+    }
+
+    // How a bit operand is written: the bare field name when it's unique, REG.FIELD when it
+    // isn't and None when the bit isn't named
+    pub fn bit_name(&self, address: u16, bit: u8) -> Option<String> {
+        let register = self.by_address(address)?;
+        let field = register.bit(bit)?;
+        match self.by_bit_name(&field.name) {
+            Some(_) => Some(field.name.clone()),
+            None => Some(format!("{}.{}", register.name, field.name))
+        }
+    }
+
+    // As used by the reg operands, extended picks the ESFR space
+    pub fn by_short_address(&self, short_address: u8, extended: bool) -> Option<&RegisterInfo> {
+        self.registers.iter().find(|register| register.is_extended() == extended && register.short_address() == short_address)
+    }
+}
@@ -0,0 +1,391 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use ::assembler::*;
+use ::cpu::CpuModel;
+use ::decoder::decode;
+use ::error::*;
+use ::parser::*;
+
+fn assemble_at(source: &str, origin: u32) -> Result<Assembly, AsmError> {
+    let mut op_lut: OpLookUpTable = OpLookUpTable::new();
+    build_lut(&mut op_lut);
+
+    assemble(source, origin, &op_lut, CpuModel::default()).map_err(|diagnostics| diagnostics[0].error.clone())
+}
+
+fn assemble_listing_at(source: &str, origin: u32) -> Result<Assembly, AsmError> {
+    let mut op_lut: OpLookUpTable = OpLookUpTable::new();
+    build_lut(&mut op_lut);
+
+    assemble_listing(source, origin, &op_lut, CpuModel::default()).map_err(|diagnostics| diagnostics[0].error.clone())
+}
+
+#[test]
+fn backward_reference() {
+    let assembly = assemble_at("loop: sub r1, #1\n    jmpr cc_NZ, loop\n", 0x100).unwrap();
+    assert_eq!(assembly.image(0), vec![0x28, 0x11, 0x3D, 0xFE]);
+    assert_eq!(assembly.symbols.get("loop"), Some(&0x100));
+}
+
+#[test]
+fn forward_reference() {
+    let source = "
+        jmpr cc_Z, done
+        calla cc_UC, done
+    done:
+        ret
+    ";
+    let assembly = assemble_at(source, 0x200).unwrap();
+    assert_eq!(assembly.image(0), vec![0x2D, 0x02, 0xCA, 0x00, 0x06, 0x02, 0xCB, 0x00]);
+    assert_eq!(assembly.symbols.get("done"), Some(&0x206));
+}
+
+#[test]
+fn segments() {
+    let assembly = assemble_at("calls far, far\nfar: ret\n", 0x1FFFC).unwrap();
+    assert_eq!(assembly.image(0), vec![0xDA, 0x02, 0x00, 0x00, 0xCB, 0x00]);
+}
+
+#[test]
+fn location_counter() {
+    let assembly = assemble_at("nop\njmpr cc_UC, $\n", 0).unwrap();
+    assert_eq!(assembly.image(0), vec![0xCC, 0x00, 0x0D, 0xFF]);
+}
+
+#[test]
+fn immediate_symbol() {
+    let assembly = assemble_at("mov r1, #table\ntable: ret\n", 0x1234).unwrap();
+    assert_eq!(assembly.image(0), vec![0xE6, 0xF1, 0x38, 0x12, 0xCB, 0x00]);
+}
+
+#[test]
+fn symbol_errors() {
+    assert_eq!(assemble_at("jmpr cc_UC, nowhere\n", 0), Err(AsmError::UndefinedSymbol { line: 1, name: "nowhere".to_string() }));
+    assert_eq!(assemble_at("here: nop\nhere: nop\n", 0), Err(AsmError::DuplicateSymbol { line: 2, name: "here".to_string() }));
+    assert_eq!(assemble_at("nop\nmov r1,, r2\n", 0), Err(AsmError::Syntax { line: 2 }));
+
+    // 256 bytes on from the jmpr
+    let mut source = String::from("jmpr cc_UC, far\n");
+    source.push_str(&"nop\n".repeat(128));
+    source.push_str("far: ret\n");
+    match assemble_at(&source, 0) {
+        Err(AsmError::Encode { line: 1, error: EncodeError::UnreachableTarget { .. } }) => {},
+        other => assert!(false, "Expected an unreachable target, got: {:?}", other)
+    }
+}
+
+fn assemble_text(source: &str) -> Result<Assembly, AsmError> {
+    assemble_source(source, CpuModel::default()).map_err(|diagnostics| diagnostics[0].error.clone())
+}
+
+#[test]
+fn data() {
+    let source = "
+        db 1, 'ab', \"c\" ; comment
+        DW 1234h, 'A'
+table:  dw table
+        END
+        db nowhere
+    ";
+    let assembly = assemble_text(source).unwrap();
+    assert_eq!(assembly.image(0), vec![0x01, 0x61, 0x62, 0x63, 0x34, 0x12, 0x41, 0x00, 0x08, 0x00]);
+
+    assert_eq!(assemble_text("db 100h\n"), Err(AsmError::OutOfRange { line: 1, value: 0x100, min: -0x80, max: 0xFF }));
+}
+
+#[test]
+fn constants() {
+    let source = "
+COUNT   equ 10
+STEP    set 1
+STEP    set 2
+        mov r1, #COUNT
+        mov r2, #STEP
+        org START
+START   equ 200h
+        nop
+    ";
+    let assembly = assemble_text(source).unwrap();
+    assert_eq!(assembly.blocks.len(), 2);
+    assert_eq!(assembly.blocks[1].address, 0x200);
+    assert_eq!(assembly.blocks[0].bytes, vec![0xE0, 0xA1, 0xE0, 0x22]);
+    assert_eq!(assembly.symbols.get("COUNT"), Some(&10));
+
+    assert_eq!(assemble_text("A equ 1\nA equ 2\n"), Err(AsmError::DuplicateSymbol { line: 2, name: "A".to_string() }));
+    assert_eq!(assemble_text("A equ 1\nA set 2\n"), Err(AsmError::DuplicateSymbol { line: 2, name: "A".to_string() }));
+}
+
+#[test]
+fn negative_constants() {
+    let source = "
+X       equ -1
+MASK    set -2
+        mov r1, #X
+        and r2, #MASK
+        db X
+    ";
+    let assembly = assemble_text(source).unwrap();
+    assert_eq!(assembly.symbols.get("X"), Some(&-1));
+    assert_eq!(assembly.blocks[0].bytes, vec![0xE6, 0xF1, 0xFF, 0xFF, 0x66, 0xF2, 0xFE, 0xFF, 0xFF]);
+
+    // It's where the constant goes that has a range
+    assert_eq!(assemble_text("X equ -1\norg X\n"), Err(AsmError::OutOfRange { line: 2, value: -1, min: 0, max: 0xFFFFFF }));
+}
+
+#[test]
+fn placement() {
+    let source = "
+        org 100h
+        db 1
+        even
+aligned: db 2
+        align 8
+eight:  ds 3
+after:  ret
+    ";
+    let assembly = assemble_text(source).unwrap();
+    assert_eq!(assembly.symbols.get("aligned"), Some(&0x102));
+    assert_eq!(assembly.symbols.get("eight"), Some(&0x108));
+    assert_eq!(assembly.symbols.get("after"), Some(&0x10B));
+    assert_eq!(assembly.origin(), Some(0x100));
+    assert_eq!(assembly.image(0xFF), vec![0x01, 0xFF, 0x02, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xCB, 0x00]);
+}
+
+#[test]
+fn sections() {
+    let source = "
+        org 100h
+        nop
+VECTORS section code at 0
+        jmps main, main
+VECTORS ends
+        nop
+CODE    section code
+main:   ret
+CODE    ends
+    ";
+    let assembly = assemble_text(source).unwrap();
+    let blocks: Vec<(Option<&str>, u32)> = assembly.blocks.iter()
+        .map(|block| (block.section.as_deref(), block.address))
+        .collect();
+    assert_eq!(blocks, vec![(None, 0x100), (Some("VECTORS"), 0), (None, 0x102), (Some("CODE"), 0x104)]);
+    assert_eq!(assembly.blocks[1].bytes, vec![0xFA, 0x00, 0x04, 0x01]);
+
+    assert_eq!(assemble_text("A section code\nB ends\n"), Err(AsmError::UnbalancedSection { line: 2, name: "B".to_string() }));
+}
+
+#[test]
+fn expressions() {
+    let source = "
+BASE    equ 10h
+        org 12344h
+table:  db (BASE + 4) * 2, BASE << 2 | 1, 0FFh & ~BASE xor 1, 7 mod 4, -(-3), $ - table
+        db SEG table, SOF table >> 8, PAG table, POF table shr 8
+    ";
+    let assembly = assemble_text(source).unwrap();
+    assert_eq!(assembly.blocks[0].bytes, vec![0x28, 0x41, 0xEE, 0x03, 0x03, 0x00, 0x01, 0x23, 0x04, 0x23]);
+
+    assert_eq!(assemble_text("db 1 / (2 - 2)\n"), Err(AsmError::DivideByZero { line: 1 }));
+    assert_eq!(assemble_text("db 1 - 130\n"), Err(AsmError::OutOfRange { line: 1, value: -129, min: -0x80, max: 0xFF }));
+}
+
+#[test]
+fn operand_expressions() {
+    let source = "
+        calls SEG func, SOF func
+        mov r1, #SOF func + 2
+        extp #PAG var, #1
+        mov r2, [r3 + #POF var]
+        org 14000h
+func:   ret
+var:    dw 0
+    ";
+    let assembly = assemble_text(source).unwrap();
+    assert_eq!(assembly.blocks[0].bytes, vec![
+        0xDA, 0x01, 0x00, 0x40,
+        0xE6, 0xF1, 0x02, 0x40,
+        0xD7, 0x40, 0x05, 0x00,
+        0xD4, 0x23, 0x02, 0x00,
+    ]);
+
+    assert_eq!(assemble_text("mov r1, #10000h + 0\n"), Err(AsmError::OutOfRange { line: 1, value: 0x10000, min: -0x8000, max: 0xFFFF }));
+    match assemble_text("VECTOR equ 80h\ntrap #VECTOR\n") {
+        Err(AsmError::Encode { line: 2, error: EncodeError::OutOfRange { value: 0x80, max: 0x7F, .. } }) => {},
+        other => assert!(false, "Expected a trap out of range, got: {:?}", other)
+    }
+    match assemble_text("extp #PAG 1000000h, #1\n") {
+        Err(AsmError::Encode { line: 1, error: EncodeError::OutOfRange { value: 0x400, max: 0x3FF, .. } }) => {},
+        other => assert!(false, "Expected a page out of range, got: {:?}", other)
+    }
+}
+
+#[test]
+fn literals() {
+    let source = "
+        mov r1, #0x1F
+        mov r1, #101b
+        mov r1, #12d
+        mov r1, #'AB'
+        mov 0xFE00, r1
+        mov r1, #-1
+        movb rl1, #-1
+        add r1, #-2
+        mov r2, [r3 + #-2]
+        db -1, 'AB', 0X10, 11B, 'A' + 1
+        dw -2, 0FFFFH
+    ";
+    let assembly = assemble_text(source).unwrap();
+    assert_eq!(assembly.blocks[0].bytes, vec![
+        0xE6, 0xF1, 0x1F, 0x00,
+        0xE0, 0x51,
+        0xE0, 0xC1,
+        0xE6, 0xF1, 0x42, 0x41,
+        0xF6, 0xF1, 0x00, 0xFE,
+        0xE6, 0xF1, 0xFF, 0xFF,
+        0xE7, 0xF2, 0xFF, 0x42,
+        0x06, 0xF1, 0xFE, 0xFF,
+        0xD4, 0x23, 0xFE, 0xFF,
+        0xFF, 0x41, 0x42, 0x10, 0x03, 0x42,
+        0xFE, 0xFF, 0xFF, 0xFF,
+    ]);
+
+    assert_eq!(assemble_text("movb rl1, #-81h\n"), Err(AsmError::OutOfRange { line: 1, value: -0x81, min: -0x80, max: 0xFF }));
+    assert_eq!(assemble_text("X equ 300h\nmovb rl0, #X\n"), Err(AsmError::OutOfRange { line: 2, value: 0x300, min: -0x80, max: 0xFF }));
+    assert_eq!(assemble_text("mov r1, #-8001h\n"), Err(AsmError::OutOfRange { line: 1, value: -0x8001, min: -0x8000, max: 0xFFFF }));
+    // data4 is zero extended, a negative shift count has no form to go in
+    match assemble_text("shl r1, #-1\n") {
+        Err(AsmError::Encode { line: 1, error: EncodeError::NoMatchingEncoding { .. } }) => {},
+        other => assert!(false, "Expected no encoding for a negative shift, got: {:?}", other)
+    }
+    assert_eq!(assemble_text("db 10201b\n"), Err(AsmError::Syntax { line: 1 }));
+    assert_eq!(assemble_text("db 'ABC' + 1\n"), Err(AsmError::Syntax { line: 1 }));
+}
+
+#[test]
+fn names_that_look_like_hex() {
+    let source = "
+dach    equ 5
+each:   mov r1, #dach
+        mov r2, dach + 1
+        db dach, 0FFh, 0FFh, 0
+        jmpr cc_UC, each
+    ";
+    let assembly = assemble_text(source).unwrap();
+    assert_eq!(assembly.blocks[0].bytes, vec![
+        0xE0, 0x51,
+        0xF2, 0xF2, 0x06, 0x00,
+        0x05, 0xFF, 0xFF, 0x00,
+        0x0D, 0xFA,
+    ]);
+
+    // Without a symbol of that name it's a typo, hex in a source starts with a digit
+    assert_eq!(assemble_text("mov r1, #deadh\n"), Err(AsmError::UndefinedSymbol { line: 1, name: "deadh".to_string() }));
+    assert_eq!(assemble_text("mov r1, #0DEADh\n").unwrap().blocks[0].bytes, vec![0xE6, 0xF1, 0xAD, 0xDE]);
+
+    // r2 lists it without one
+    assert_eq!(assemble_listing_at("mov r1, #dach\n", 0).unwrap().blocks[0].bytes, vec![0xE6, 0xF1, 0xAC, 0x0D]);
+    assert_eq!(assemble_listing_at("dach equ 5\nmov r1, #dach\n", 0).unwrap().blocks[0].bytes, vec![0xE0, 0x51]);
+}
+
+#[test]
+fn branches_stay_in_their_segment() {
+    let source = "
+        jmpa cc_UC, far
+        jmps SEG far, far
+        org 20000h
+far:    ret
+    ";
+    match assemble_text(source) {
+        Err(AsmError::Encode { line: 2, error: EncodeError::UnreachableTarget { opcode: 0xEA, target: 0x20000, pc: 0 } }) => {},
+        other => assert!(false, "Expected jmpa to be out of reach, got: {:?}", other)
+    }
+
+    match assemble_text("jmpr cc_UC, far\norg 10000h\nfar: ret\n") {
+        Err(AsmError::Encode { line: 1, error: EncodeError::UnreachableTarget { opcode: 0x0D, target: 0x10000, pc: 0 } }) => {},
+        other => assert!(false, "Expected jmpr to be out of reach, got: {:?}", other)
+    }
+
+    let assembly = assemble_text("jmps SEG far, far\norg 20000h\nfar: ret\n").unwrap();
+    assert_eq!(assembly.blocks[0].bytes, vec![0xFA, 0x02, 0x00, 0x00]);
+
+    // A number on its own is an offset into pc's segment
+    assert_eq!(assemble_at("calla cc_UC, 1234h\n", 0x12000).unwrap().image(0), vec![0xCA, 0x00, 0x34, 0x12]);
+    assert_eq!(assemble_listing_at("jmpa cc_UC, F757h\n", 0x12000).unwrap().image(0), vec![0xEA, 0x00, 0x57, 0xF7]);
+    assert_eq!(assemble_at("pcall r1, 1234h\n", 0x12000).unwrap().image(0), vec![0xE2, 0xF1, 0x34, 0x12]);
+    assert_eq!(assemble_at("jmpr cc_UC, 2000h\n", 0x12004).unwrap().image(0), vec![0x0D, 0xFD]);
+
+    // Unless it's too big for one
+    match assemble_at("calla cc_UC, 21234h\n", 0x12000) {
+        Err(AsmError::Encode { line: 1, error: EncodeError::UnreachableTarget { opcode: 0xCA, target: 0x21234, pc: 0x12000 } }) => {},
+        other => assert!(false, "Expected calla to be out of reach, got: {:?}", other)
+    }
+
+    // A label in the first segment is still in another one
+    match assemble_at("low: nop\norg 12000h\njmpa cc_UC, low\n", 0x1000) {
+        Err(AsmError::Encode { line: 3, error: EncodeError::UnreachableTarget { opcode: 0xEA, target: 0x1000, pc: 0x12000 } }) => {},
+        other => assert!(false, "Expected jmpa to be out of reach, got: {:?}", other)
+    }
+}
+
+#[test]
+fn given_segments() {
+    // A segment written as a number is that segment, hex the way r2 lists it included
+    assert_eq!(assemble_listing_at("calls E5h, 79B4h\n", 0).unwrap().image(0), vec![0xDA, 0xE5, 0xB4, 0x79]);
+    assert_eq!(assemble_text("jmps 0F9h, 2C4Ch\n").unwrap().image(0), vec![0xFA, 0xF9, 0x4C, 0x2C]);
+    assert_eq!(assemble_text("calls SEG 0E50000h, 0\n").unwrap().image(0), vec![0xDA, 0xE5, 0x00, 0x00]);
+
+    match assemble_text("calls 100h, 0\n") {
+        Err(AsmError::Encode { line: 1, error: EncodeError::OutOfRange { opcode: 0xDA, operand: 1, value: 0x100, max: 0xFF, .. } }) => {},
+        other => assert!(false, "Expected the segment to be out of range, got: {:?}", other)
+    }
+
+    match assemble_text("calls (1 + 100h), 0\n") {
+        Err(AsmError::OutOfRange { line: 1, value: 0x101, min: 0, max: 0xFF }) => {},
+        other => assert!(false, "Expected the segment to be out of range, got: {:?}", other)
+    }
+}
+
+#[test]
+fn disassembly_round_trip() {
+    // What r2 lists anywhere in memory assembles back where it came from
+    let listed = [
+        (vec![0xDA, 0xE5, 0xB4, 0x79], 0x12000),
+        (vec![0xFA, 0xF9, 0x4C, 0x2C], 0x3A000),
+        (vec![0xCA, 0xB0, 0x57, 0xF7], 0x3A000),
+        (vec![0x0D, 0xC4], 0x1FFC0)
+    ];
+
+    for &(ref bytes, address) in listed.iter() {
+        let text = format!("{}\n", decode(bytes, address).unwrap());
+        assert_eq!(&assemble_listing_at(&text, address).unwrap().image(0), bytes, "{}", text);
+    }
+}
+
+#[test]
+fn data_in_first_64k() {
+    // Data goes through a DPP, an address past 64K needs SOF or POF to say which part of it is meant
+    match assemble_text("mov r0, far\norg 30000h\nfar: dw 0\n") {
+        Err(AsmError::Encode { line: 1, error: EncodeError::OutOfRange { opcode: 0xF2, operand: 2, value: 0x30000, max: 0xFFFF, .. } }) => {},
+        other => assert!(false, "Expected far to be out of range, got: {:?}", other)
+    }
+
+    let assembly = assemble_text("mov r0, POF far\norg 30000h\nfar: dw 0\n").unwrap();
+    assert_eq!(assembly.blocks[0].bytes, vec![0xF2, 0xF0, 0x00, 0x00]);
+}
+
+
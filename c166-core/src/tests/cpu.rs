@@ -0,0 +1,113 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::str::FromStr;
+
+use ::cpu::*;
+use ::decoder::*;
+use ::error::*;
+use ::instruction::*;
+use ::parser::*;
+use ::reg::*;
+
+fn assemble_for(line: &str, cpu: CpuModel) -> Result<Vec<u8>, EncodeError> {
+    let mut op_lut: OpLookUpTable = OpLookUpTable::new();
+    build_lut(&mut op_lut);
+
+    let (_remainder, ops) = asm_lines(line).unwrap();
+    operation_to_bytes_for_cpu(&ops[0], &op_lut, cpu)
+}
+
+#[test]
+fn names() {
+    for cpu in CpuModel::ALL.iter() {
+        assert_eq!(CpuModel::from_str(cpu.name()), Ok(*cpu));
+    }
+
+    assert_eq!(CpuModel::from_str("C167"), Ok(CpuModel::C167CR));
+    assert_eq!(CpuModel::from_str("c164"), Err(CpuError::UnknownCpu("c164".to_string())));
+    assert_eq!(CpuModel::default(), CpuModel::C167CR);
+}
+
+#[test]
+fn ext_sequences_need_a_c167() {
+    // extp #3h, #1
+    let bytes: &[u8] = &[0xD7, 0x40, 0x03, 0x00];
+
+    assert!(decode_for_cpu(bytes, 0, CpuModel::C167CR).is_ok());
+    assert_eq!(decode_for_cpu(bytes, 0, CpuModel::C166).unwrap_err(), DecodeError::UnsupportedInstruction { opcode: 0xD7, cpu: CpuModel::C166 });
+
+    assert!(assemble_for("atomic #2\0", CpuModel::ST10).is_ok());
+    assert_eq!(assemble_for("atomic #2\0", CpuModel::C166), Err(EncodeError::UnsupportedInstruction { mnemonic: "atomic".to_string(), cpu: CpuModel::C166 }));
+}
+
+#[test]
+fn c166_has_no_esfrs() {
+    assert!(CpuModel::C165.has_reg(&Reg::ODP2));
+    assert!(!CpuModel::C166.has_reg(&Reg::ODP2));
+    assert!(!CpuModel::C166.has_reg(&Reg::ESFR(0x10)));
+    assert!(CpuModel::C166.has_reg(&Reg::DPP0));
+
+    let mut op_lut: OpLookUpTable = OpLookUpTable::new();
+    build_lut(&mut op_lut);
+    let asm = AsmOperation { mnem: "mov", operands: vec![Operand::Register(Reg::ODP2), Operand::Register(Reg::R1)] };
+
+    match operation_to_bytes_for_cpu(&asm, &op_lut, CpuModel::C166) {
+        Err(EncodeError::UnsupportedRegister { operand: 1, reg: Reg::ODP2, cpu: CpuModel::C166, .. }) => {},
+        other => assert!(false, "Expected UnsupportedRegister, got: {:?}", other)
+    }
+}
+
+#[test]
+fn c166_has_no_c167_sfrs() {
+    assert!(!CpuModel::C166.has_reg(&Reg::PWMCON0));
+    assert!(!CpuModel::C166.has_reg(&Reg::CC16));
+    assert!(CpuModel::C166.has_reg(&Reg::CC15));
+    assert!(CpuModel::C166.has_reg(&Reg::SFR(0x98)));
+
+    assert!(assemble_for("mov PWMCON0, #1234h\0", CpuModel::C167CR).is_ok());
+    assert_eq!(assemble_for("mov PWMCON0, #1234h\0", CpuModel::C166), Err(EncodeError::UnsupportedRegister { mnemonic: "mov".to_string(), operand: 1, reg: Reg::PWMCON0, cpu: CpuModel::C166 }));
+
+    // The 80C166 has SYSCON where the C167CR has BUSCON0
+    assert_eq!(assemble_for("mov SYSCON, #1234h\0", CpuModel::C166), Ok(vec![0xE6, 0x86, 0x34, 0x12]));
+    assert_eq!(assemble_for("mov BUSCON0, #1234h\0", CpuModel::C167CR), Ok(vec![0xE6, 0x86, 0x34, 0x12]));
+
+    // mov PWMCON0, #0001h
+    let bytes: &[u8] = &[0xE6, 0x98, 0x01, 0x00];
+    assert_eq!(format!("{}", decode_for_cpu(bytes, 0, CpuModel::C167CR).unwrap()), "mov PWMCON0, #0001h");
+    assert_eq!(format!("{}", decode_for_cpu(bytes, 0, CpuModel::C166).unwrap()), "mov FF30h, #0001h");
+}
+
+#[test]
+fn decode_all_uses_the_cpu() {
+    // nop, extr #1, nop
+    let bytes: &[u8] = &[0xCC, 0x00, 0xD1, 0x80, 0xCC, 0x00];
+    let results: Vec<bool> = decode_all_for_cpu(bytes, 0, CpuModel::C166).map(|result| result.is_ok()).collect();
+    assert_eq!(results, vec![true, false, true]);
+}
+
+#[test]
+fn mac_needs_an_st10_or_xc16x() {
+    // comul r1, r2
+    let bytes: &[u8] = &[0xA3, 0x12, 0x00, 0x00];
+
+    assert!(decode_for_cpu(bytes, 0, CpuModel::ST10).is_ok());
+    assert_eq!(decode_for_cpu(bytes, 0, CpuModel::C167CR).unwrap_err(), DecodeError::UnsupportedInstruction { opcode: 0xA3, cpu: CpuModel::C167CR });
+
+    assert!(assemble_for("mov MSW, #1234h\0", CpuModel::XC16x).is_ok());
+    assert_eq!(assemble_for("mov MSW, #1234h\0", CpuModel::C167CR), Err(EncodeError::UnsupportedRegister { mnemonic: "mov".to_string(), operand: 1, reg: Reg::MSW, cpu: CpuModel::C167CR }));
+}
@@ -56,3 +56,10 @@ fn op_6e() {
 fn op_7e() {
     test_disasm_op!([0x7E, 0xEA], "bclr P8.7");
 }
+
+#[test]
+fn named_bit() {
+    test_disasm_op!([0xBE, 0x88], "bclr IEN");
+    // PSW.7 has no name
+    test_disasm_op!([0x7E, 0x88], "bclr PSW.7");
+}
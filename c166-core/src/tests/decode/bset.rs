@@ -56,3 +56,9 @@ fn op_6f() {
 fn op_7f() {
     test_disasm_op!([0x7F, 0xEA], "bset P8.7");
 }
+
+#[test]
+fn named_bit() {
+    test_disasm_op!([0xBF, 0x88], "bset IEN");
+    test_disasm_op!([0x6F, 0xA1], "bset T3R");
+}
@@ -19,5 +19,5 @@ test_requires_decode!();
 
 #[test]
 fn op_bb() {
-    test_disasm_op!([0xBB, 0x03], "callr 0006h");
+    test_disasm_op!([0xBB, 0x03], "callr 0008h");
 }
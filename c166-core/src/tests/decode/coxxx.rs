@@ -0,0 +1,104 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+test_requires_decode!();
+
+use ::cpu::CpuModel;
+
+#[test]
+fn op_a3_1() {
+    test_disasm_op!(CpuModel::XC16x, [0xA3, 0x12, 0x14, 0x00], "comac- r1, r2");
+}
+
+#[test]
+fn op_a3_2() {
+    test_disasm_op!(CpuModel::XC16x, [0xA3, 0x0F, 0x52, 0x00], "coashr #0Fh");
+}
+
+#[test]
+fn op_a3_3() {
+    test_disasm_op!(CpuModel::XC16x, [0xA3, 0x00, 0x60, 0x00], "conop");
+}
+
+#[test]
+#[should_panic(expected = "Instruction was invalid")]
+fn op_a3_4() {
+    // Shifts only take #data4, the upper nibble must be clear
+    test_disasm_op!(CpuModel::XC16x, [0xA3, 0x13, 0x50, 0x00], "invalid");
+}
+
+#[test]
+#[should_panic(expected = "Instruction was invalid")]
+fn op_a3_5() {
+    test_disasm_op!(CpuModel::XC16x, [0xA3, 0x12, 0xFF, 0x00], "invalid");
+}
+
+#[test]
+fn op_83_1() {
+    test_disasm_op!(CpuModel::ST10, [0x83, 0x34, 0x38, 0x02], "coload r3, [r4-]");
+}
+
+#[test]
+fn op_83_2() {
+    test_disasm_op!(CpuModel::ST10, [0x83, 0x34, 0x38, 0x05], "coload r3, [r4+QR1]");
+}
+
+#[test]
+#[should_panic(expected = "Instruction was invalid")]
+fn op_83_3() {
+    // coneg doesn't take any operands
+    test_disasm_op!(CpuModel::ST10, [0x83, 0x34, 0x61, 0x00], "invalid");
+}
+
+#[test]
+#[should_panic(expected = "Instruction was invalid")]
+fn op_83_4() {
+    test_disasm_op!(CpuModel::ST10, [0x83, 0x34, 0x38, 0x07], "invalid");
+}
+
+#[test]
+fn op_93_1() {
+    test_disasm_op!(CpuModel::XC16x, [0x93, 0x0F, 0x40, 0x62], "cocmp [IDX0-QX1], [r15-]");
+}
+
+#[test]
+#[should_panic(expected = "Instruction was invalid")]
+fn op_93_2() {
+    // There are only two index pointers
+    test_disasm_op!(CpuModel::XC16x, [0x93, 0x2F, 0x40, 0x00], "invalid");
+}
+
+#[test]
+fn op_b3() {
+    test_disasm_op!(CpuModel::XC16x, [0xB3, 0xF5, 0x00, 0x00], "costore r15, MCW");
+}
+
+#[test]
+fn op_c3() {
+    test_disasm_op!(CpuModel::XC16x, [0xC3, 0x41, 0x00, 0x40], "costore [r4-QR0], MAH");
+}
+
+#[test]
+fn op_d3() {
+    test_disasm_op!(CpuModel::XC16x, [0xD3, 0x1A, 0x00, 0x00], "comov [IDX1], [r10]");
+}
+
+#[test]
+fn mac_op_type() {
+    let decoded = decode_for_cpu(&[0x83, 0x34, 0x32, 0x01], 0x0000, CpuModel::XC16x).unwrap();
+    assert_eq!(decoded.mnemonic, "cosub");
+    assert_eq!(decoded.instruction.op_type.kind, ::instruction::OpKind::Sub);
+}
@@ -2,7 +2,7 @@ test_requires_decode!();
 
 #[test]
 fn op_aa_1() {
-    test_disasm_op!([0xAA, 0xF5, 0x0A, 0xF0], "jbc r5.15, 0014h");
+    test_disasm_op!([0xAA, 0xF5, 0x0A, 0xF0], "jbc r5.15, 0018h");
 }
 
 #[test]
@@ -19,10 +19,10 @@ test_requires_decode!();
 
 #[test]
 fn op_0d() {
-    test_disasm_op!([0x0D, 0x38], "jmpr cc_UC, 0070h");
+    test_disasm_op!([0x0D, 0x38], "jmpr cc_UC, 0072h");
 }
 
 #[test]
 fn op_5d() {
-    test_disasm_op!([0x5D, 0x38], "jmpr cc_NV, 0070h");
+    test_disasm_op!([0x5D, 0x38], "jmpr cc_NV, 0072h");
 }
@@ -43,6 +43,7 @@ pub mod cmpd1;
 pub mod cmpd2;
 pub mod cmpi1;
 pub mod cmpi2;
+pub mod coxxx;
 pub mod cpl;
 pub mod cplb;
 pub mod diswdt;
@@ -0,0 +1,301 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use ::cpu::CpuModel;
+use ::decoder::*;
+use ::error::*;
+use ::instruction::Operand;
+use ::parser::*;
+
+#[test]
+fn decoded_fields() {
+    // calls 01h, 2345h
+    let decoded = decode(&[0xDA, 0x01, 0x45, 0x23, 0xFF], 0x10000).unwrap();
+
+    assert_eq!(decoded.addr, 0x10000);
+    assert_eq!(decoded.length, 4);
+    assert_eq!(decoded.bytes, vec![0xDA, 0x01, 0x45, 0x23]);
+    assert_eq!(decoded.mnemonic, "calls");
+    assert_eq!(decoded.operands.len(), 2);
+    assert_eq!(decoded.flow, FlowKind::Call);
+    assert_eq!(decoded.targets, vec![0x12345]);
+}
+
+#[test]
+fn sub_op_mnemonic() {
+    let decoded = decode(&[0xD7, 0x50, 0x03, 0x00], 0).unwrap();
+    assert_eq!(decoded.mnemonic, "extp");
+}
+
+#[test]
+fn typed_operands() {
+    // calls 01h, 2345h
+    let decoded = decode(&[0xDA, 0x01, 0x45, 0x23], 0).unwrap();
+    match (decoded.arguments.op1, decoded.arguments.op2) {
+        (Some(Operand::Segment8(0x01)), Some(Operand::CodeAddr16(0x2345))) => {},
+        other => assert!(false, "Expected a segment and a code address, got: {:?}", other)
+    }
+
+    // jmpr cc_UC, -2 words
+    let decoded = decode(&[0x0D, 0xFE], 0).unwrap();
+    match decoded.arguments.op2 {
+        Some(Operand::Rel8(-2)) => {},
+        other => assert!(false, "Expected a relative offset, got: {:?}", other)
+    }
+
+    // extp #0203h, #1
+    let decoded = decode(&[0xD7, 0x40, 0x03, 0x02], 0).unwrap();
+    match decoded.arguments.op1 {
+        Some(Operand::Page10(0x0203)) => {},
+        other => assert!(false, "Expected a page, got: {:?}", other)
+    }
+
+    // pcall R1, 1234h
+    let decoded = decode(&[0xE2, 0xF1, 0x34, 0x12], 0).unwrap();
+    match decoded.arguments.op2 {
+        Some(Operand::CodeAddr16(0x1234)) => {},
+        other => assert!(false, "Expected a code address, got: {:?}", other)
+    }
+}
+
+#[test]
+fn relative_targets() {
+    // jmpr cc_UC, -2 words loops back onto itself
+    let decoded = decode(&[0x0D, 0xFF], 0x0200).unwrap();
+    assert_eq!(decoded.targets, vec![0x0200]);
+
+    let decoded = decode(&[0x0D, 0x02], 0x0200).unwrap();
+    assert_eq!(decoded.targets, vec![0x0206]);
+}
+
+#[test]
+fn relative_operands() {
+    // Shown as the IP they go to, backwards too
+    let decoded = decode(&[0x0D, 0xFE], 0x0200).unwrap();
+    assert_eq!(format!("{}", decoded), "jmpr cc_UC, 01FEh");
+
+    // jb r5.15, +5 words
+    let decoded = decode(&[0x8A, 0xF5, 0x05, 0xF0], 0x0100).unwrap();
+    assert_eq!(decoded.targets, vec![0x010E]);
+    assert_eq!(format!("{}", decoded), "jb r5.15, 010Eh");
+
+    // IP wraps around within the segment
+    let decoded = decode(&[0x0D, 0x02], 0x1FFFE).unwrap();
+    assert_eq!(decoded.targets, vec![0x10004]);
+
+    // And the targets move with the instruction
+    assert_eq!(decoded.branch_targets(0x30000), vec![0x30006]);
+}
+
+#[test]
+fn relative_round_trip() {
+    let mut op_lut: OpLookUpTable = OpLookUpTable::new();
+    build_lut(&mut op_lut);
+
+    for (bytes, addr) in [(vec![0x0D, 0xFE], 0x0200), (vec![0x3D, 0x7F], 0x1000), (vec![0xBB, 0x80], 0x1000), (vec![0x9A, 0xF2, 0x81, 0xF0], 0x0400)].iter() {
+        let text = format!("{}\0", decode(bytes, *addr).unwrap());
+        let (_remainder, ops) = asm_lines(&text).unwrap();
+        let context = AsmContext { pc: *addr, bank: None, segments: [None; 3] };
+        assert_eq!(&operation_to_bytes_in_context(&ops[0], &op_lut, CpuModel::C167CR, &context).unwrap(), bytes, "{}", text);
+    }
+}
+
+#[test]
+fn indirect_flow() {
+    // jmpi cc_UC, [r3] and calli cc_UC, [r3]
+    for bytes in [[0x9C, 0x03], [0xAB, 0x03]].iter() {
+        let decoded = decode(bytes, 0).unwrap();
+        assert_eq!(decoded.flow, FlowKind::Indirect);
+        assert!(decoded.targets.is_empty());
+    }
+
+    // pcall takes a register but isn't indirect
+    let decoded = decode(&[0xE2, 0xF1, 0x34, 0x12], 0).unwrap();
+    assert_eq!(decoded.flow, FlowKind::Call);
+}
+
+#[test]
+fn segment_relative_targets() {
+    // jmpa cc_UC, 1234h stays in the current code segment
+    let decoded = decode(&[0xEA, 0x00, 0x34, 0x12], 0x30000).unwrap();
+    assert_eq!(decoded.flow, FlowKind::ConditionalJump);
+    assert_eq!(decoded.targets, vec![0x31234]);
+}
+
+#[test]
+fn trap_targets() {
+    let decoded = decode(&[0x9B, 0x54], 0).unwrap();
+    assert_eq!(decoded.flow, FlowKind::Trap);
+    assert_eq!(decoded.targets, vec![0xA8]);
+}
+
+#[test]
+fn no_targets() {
+    let decoded = decode(&[0xCC, 0x00], 0).unwrap();
+    assert_eq!(decoded.flow, FlowKind::Fallthrough);
+    assert!(decoded.targets.is_empty());
+}
+
+#[test]
+fn walk_buffer() {
+    // nop, mov r1, #1234h, <unknown>, ret, then half of a mov
+    let bytes: &[u8] = &[0xCC, 0x00, 0xE6, 0xF1, 0x34, 0x12, 0x8B, 0x00, 0xCB, 0x00, 0xE6, 0xF1];
+    let results: Vec<Result<DecodedInstruction, DecodeError>> = decode_all(bytes, 0x100).collect();
+
+    assert_eq!(results.len(), 5);
+    assert_eq!(results[0].as_ref().unwrap().mnemonic, "nop");
+    assert_eq!(results[1].as_ref().unwrap().addr, 0x102);
+    assert_eq!(format!("{}", results[1].as_ref().unwrap()), "mov r1, #1234h");
+    assert_eq!(results[2].as_ref().unwrap_err(), &DecodeError::UnknownOpcode { opcode: 0x8B });
+    assert_eq!(results[3].as_ref().unwrap().addr, 0x108);
+    assert_eq!(results[4].as_ref().unwrap_err(), &DecodeError::Incomplete { needed: 2 });
+}
+
+fn disassemble(bytes: &[u8]) -> Vec<String> {
+    decode_all(bytes, 0).map(|result| format!("{}", result.unwrap())).collect()
+}
+
+#[test]
+fn extr_window() {
+    // extr #1, mov DP0L, #1, mov P0L, #1
+    let bytes: &[u8] = &[0xD1, 0x80, 0xE6, 0x80, 0x01, 0x00, 0xE6, 0x80, 0x01, 0x00];
+    assert_eq!(disassemble(bytes), vec!["extr #1", "mov DP0L, #0001h", "mov P0L, #0001h"]);
+
+    // extr #2, bset DP0L.11, mov r1, r2 (GPRs aren't affected)
+    let bytes: &[u8] = &[0xD1, 0x90, 0xBF, 0x80, 0xF0, 0x12];
+    assert_eq!(disassemble(bytes), vec!["extr #2", "bset DP0L.11", "mov r1, r2"]);
+}
+
+#[test]
+fn extp_window() {
+    // extp #1, #2, mov r1, 1234h, mov r1, 1234h, mov r1, 1234h
+    let bytes: &[u8] = &[0xD7, 0x50, 0x01, 0x00, 0xF2, 0xF1, 0x34, 0x12, 0xF2, 0xF1, 0x34, 0x12, 0xF2, 0xF1, 0x34, 0x12];
+    let mem_addrs: Vec<Option<u32>> = decode_all(bytes, 0).map(|result| result.unwrap().mem_addr).collect();
+    // The last one is back to DPP0, which still holds page 0
+    assert_eq!(mem_addrs, vec![None, Some(0x5234), Some(0x5234), Some(0x1234)]);
+
+    // extsr #2, #1, mov DP0L, 1234h
+    let bytes: &[u8] = &[0xD7, 0x80, 0x02, 0x00, 0xF2, 0x80, 0x34, 0x12];
+    let decoded: Vec<DecodedInstruction> = decode_all(bytes, 0).map(|result| result.unwrap()).collect();
+    assert_eq!(format!("{}", decoded[1]), "mov DP0L, 1234h");
+    assert_eq!(decoded[1].mem_addr, Some(0x21234));
+
+    // extp r4, #1, the page isn't known
+    let bytes: &[u8] = &[0xDC, 0x44, 0xF2, 0xF1, 0x34, 0x12];
+    let decoded: Vec<DecodedInstruction> = decode_all(bytes, 0).map(|result| result.unwrap()).collect();
+    assert_eq!(decoded[1].mem_addr, None);
+}
+
+#[test]
+fn reserved_fields() {
+    // movb PWMCON0, #24h as the encoder writes it
+    let decoded = decode(&[0xE7, 0x98, 0x24, 0x42], 0).unwrap();
+    assert_eq!(decoded.reserved, None);
+
+    // addb DPP3, #56h with something else in the nop8 byte
+    let decoded = decode(&[0x07, 0x03, 0x56, 0x78], 0).unwrap();
+    assert_eq!(format!("{}", decoded), "addb DPP3, #56h");
+    match decoded.reserved {
+        Some(DecodeError::InvalidEncoding { opcode: 0x07, offset: 3, .. }) => {},
+        other => assert!(false, "Expected the nop8 byte to be flagged, got: {:?}", other)
+    }
+
+    let mut context = DecodeContext::with_mode(DecodeMode::Strict);
+    match context.decode(&[0x07, 0x03, 0x56, 0x78], 0, CpuModel::C167CR) {
+        Err(DecodeError::InvalidEncoding { opcode: 0x07, offset: 3, .. }) => {},
+        other => assert!(false, "Expected InvalidEncoding, got: {:?}", other)
+    }
+
+    // trap #3Dh with the spare low bit set
+    let results: Vec<Result<DecodedInstruction, DecodeError>> = decode_all_in_mode(&[0x9B, 0x7B, 0x9B, 0x7A], 0, CpuModel::C167CR, DecodeMode::Strict).collect();
+    match results[0] {
+        Err(DecodeError::InvalidEncoding { opcode: 0x9B, offset: 1, .. }) => {},
+        ref other => assert!(false, "Expected InvalidEncoding, got: {:?}", other)
+    }
+    assert_eq!(format!("{}", results[1].as_ref().unwrap()), "trap #3Dh");
+
+    // The undefined atomic/extr sub-ops
+    assert!(decode(&[0xD1, 0x40], 0).unwrap().reserved.is_some());
+    assert!(DecodeContext::with_mode(DecodeMode::Strict).decode(&[0xD1, 0xC0], 0, CpuModel::C167CR).is_err());
+}
+
+#[test]
+fn context_follows_addresses() {
+    let mut context = DecodeContext::new();
+
+    // extr #1 at 100h
+    context.decode(&[0xD1, 0x80], 0x100, CpuModel::C167CR).unwrap();
+    assert!(context.is_extended());
+    assert_eq!(context.next_addr, Some(0x102));
+
+    // Anywhere other than 102h is somewhere else entirely
+    let decoded = context.decode(&[0xE6, 0x80, 0x01, 0x00], 0x200, CpuModel::C167CR).unwrap();
+    assert_eq!(format!("{}", decoded), "mov P0L, #0001h");
+    assert!(!context.is_extended());
+}
+
+#[test]
+fn dpp_loads() {
+    // mov DPP0, #8, mov r1, 1234h, pop DPP0, mov r1, 1234h
+    let bytes: &[u8] = &[0xE6, 0x00, 0x08, 0x00, 0xF2, 0xF1, 0x34, 0x12, 0xFC, 0x00, 0xF2, 0xF1, 0x34, 0x12];
+    let decoded: Vec<DecodedInstruction> = decode_all(bytes, 0).map(|result| result.unwrap()).collect();
+    assert_eq!(format!("{}", decoded[2]), "pop DPP0");
+    assert_eq!(decoded[1].mem_addr, Some(0x21234));
+    assert_eq!(decoded[3].mem_addr, None);
+
+    // Straight on the load holds, anywhere else may have been reached with other DPPs
+    let mut context = DecodeContext::new();
+    context.decode(&[0xE6, 0x01, 0x08, 0x00], 0x100, CpuModel::C167CR).unwrap();
+    let decoded = context.decode(&[0xF2, 0xF1, 0x34, 0x52], 0x104, CpuModel::C167CR).unwrap();
+    assert_eq!(decoded.mem_addr, Some(0x21234));
+    let decoded = context.decode(&[0xF2, 0xF1, 0x34, 0x52], 0x300, CpuModel::C167CR).unwrap();
+    assert_eq!(decoded.mem_addr, None);
+    assert_eq!(context.memory.dpp, [None; 4]);
+
+    // Until a DPP is loaded again, the others stay unknown
+    context.decode(&[0xE6, 0x01, 0x09, 0x00], 0x304, CpuModel::C167CR).unwrap();
+    let decoded = context.decode(&[0xF2, 0xF1, 0x34, 0x52], 0x308, CpuModel::C167CR).unwrap();
+    assert_eq!(decoded.mem_addr, Some(0x25234));
+    let decoded = context.decode(&[0xF2, 0xF1, 0x34, 0x12], 0x30C, CpuModel::C167CR).unwrap();
+    assert_eq!(decoded.mem_addr, None);
+}
+
+#[test]
+fn seeks_forget_cp() {
+    let mut context = DecodeContext::new();
+
+    // mov CP, #F600h at 100h, then mov r1, F606h at 104h and 300h
+    context.decode(&[0xE6, 0x08, 0x00, 0xF6], 0x100, CpuModel::C167CR).unwrap();
+    let decoded = context.decode(&[0xF2, 0xF1, 0x06, 0xF6], 0x104, CpuModel::C167CR).unwrap();
+    assert_eq!(format!("{}", decoded), "mov r1, r3");
+    let decoded = context.decode(&[0xF2, 0xF1, 0x06, 0xF6], 0x300, CpuModel::C167CR).unwrap();
+    assert_eq!(format!("{}", decoded), "mov r1, F606h");
+    assert_eq!(context.memory.cp, None);
+}
+
+#[test]
+fn cp_loads() {
+    // mov r1, F606h, mov CP, #F600h, mov r1, F606h, movbz r1, F607h, mov r1, F640h
+    let bytes: &[u8] = &[0xF2, 0xF1, 0x06, 0xF6, 0xE6, 0x08, 0x00, 0xF6, 0xF2, 0xF1, 0x06, 0xF6,
+                         0xC2, 0xF1, 0x07, 0xF6, 0xF2, 0xF1, 0x40, 0xF6];
+    let decoded: Vec<DecodedInstruction> = decode_all(bytes, 0).map(|result| result.unwrap()).collect();
+    let formatted: Vec<String> = decoded.iter().map(|decoded| format!("{}", decoded)).collect();
+    assert_eq!(formatted, vec!["mov r1, F606h", "mov CP, #F600h", "mov r1, r3", "movbz r1, rh3", "mov r1, F640h"]);
+
+    // The analysis still gets to see where the GPR lives
+    assert_eq!(decoded[2].mem_addr, Some(0xF606));
+}
@@ -0,0 +1,91 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use ::assembler::*;
+use ::cpu::CpuModel;
+use ::diagnostic::*;
+use ::error::*;
+
+fn diagnose(source: &str) -> Vec<Diagnostic> {
+    assemble_source(source, CpuModel::default()).unwrap_err()
+}
+
+fn span(line: usize, column: usize, length: usize) -> Option<Span> {
+    Some(Span { line: line, column: column, length: length })
+}
+
+#[test]
+fn every_error_in_one_run() {
+    let source = "
+start:  frob r2
+        ret r1
+        mov r1, #10000h + 0
+        mov r1, @x
+start:  jmpr cc_UC, nowhere
+        db 1, 2, 300
+    ";
+    let diagnostics = diagnose(source);
+    let found: Vec<(Option<usize>, Option<Span>)> = diagnostics.iter().map(|d| (d.error.line(), d.span)).collect();
+    assert_eq!(found, vec![
+        (Some(2), span(2, 9, 4)),
+        (Some(3), span(3, 9, 6)),
+        (Some(4), span(4, 17, 11)),
+        (Some(5), span(5, 17, 2)),
+        (Some(6), span(6, 1, 5)),
+        (Some(6), span(6, 21, 7)),
+        (Some(7), span(7, 18, 3)),
+    ]);
+
+    assert_eq!(diagnostics[0].error, AsmError::Encode { line: 2, error: EncodeError::UnknownMnemonic { mnemonic: "frob".to_string() } });
+    assert_eq!(diagnostics[1].forms, vec!["ret"]);
+    assert_eq!(diagnostics[2].error, AsmError::OutOfRange { line: 4, value: 0x10000, min: -0x8000, max: 0xFFFF });
+    assert_eq!(diagnostics[3].error, AsmError::Syntax { line: 5 });
+    assert_eq!(diagnostics[4].error, AsmError::DuplicateSymbol { line: 6, name: "start".to_string() });
+    assert_eq!(diagnostics[5].error, AsmError::UndefinedSymbol { line: 6, name: "nowhere".to_string() });
+}
+
+#[test]
+fn lines_after_a_syntax_error_still_count() {
+    let source = "
+        mov r1, @x
+        jmpr cc_UC, done
+done:   ret
+    ";
+    assert_eq!(diagnose(source), vec![Diagnostic::new(AsmError::Syntax { line: 2 }, span(2, 17, 2))]);
+}
+
+#[test]
+fn rendered() {
+    let source = "\tmov r1, #1\n\tshl r1, #20\n";
+    assert_eq!(render(&diagnose(source), source), concat!(
+        "Line 2, column 2: No encoding of \"shl\" takes these operands\n",
+        "    \tshl r1, #20\n",
+        "    \t^^^^^^^^^^^\n",
+        "    valid forms:\n",
+        "        shl Rw, Rw\n",
+        "        shl Rw, #data4\n",
+    ));
+
+    let diagnostic = Diagnostic::new(AsmError::Unsettled { passes: 8 }, None);
+    assert_eq!(diagnostic.render(source), "Label addresses didn't settle after 8 passes\n");
+
+    let diagnostics = vec![
+        Diagnostic::new(AsmError::Misplaced { origin: 0x200, pc: 0x100 }, None),
+        Diagnostic::new(AsmError::TooLong { length: 0x140, max: 0x100 }, None),
+    ];
+    assert_eq!(render(&diagnostics, source), "Assembled to 200h rather than 100h\n140h bytes don't fit, max len is 100h\n");
+}
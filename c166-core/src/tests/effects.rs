@@ -0,0 +1,161 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use ::cpu::CpuModel;
+use ::decoder::*;
+use ::effects::*;
+use ::instruction::FlagEffect;
+use ::reg::Reg;
+
+fn effects_of(bytes: &[u8]) -> Effects {
+    decode(bytes, 0).unwrap().effects()
+}
+
+fn mac_effects_of(bytes: &[u8]) -> Effects {
+    decode_for_cpu(bytes, 0, CpuModel::ST10).unwrap().effects()
+}
+
+#[test]
+fn arithmetic() {
+    // add r1, r2
+    let effects = effects_of(&[0x00, 0x12]);
+    assert_eq!(effects.reads, vec![Reg::R1, Reg::R2]);
+    assert_eq!(effects.writes, vec![Reg::R1, Reg::PSW]);
+    assert_eq!(effects.flags, [FlagEffect::Computed; 5]);
+    assert!(effects.memory.is_empty());
+
+    // addc r1, r2 needs the carry
+    assert!(effects_of(&[0x10, 0x12]).reads_reg(Reg::PSW));
+
+    // div r2
+    let effects = effects_of(&[0x4B, 0x22]);
+    assert_eq!(effects.reads, vec![Reg::MDL, Reg::R2]);
+    assert_eq!(effects.writes, vec![Reg::MDH, Reg::MDL, Reg::PSW]);
+}
+
+#[test]
+fn flags() {
+    // mov r1, r2 leaves V and C alone
+    let effects = effects_of(&[0xF0, 0x12]);
+    assert_eq!(effects.flag(Flag::Z), FlagEffect::Computed);
+    assert_eq!(effects.flag(Flag::V), FlagEffect::Unchanged);
+    assert_eq!(effects.flag(Flag::C), FlagEffect::Unchanged);
+
+    // and r1, r2 clears V and C
+    let effects = effects_of(&[0x60, 0x12]);
+    assert_eq!(effects.flag(Flag::V), FlagEffect::Cleared);
+    assert_eq!(effects.flag(Flag::C), FlagEffect::Cleared);
+
+    // jmpr cc_Z only looks at them
+    let effects = effects_of(&[0x2D, 0x04]);
+    assert_eq!(effects.flags, [FlagEffect::Unchanged; 5]);
+    assert_eq!(effects.reads, vec![Reg::PSW]);
+    assert!(effects.writes.is_empty());
+}
+
+#[test]
+fn memory_accesses() {
+    // mov r1, [r2+]
+    let effects = effects_of(&[0x98, 0x12]);
+    assert_eq!(effects.reads, vec![Reg::R2]);
+    assert_eq!(effects.writes, vec![Reg::R1, Reg::R2, Reg::PSW]);
+    assert_eq!(effects.memory, vec![MemoryAccess { address: Address::Indirect(Reg::R2, 0), width: 2, access: Access::Read }]);
+
+    // movb [r4], rh2
+    let effects = effects_of(&[0xB9, 0x54]);
+    assert_eq!(effects.memory, vec![MemoryAccess { address: Address::Indirect(Reg::R4, 0), width: 1, access: Access::Write }]);
+
+    // add 1234h, r1 reads and writes memory
+    let effects = effects_of(&[0x04, 0xF1, 0x34, 0x12]);
+    assert_eq!(effects.memory, vec![
+        MemoryAccess { address: Address::Direct(0x1234), width: 2, access: Access::Read },
+        MemoryAccess { address: Address::Direct(0x1234), width: 2, access: Access::Write },
+    ]);
+
+    // jmpi cc_UC, [r3] goes to r3 rather than reading through it
+    let effects = effects_of(&[0x9C, 0x03]);
+    assert_eq!(effects.reads, vec![Reg::R3]);
+    assert!(effects.memory.is_empty());
+}
+
+#[test]
+fn bits() {
+    // bset T3R
+    let effects = effects_of(&[0x6F, 0xA1]);
+    assert_eq!(effects.reads, vec![Reg::T3CON]);
+    assert!(effects.writes_reg(Reg::T3CON));
+
+    // jb FD02h.0, ... reads bit-addressable RAM
+    let effects = effects_of(&[0x8A, 0x01, 0x04, 0x00]);
+    assert_eq!(effects.memory, vec![MemoryAccess { address: Address::Direct(0xFD02), width: 2, access: Access::Read }]);
+}
+
+#[test]
+fn stack() {
+    // push r1, the flags reflect the pushed word
+    let effects = effects_of(&[0xEC, 0xF1]);
+    assert_eq!(effects.reads, vec![Reg::R1, Reg::SP]);
+    assert_eq!(effects.writes, vec![Reg::SP, Reg::PSW]);
+    assert_eq!(effects.memory, vec![MemoryAccess { address: Address::Stack, width: 2, access: Access::Write }]);
+
+    // calls 01h, 2345h pushes CSP and IP
+    let effects = effects_of(&[0xDA, 0x01, 0x45, 0x23]);
+    assert!(effects.reads_reg(Reg::CSP));
+    assert!(effects.writes_reg(Reg::CSP));
+    assert_eq!(effects.memory.len(), 2);
+
+    // reti
+    let effects = effects_of(&[0xFB, 0x88]);
+    assert!(effects.writes_reg(Reg::PSW));
+    assert!(effects.memory.iter().all(|access| access.access == Access::Read));
+}
+
+#[test]
+fn mac() {
+    // comul r1, r2 replaces the accumulator
+    let effects = mac_effects_of(&[0xA3, 0x12, 0x00, 0x00]);
+    assert_eq!(effects.reads, vec![Reg::MCW, Reg::R1, Reg::R2]);
+    assert_eq!(effects.writes, vec![Reg::MAH, Reg::MAL, Reg::MSW]);
+
+    // coadd r1, r2 adds to it and leaves the GPRs alone
+    let effects = mac_effects_of(&[0xA3, 0x12, 0x30, 0x00]);
+    assert_eq!(effects.reads, vec![Reg::MAH, Reg::MAL, Reg::MCW, Reg::R1, Reg::R2]);
+    assert_eq!(effects.writes, vec![Reg::MAH, Reg::MAL, Reg::MSW]);
+
+    // coload r1, r2
+    let effects = mac_effects_of(&[0xA3, 0x12, 0x38, 0x00]);
+    assert!(!effects.reads_reg(Reg::MAH));
+    assert!(!effects.writes_reg(Reg::R1));
+    assert!(effects.writes_reg(Reg::MAL));
+
+    // cocmp r1, r2 only sets the flags
+    let effects = mac_effects_of(&[0xA3, 0x12, 0x40, 0x00]);
+    assert_eq!(effects.writes, vec![Reg::MSW]);
+
+    // coshl #03h and coneg work on the accumulator alone
+    for bytes in [[0xA3, 0x03, 0x50, 0x00], [0xA3, 0x00, 0x61, 0x00]].iter() {
+        let effects = mac_effects_of(bytes);
+        assert_eq!(effects.reads, vec![Reg::MAH, Reg::MAL, Reg::MCW]);
+        assert_eq!(effects.writes, vec![Reg::MAH, Reg::MAL, Reg::MSW]);
+    }
+
+    // comac r1, [r2+] moves the pointer too
+    let effects = mac_effects_of(&[0x83, 0x12, 0x10, 0x01]);
+    assert!(effects.writes_reg(Reg::R2));
+    assert!(effects.writes_reg(Reg::MAL));
+    assert!(!effects.writes_reg(Reg::R1));
+}
@@ -56,3 +56,9 @@ fn op_6e() {
 fn op_7e() {
     test_asm_op!("bclr P8.7", [0x7E, 0xEA]);
 }
+
+#[test]
+fn named_bit() {
+    test_asm_op!("bclr IEN", [0xBE, 0x88]);
+    test_asm_op!("bclr S0CON.S0REN", [0x4E, 0xD8]);
+}
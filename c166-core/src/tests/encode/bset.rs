@@ -56,3 +56,10 @@ fn op_6f() {
 fn op_7f() {
     test_asm_op!("bset P8.7", [0x7F, 0xEA]);
 }
+
+#[test]
+fn named_bit() {
+    test_asm_op!("bset IEN", [0xBF, 0x88]);
+    test_asm_op!("bset PSW.IEN", [0xBF, 0x88]);
+    test_asm_op!("bset t3r", [0x6F, 0xA1]);
+}
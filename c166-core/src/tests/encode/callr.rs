@@ -19,5 +19,5 @@ test_requires_encode!();
 
 #[test]
 fn op_bb() {
-    test_asm_op!("callr 0006h", [0xBB, 0x03]);
+    test_asm_op!("callr 0008h", [0xBB, 0x03]);
 }
@@ -0,0 +1,78 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+test_requires_encode!();
+
+use ::cpu::CpuModel;
+
+#[test]
+fn op_a3_1() {
+    test_asm_op!(CpuModel::XC16x, "comac- r1, r2", [0xA3, 0x12, 0x14, 0x00]);
+}
+
+#[test]
+fn op_a3_2() {
+    test_asm_op!(CpuModel::XC16x, "coashr #0Fh", [0xA3, 0x0F, 0x52, 0x00]);
+}
+
+#[test]
+fn op_a3_3() {
+    test_asm_op!(CpuModel::XC16x, "conop", [0xA3, 0x00, 0x60, 0x00]);
+}
+
+#[test]
+#[should_panic(expected = "The operation was unable to be encoded")]
+fn op_a3_4() {
+    // Shifts don't take registers
+    test_asm_op!(CpuModel::XC16x, "coshl r1, r2", [0x00]);
+}
+
+#[test]
+fn op_83_1() {
+    test_asm_op!(CpuModel::ST10, "coload r3, [r4-]", [0x83, 0x34, 0x38, 0x02]);
+}
+
+#[test]
+fn op_83_2() {
+    test_asm_op!(CpuModel::ST10, "coload r3, [r4+QR1]", [0x83, 0x34, 0x38, 0x05]);
+}
+
+#[test]
+#[should_panic(expected = "The operation was unable to be encoded")]
+fn op_83_3() {
+    // GPR pointers are offset by QRj, not QXj
+    test_asm_op!(CpuModel::ST10, "coload r3, [r4+QX1]", [0x00]);
+}
+
+#[test]
+fn op_93() {
+    test_asm_op!(CpuModel::XC16x, "cocmp [IDX0-QX1], [r15-]", [0x93, 0x0F, 0x40, 0x62]);
+}
+
+#[test]
+fn op_b3() {
+    test_asm_op!(CpuModel::XC16x, "costore r15, MCW", [0xB3, 0xF5, 0x00, 0x00]);
+}
+
+#[test]
+fn op_c3() {
+    test_asm_op!(CpuModel::XC16x, "costore [r4-QR0], MAH", [0xC3, 0x41, 0x00, 0x40]);
+}
+
+#[test]
+fn op_d3() {
+    test_asm_op!(CpuModel::XC16x, "comov [idx1], [r10]", [0xD3, 0x1A, 0x00, 0x00]);
+}
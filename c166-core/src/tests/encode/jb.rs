@@ -19,5 +19,5 @@ test_requires_encode!();
 
 #[test]
 fn op_8a_1() {
-    test_asm_op!("jb r5.15, 0014h", [0x8A, 0xF5, 0x0A, 0xF0]);
+    test_asm_op!("jb r5.15, 0018h", [0x8A, 0xF5, 0x0A, 0xF0]);
 }
@@ -19,5 +19,5 @@ test_requires_encode!();
 
 #[test]
 fn op_aa_1() {
-    test_asm_op!("jbc r5.15, 0014h", [0xAA, 0xF5, 0x0A, 0xF0]);
+    test_asm_op!("jbc r5.15, 0018h", [0xAA, 0xF5, 0x0A, 0xF0]);
 }
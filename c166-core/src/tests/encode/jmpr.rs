@@ -19,10 +19,10 @@ test_requires_encode!();
 
 #[test]
 fn op_0d() {
-    test_asm_op!("jmpr cc_UC, 0070h", [0x0D, 0x38]);
+    test_asm_op!("jmpr cc_UC, 0072h", [0x0D, 0x38]);
 }
 
 #[test]
 fn op_5d() {
-    test_asm_op!("jmpr cc_NV, 0070h", [0x5D, 0x38]);
+    test_asm_op!("jmpr cc_NV, 0072h", [0x5D, 0x38]);
 }
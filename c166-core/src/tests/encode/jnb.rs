@@ -18,6 +18,6 @@ test_requires_encode!();
 
 #[test]
 fn op_9a_1() {
-    test_asm_op!("jnb r2.15, 0002h", [0x9A, 0xF2, 0x01, 0xF0]); // Need to know CP
+    test_asm_op!("jnb r2.15, 0006h", [0x9A, 0xF2, 0x01, 0xF0]); // Need to know CP
 }
 
@@ -19,6 +19,6 @@ test_requires_encode!();
 
 #[test]
 fn op_ba_1() {
-    test_asm_op!("jnbs r2.15, 0002h", [0xBA, 0xF2, 0x01, 0xF0]); // Need to know CP
+    test_asm_op!("jnbs r2.15, 0006h", [0xBA, 0xF2, 0x01, 0xF0]); // Need to know CP
 }
 
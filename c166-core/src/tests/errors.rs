@@ -0,0 +1,167 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::convert::TryFrom;
+
+use ::cpu::CpuModel;
+use ::encoding::*;
+use ::error::*;
+use ::instruction::*;
+use ::parser::*;
+use ::reg::*;
+
+fn lut() -> OpLookUpTable<'static> {
+    let mut op_lut: OpLookUpTable = OpLookUpTable::new();
+    build_lut(&mut op_lut);
+    op_lut
+}
+
+fn assemble(line: &str) -> Result<Vec<u8>, EncodeError> {
+    let (_remainder, ops) = asm_lines(line).unwrap();
+    operation_to_bytes(&ops[0], &lut())
+}
+
+#[test]
+fn unknown_opcode() {
+    let err = Instruction::try_from(0x8B).unwrap_err();
+    assert_eq!(err, DecodeError::UnknownOpcode { opcode: 0x8B });
+}
+
+#[test]
+fn invalid_encoding_reports_offset() {
+    let bytes: &[u8] = &[0x87, 0x78, 0x07, 0x87];
+    let op = Instruction::try_from(bytes).unwrap();
+    let encoding = Encoding::from(&op.encoding);
+    match (encoding.decode)(&op, bytes) {
+        Err(DecodeError::InvalidEncoding { opcode: 0x87, encoding: EncodingType::NO_ARGS4, offset: 1, .. }) => {},
+        other => assert!(false, "Expected InvalidEncoding, got: {:?}", other)
+    }
+}
+
+#[test]
+fn reserved_nibble() {
+    let bytes: &[u8] = &[0xB1, 0x21];
+    let op = Instruction::try_from(bytes).unwrap();
+    let encoding = Encoding::from(&op.encoding);
+    match (encoding.decode)(&op, bytes) {
+        Err(DecodeError::InvalidEncoding { opcode: 0xB1, encoding: EncodingType::reg4_0, offset: 1, .. }) => {},
+        other => assert!(false, "Expected InvalidEncoding, got: {:?}", other)
+    }
+}
+
+#[test]
+fn unknown_mnemonic() {
+    assert_eq!(assemble("frob r1\0"), Err(EncodeError::UnknownMnemonic { mnemonic: "frob".to_string() }));
+}
+
+#[test]
+fn no_matching_encoding() {
+    assert_eq!(assemble("ret r1\0"), Err(EncodeError::NoMatchingEncoding { mnemonic: "ret".to_string() }));
+}
+
+#[test]
+fn irange_out_of_range() {
+    match assemble("atomic #0\0") {
+        Err(EncodeError::OutOfRange { operand: 1, value: 0, max: 4, .. }) => {},
+        other => assert!(false, "Expected OutOfRange, got: {:?}", other)
+    }
+}
+
+#[test]
+fn segment_out_of_range() {
+    match assemble("calls 100h, 1234h\0") {
+        Err(EncodeError::OutOfRange { opcode: 0xDA, operand: 1, value: 0x100, max: 0xFF, .. }) => {},
+        other => assert!(false, "Expected OutOfRange, got: {:?}", other)
+    }
+}
+
+#[test]
+fn page_out_of_range() {
+    match assemble("extp #400h, #1\0") {
+        Err(EncodeError::OutOfRange { opcode: 0xD7, operand: 1, value: 0x400, max: 0x3FF, .. }) => {},
+        other => assert!(false, "Expected OutOfRange, got: {:?}", other)
+    }
+}
+
+#[test]
+fn trap_out_of_range() {
+    match assemble("trap #80h\0") {
+        Err(EncodeError::OutOfRange { opcode: 0x9B, operand: 1, value: 0x80, max: 0x7F, .. }) => {},
+        other => assert!(false, "Expected OutOfRange, got: {:?}", other)
+    }
+}
+
+#[test]
+fn unreachable_target() {
+    let (_remainder, ops) = asm_lines("jmpr cc_UC, 0400h\0").unwrap();
+    let context = AsmContext { pc: 0x200, bank: None, segments: [None; 3] };
+    assert_eq!(operation_to_bytes_in_context(&ops[0], &lut(), CpuModel::C167CR, &context),
+        Err(EncodeError::UnreachableTarget { opcode: 0x0D, target: 0x0400, pc: 0x200 }));
+
+    // Targets are whole words
+    assert_eq!(assemble("jmpr cc_UC, 0011h\0"), Err(EncodeError::UnreachableTarget { opcode: 0x0D, target: 0x0011, pc: 0 }));
+
+    // Only calls and jmps leave pc's segment, 0000h in segment 2 isn't 0000h here
+    let other_segment = AsmContext { pc: 0, bank: None, segments: [None, Some(2), None] };
+    let (_remainder, ops) = asm_lines("jmpa cc_UC, 0000h\0").unwrap();
+    assert_eq!(operation_to_bytes_in_context(&ops[0], &lut(), CpuModel::C167CR, &other_segment),
+        Err(EncodeError::UnreachableTarget { opcode: 0xEA, target: 0x20000, pc: 0 }));
+    let (_remainder, ops) = asm_lines("jmpr cc_UC, 0000h\0").unwrap();
+    assert_eq!(operation_to_bytes_in_context(&ops[0], &lut(), CpuModel::C167CR, &other_segment),
+        Err(EncodeError::UnreachableTarget { opcode: 0x0D, target: 0x20000, pc: 0 }));
+}
+
+#[test]
+fn parse_bad_bit_position() {
+    assert!(asm_line("bset PSW.16\0").is_err());
+}
+
+#[test]
+fn parse_unknown_bit_address() {
+    assert!(asm_line("bset 1234h.1\0").is_err());
+}
+
+#[test]
+fn reg_errors() {
+    assert_eq!(Reg::PSW.to_reg4(), Err(RegError::NotAGpr(Reg::PSW)));
+    assert_eq!(Reg::from_reg4(0x10, &OperandType::WordRegister(0)), Err(RegError::InvalidNumber(0x10)));
+    assert_eq!(Reg::from_reg4(0x01, &OperandType::DirectMemory16), Err(RegError::WrongOperandType(OperandType::DirectMemory16)));
+    assert_eq!("r99".parse::<Reg>(), Err(RegError::UnknownName("r99".to_string())));
+}
+
+#[test]
+fn empty_input() {
+    let bytes: &[u8] = &[];
+    assert_eq!(Instruction::try_from(bytes).unwrap_err(), DecodeError::Incomplete { needed: 1 });
+}
+
+#[test]
+fn truncated_four_byte_op() {
+    // mov r1, #1234h cut off after the register byte
+    let bytes: &[u8] = &[0xE6, 0xF1];
+    let op = Instruction::try_from(bytes).unwrap();
+    let encoding = Encoding::from(&op.encoding);
+    assert_eq!((encoding.decode)(&op, bytes).unwrap_err(), DecodeError::Incomplete { needed: 2 });
+}
+
+#[test]
+fn truncated_two_byte_op() {
+    let bytes: &[u8] = &[0xCC];
+    let op = Instruction::try_from(bytes).unwrap();
+    let encoding = Encoding::from(&op.encoding);
+    assert_eq!((encoding.decode)(&op, bytes).unwrap_err(), DecodeError::Incomplete { needed: 1 });
+}
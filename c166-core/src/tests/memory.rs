@@ -0,0 +1,88 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use ::memory::*;
+use ::reg::Reg;
+
+#[test]
+fn reset_state() {
+    let model = MemoryModel::new();
+    assert_eq!(model.translate(0x1234), Some(0x1234));
+    assert_eq!(model.translate(0xFE00), Some(0xFE00));
+    assert_eq!(MemoryModel::dpp_number(0x8000), 2);
+}
+
+#[test]
+fn dpps() {
+    let mut model = MemoryModel::new();
+    model.set_dpp(1, Some(0x0123));
+    model.set_dpp(2, None);
+    assert_eq!(model.translate(0x4010), Some(0x48C010));
+    assert_eq!(model.translate(0x8010), None);
+
+    // Pages only have ten bits
+    model.set_dpp(1, Some(0xFC05));
+    assert_eq!(model.dpp[1], Some(0x0005));
+
+    // Without segmentation the page is cut down to A15/A14
+    model.segmented = false;
+    model.set_dpp(1, Some(0x0006));
+    assert_eq!(model.translate(0x4010), Some(0x8010));
+}
+
+#[test]
+fn overrides() {
+    let mut model = MemoryModel::new();
+    model.ext = Some(Override::Page(Some(0x0040)));
+    assert_eq!(model.translate(0xC010), Some(0x100010));
+
+    model.ext = Some(Override::Segment(Some(0x12)));
+    assert_eq!(model.translate(0xC010), Some(0x12C010));
+
+    model.ext = Some(Override::Page(None));
+    assert_eq!(model.translate(0xC010), None);
+}
+
+#[test]
+fn register_bank() {
+    let bank = RegisterBank::new(0xF600);
+    assert_eq!(bank.address(Reg::R0), Some(0xF600));
+    assert_eq!(bank.address(Reg::R15), Some(0xF61E));
+    assert_eq!(bank.address(Reg::RL3), Some(0xF606));
+    assert_eq!(bank.address(Reg::RH3), Some(0xF607));
+    assert_eq!(bank.address(Reg::DPP0), None);
+
+    assert_eq!(bank.register(0xF606, false), Some(Reg::R3));
+    assert_eq!(bank.register(0xF607, true), Some(Reg::RH3));
+    assert_eq!(bank.register(0xF61E, false), Some(Reg::R15));
+
+    // Only R0-R7 have byte halves and words have to be aligned
+    assert_eq!(bank.register(0xF610, true), None);
+    assert_eq!(bank.register(0xF607, false), None);
+    assert_eq!(bank.register(0xF620, false), None);
+    assert_eq!(bank.register(0xF5FE, false), None);
+
+    // CP is word aligned
+    assert_eq!(RegisterBank::new(0xFC01).address(Reg::R1), Some(0xFC02));
+
+    // The bank can't run past the top of the 64K
+    let high = RegisterBank::new(0xFFF0);
+    assert_eq!(high.address(Reg::R7), Some(0xFFFE));
+    assert_eq!(high.address(Reg::RH7), Some(0xFFFF));
+    assert_eq!(high.address(Reg::R8), None);
+    assert_eq!(high.address(Reg::R15), None);
+}
@@ -1,6 +1,19 @@
 #[macro_use]
 mod test_macros;
 
+pub mod assembler;
+pub mod cpu;
 pub mod decode;
+pub mod decoder;
+pub mod diagnostic;
+pub mod effects;
 pub mod encode;
+pub mod errors;
+pub mod isa;
+pub mod memory;
+pub mod opformat;
+pub mod opcodes;
 pub mod parser;
+pub mod reg;
+pub mod sfr;
+pub mod timing;
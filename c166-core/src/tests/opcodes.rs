@@ -0,0 +1,64 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::convert::TryFrom;
+
+use ::encoding::EncodingType;
+use ::instruction::*;
+use ::opcodes::*;
+
+#[test]
+fn table_matches_opcodes() {
+    for (opcode, entry) in OPCODES.iter().enumerate() {
+        if let Some(isn) = entry {
+            assert_eq!(isn.id as usize, opcode, "Opcode {:02X}h is filed under the wrong index", opcode);
+        }
+    }
+}
+
+#[test]
+fn table_size() {
+    assert_eq!(instructions().count(), 242);
+    assert!(lookup(0x8B).is_none());
+    assert_eq!(lookup(0xCC).unwrap().mnemonic, "nop");
+    assert_eq!(Instruction::try_from(0xCC).unwrap().mnemonic, "nop");
+}
+
+#[test]
+fn forms_of_mnemonic() {
+    let ids: Vec<u8> = forms("add").map(|isn| isn.id).collect();
+    assert_eq!(ids, vec![0x00, 0x02, 0x04, 0x06, 0x08]);
+
+    // Sub-op mnemonics share a table entry
+    let ids: Vec<u8> = forms("extpr").map(|isn| isn.id).collect();
+    assert_eq!(ids, vec![0xD7, 0xDC]);
+
+    assert_eq!(forms("frob").count(), 0);
+}
+
+#[test]
+fn opcodes_by_encoding() {
+    let ids: Vec<u8> = by_encoding(EncodingType::NO_ARGS4).map(|isn| isn.id).collect();
+    assert_eq!(ids, vec![0x87, 0x97, 0xA5, 0xA7, 0xB5, 0xB7]);
+}
+
+#[test]
+fn operand_signatures() {
+    assert_eq!(signatures("jmpr"), vec![[Some(OperandType::Condition), Some(OperandType::DirectRelative8S), None]]);
+
+    assert_eq!(signatures("ret"), vec![[None, None, None]]);
+}
@@ -0,0 +1,56 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use ::decoder::*;
+use ::opformat::*;
+
+#[test]
+fn default_is_r2() {
+    assert_eq!(FormatOptions::from_features(""), FormatOptions::default());
+    assert_eq!(FormatOptions::from_features("keil,r2"), FormatOptions::default());
+
+    // mov r1, #FF30h
+    let decoded = decode(&[0xE6, 0xF1, 0x30, 0xFF], 0).unwrap();
+    assert_eq!(decoded.format(&FormatOptions::default()), format!("{}", decoded));
+}
+
+#[test]
+fn presets() {
+    // mov r1, #FF30h
+    let decoded = decode(&[0xE6, 0xF1, 0x30, 0xFF], 0).unwrap();
+    assert_eq!(decoded.format(&FormatOptions::keil()), "MOV R1, #0FF30H");
+    assert_eq!(decoded.format(&FormatOptions::tasking()), "mov r1, #0FF30h");
+
+    // mov [r4 + #12h], r5
+    let decoded = decode(&[0xC4, 0x54, 0x12, 0x00], 0).unwrap();
+    assert_eq!(format!("{}", decoded), "mov [r4 + #12h], r5");
+    assert_eq!(decoded.format(&FormatOptions::keil()), "MOV [R4+#12H], R5");
+}
+
+#[test]
+fn features() {
+    // mov r1, #FF30h
+    let decoded = decode(&[0xE6, 0xF1, 0x30, 0xFF], 0).unwrap();
+    assert_eq!(decoded.format(&FormatOptions::from_features("0x")), "mov r1, #0xFF30");
+    assert_eq!(decoded.format(&FormatOptions::from_features("keil,0x")), "MOV R1, #0xFF30");
+    assert_eq!(decoded.format(&FormatOptions::from_features("nohash")), "mov r1, FF30h");
+
+    // jmpa cc_Z, 1234h
+    let decoded = decode(&[0xEA, 0x20, 0x34, 0x12], 0).unwrap();
+    assert_eq!(decoded.format(&FormatOptions::from_features("nocc")), "jmpa Z, 1234h");
+    assert_eq!(decoded.format(&FormatOptions::from_features("tasking,nocc")), "jmpa z, 1234h");
+}
@@ -73,16 +73,16 @@ fn decode_reg() {
     }
 }
 
-// Operand::Direct
+// Operand::Mem16
 #[test]
 fn decode_direct() {
     eprintln!("UGH");
     let op = asm_line("mov 12h\0").unwrap().1;
     match &op.operands[0] {
-        Operand::Direct(value, _width) => {
+        Operand::Mem16(value) => {
             assert_eq!(*value, 0x12)
         }
-        _ => assert!(false, "Expected Operand::Direct, got: {:?}", op.operands[0])
+        _ => assert!(false, "Expected Operand::Mem16, got: {:?}", op.operands[0])
     }
 }
 
@@ -0,0 +1,124 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::str::FromStr;
+
+use ::cpu::CpuModel;
+use ::error::*;
+use ::instruction::*;
+use ::memory::RegisterBank;
+use ::parser::*;
+use ::reg::*;
+
+fn assemble(line: &str) -> Result<Vec<u8>, EncodeError> {
+    let mut op_lut: OpLookUpTable = OpLookUpTable::new();
+    build_lut(&mut op_lut);
+
+    let (_remainder, ops) = asm_lines(line).unwrap();
+    operation_to_bytes_for_cpu(&ops[0], &op_lut, CpuModel::XC16x)
+}
+
+#[test]
+fn physical_addresses() {
+    assert_eq!(Reg::PWMCON0.to_phys16(), Ok(0xFF30));
+    assert_eq!(Reg::DPP0.to_phys16(), Ok(0xFE00));
+    assert_eq!(Reg::ADDAT2.to_phys16(), Ok(0xF0A0));
+    assert_eq!(Reg::XP3IC.to_phys16(), Ok(0xF19E));
+    assert_eq!(Reg::C1CSR.to_phys16(), Ok(0xEF00));
+    assert_eq!(Reg::SFR(0x12).to_phys16(), Ok(0xFE24));
+    assert_eq!(Reg::ESFR(0x12).to_phys16(), Ok(0xF024));
+
+    // GPRs move with CP and MAS is only reachable through CoSTORE
+    assert_eq!(Reg::R4.to_phys16(), Err(RegError::NoPhysicalAddress(Reg::R4)));
+    assert_eq!(Reg::RH7.to_phys16(), Err(RegError::NoPhysicalAddress(Reg::RH7)));
+    assert_eq!(Reg::MAS.to_phys16(), Err(RegError::NoPhysicalAddress(Reg::MAS)));
+
+    assert_eq!(Reg::from_phys16(0xFF30), Ok(Reg::PWMCON0));
+    assert_eq!(Reg::from_phys16(0xFEA0), Ok(Reg::ADDAT));
+    assert_eq!(Reg::from_phys16(0xF0A0), Ok(Reg::ADDAT2));
+    assert_eq!(Reg::from_phys16(0xEF0E), Ok(Reg::C1LMLM));
+    assert_eq!(Reg::from_phys16(0xFE24), Ok(Reg::SFR(0x12)));
+    assert_eq!(Reg::from_phys16(0xFF31), Err(RegError::UnknownAddress(0xFF31)));
+    assert_eq!(Reg::from_phys16(0xFFE0), Err(RegError::UnknownAddress(0xFFE0)));
+    assert_eq!(Reg::from_phys16(0x1234), Err(RegError::UnknownAddress(0x1234)));
+}
+
+#[test]
+fn physical_addresses_round_trip() {
+    for short in 0x00..0xF0 {
+        for &(base, reg_type) in [(0xFE00, OperandType::WordRegister(0)), (0xF000, OperandType::ExtendedRegister)].iter() {
+            let address = base + (short as u16 * 2);
+            let reg = Reg::from_reg8(short, &reg_type).unwrap();
+            assert_eq!(Reg::from_phys16(address), Ok(reg), "{:04X}h", address);
+            assert_eq!(reg.to_phys16(), Ok(address), "{}", reg);
+        }
+    }
+}
+
+#[test]
+fn esfr_names() {
+    assert_eq!(Reg::from_str("odp2"), Ok(Reg::ODP2));
+    assert_eq!(Reg::from_str("C1CSR"), Ok(Reg::C1CSR));
+    assert_eq!(format!("{}", Reg::ODP2), "ODP2");
+}
+
+#[test]
+fn sfr_small_immediates() {
+    // Only GPRs have the data3 and data4 forms, an SFR takes #data16 whatever the value
+    assert_eq!(assemble("mov T3, #1\0"), Ok(vec![0xE6, 0x21, 0x01, 0x00]));
+    assert_eq!(assemble("mov CC24, #0Eh\0"), Ok(vec![0xE6, 0x38, 0x0E, 0x00]));
+    assert_eq!(assemble("mov FE70h, #0Eh\0"), Ok(vec![0xE6, 0x38, 0x0E, 0x00]));
+    assert_eq!(assemble("add CC24, #1\0"), Ok(vec![0x06, 0x38, 0x01, 0x00]));
+    assert_eq!(assemble("mov MSW, #1\0"), Ok(vec![0xE6, 0xEF, 0x01, 0x00]));
+
+    assert_eq!(assemble("mov r1, #1\0"), Ok(vec![0xE0, 0x11]));
+    assert_eq!(assemble("add r1, #1\0"), Ok(vec![0x08, 0x11]));
+}
+
+#[test]
+fn registers_by_address() {
+    assert_eq!(assemble("mov FF30h, #0C03h\0"), assemble("mov PWMCON0, #0C03h\0"));
+    assert_eq!(assemble("mov r1, FF30h\0"), assemble("mov r1, PWMCON0\0"));
+    assert_eq!(assemble("mov PWMCON0, r1\0"), assemble("mov FF30h, r1\0"));
+    assert_eq!(assemble("push FE00h\0"), assemble("push DPP0\0"));
+    assert_eq!(assemble("mov FF30h, #0C03h\0"), Ok(vec![0xE6, 0x98, 0x03, 0x0C]));
+    assert_eq!(assemble("mov r1, PWMCON0\0"), Ok(vec![0xF2, 0xF1, 0x30, 0xFF]));
+
+    // ESFRs keep their long form, the short one needs an EXT*R
+    assert_eq!(assemble("mov F1C2h, r1\0"), assemble("mov ODP2, r1\0"));
+    assert_eq!(assemble("mov F1C2h, r1\0"), Ok(vec![0xF6, 0xF1, 0xC2, 0xF1]));
+}
+
+#[test]
+fn register_bank_addresses() {
+    let mut op_lut: OpLookUpTable = OpLookUpTable::new();
+    build_lut(&mut op_lut);
+    let context = AsmContext { pc: 0, bank: Some(RegisterBank::new(0xF600)), segments: [None; 3] };
+
+    let assemble_in_bank = |line: &str| {
+        let (_remainder, ops) = asm_lines(line).unwrap();
+        operation_to_bytes_in_context(&ops[0], &op_lut, CpuModel::XC16x, &context)
+    };
+
+    // There's no mov mem, #data so the address only assembles as the GPR
+    assert!(assemble("mov F606h, #0005h\0").is_err());
+    assert_eq!(assemble_in_bank("mov F606h, #0005h\0"), assemble("mov r3, #0005h\0"));
+    assert_eq!(assemble_in_bank("movb F607h, rl0\0"), assemble("movb rh3, rl0\0"));
+
+    // Outside the bank addresses are left alone
+    assert_eq!(assemble_in_bank("mov r1, F640h\0"), assemble("mov r1, F640h\0"));
+}
@@ -0,0 +1,216 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::str::FromStr;
+
+use ::cpu::CpuModel;
+use ::decoder::*;
+use ::error::SfrError;
+use ::reg::Reg;
+use ::sfr::*;
+
+const CUSTOM: &str = r#"
+device = "custom"
+extends = "c167cr"
+
+[[register]]
+name = "custom0"
+address = 0xFE24
+reset = 0x8000
+access = "r"
+fields = [{ name = "ready", bit = 15 }, { name = "count", bit = 0, width = 8 }]
+"#;
+
+#[test]
+fn every_cpu_has_a_description() {
+    for cpu in CpuModel::ALL.iter() {
+        assert_eq!(cpu.registers().device, cpu.device());
+    }
+
+    assert_eq!(RegisterMap::builtins().count(), 4);
+}
+
+#[test]
+fn c167cr() {
+    let map = RegisterMap::builtin("C167CR").unwrap();
+
+    let psw = map.by_name("psw").unwrap();
+    assert_eq!(psw.address, 0xFF10);
+    assert_eq!(psw.reset, Some(0x0000));
+    assert_eq!(psw.access, Access::ReadWrite);
+    assert!(psw.is_bit_addressable());
+    assert_eq!(psw.field("IEN"), Some(&BitField { name: "IEN".to_string(), bit: 11, width: 1 }));
+    assert_eq!(psw.field("ILVL").map(|field| field.width), Some(4));
+
+    assert_eq!(map.by_address(0xF1C2).map(|info| info.name.as_str()), Some("ODP2"));
+    assert_eq!(map.by_short_address(0xE1, true).map(|info| info.name.as_str()), Some("ODP2"));
+    assert_eq!(map.by_name("ZEROS").map(|info| info.access), Some(Access::ReadOnly));
+    assert_eq!(map.by_name("SYSCON").and_then(|info| info.reset), None);
+    assert!(map.by_name("MSW").is_none());
+}
+
+#[test]
+fn mac_parts_extend_the_c167cr() {
+    for device in ["st10", "xc16x"].iter() {
+        let map = RegisterMap::builtin(device).unwrap();
+        assert_eq!(map.by_address(0xFFDE).map(|info| info.name.as_str()), Some("MSW"));
+        assert!(map.by_name("PSW").is_some());
+    }
+}
+
+#[test]
+fn xc16x_core() {
+    let map = RegisterMap::builtin("xc16x").unwrap();
+    assert!(map.by_name("BUSCON0").is_none());
+    assert!(map.by_name("ADDRSEL1").is_none());
+    assert_eq!(map.by_address(0xFE18).map(|info| info.name.as_str()), Some("CPUCON1"));
+    assert_eq!(map.by_address(0xFF12).map(|info| info.name.as_str()), Some("VECSEG"));
+
+    // mov SYSCON, #0C03h
+    let bytes: &[u8] = &[0xE6, 0x89, 0x03, 0x0C];
+    assert_eq!(format!("{}", decode_for_cpu(bytes, 0, CpuModel::XC16x).unwrap()), "mov VECSEG, #0C03h");
+    assert_eq!(format!("{}", decode_for_cpu(bytes, 0, CpuModel::ST10).unwrap()), "mov SYSCON, #0C03h");
+    assert!(!CpuModel::XC16x.has_reg(&Reg::SYSCON));
+}
+
+#[test]
+fn descriptions_agree_with_reg() {
+    // The enum has the C167CR's registers, other parts' are found through their own map
+    for info in RegisterMap::builtin("c167cr").unwrap().registers.iter() {
+        assert_eq!(Reg::from_phys16(info.address).map(|reg| reg.to_string()), Ok(info.name.clone()));
+    }
+
+    for map in RegisterMap::builtins() {
+        for info in map.registers.iter() {
+            let reg = Reg::from_str(&info.name).ok().and_then(|reg| reg.named_by(map)).unwrap();
+            assert_eq!(reg.to_reg8(), Ok(info.short_address()), "{}", info.name);
+            assert_eq!(reg.is_extended(), info.is_extended(), "{}", info.name);
+            assert_eq!(reg.to_phys16(), Ok(info.address), "{}", info.name);
+            assert_eq!(Reg::from_address(info.address, map).map(|reg| reg.to_string()), Ok(info.name.clone()));
+        }
+    }
+}
+
+#[test]
+fn runtime_description() {
+    let map: &'static RegisterMap = Box::leak(Box::new(RegisterMap::from_toml(CUSTOM).unwrap()));
+
+    let custom = map.by_name("CUSTOM0").unwrap();
+    assert_eq!(custom.access, Access::ReadOnly);
+    assert_eq!(custom.fields[0], BitField { name: "READY".to_string(), bit: 15, width: 1 });
+    assert!(map.by_name("PSW").is_some());
+
+    // mov FE24h, #0C03h
+    let bytes: &[u8] = &[0xE6, 0x12, 0x03, 0x0C];
+    assert_eq!(format!("{}", decode(bytes, 0).unwrap()), "mov FE24h, #0C03h");
+    assert_eq!(format!("{}", decode_with_registers(bytes, 0, CpuModel::C167CR, map).unwrap()), "mov CUSTOM0, #0C03h");
+}
+
+#[test]
+fn descriptions_name_every_sfr() {
+    let map: &'static RegisterMap = Box::leak(Box::new(RegisterMap::from_toml(r#"
+        device = "renamed"
+
+        [[register]]
+        name = "SYSCTL"
+        address = 0xFF12
+        fields = [{ name = "halt", bit = 0 }]
+    "#).unwrap()));
+
+    // mov SYSCON, #0C03h
+    let bytes: &[u8] = &[0xE6, 0x89, 0x03, 0x0C];
+    assert_eq!(format!("{}", decode_with_registers(bytes, 0, CpuModel::C167CR, map).unwrap()), "mov SYSCTL, #0C03h");
+
+    // mov PWMCON0, #0001h, which the map doesn't describe
+    let bytes: &[u8] = &[0xE6, 0x98, 0x01, 0x00];
+    assert_eq!(format!("{}", decode_with_registers(bytes, 0, CpuModel::C167CR, map).unwrap()), "mov FF30h, #0001h");
+
+    // bset SYSCON.0
+    let bytes: &[u8] = &[0x0F, 0x89];
+    assert_eq!(format!("{}", decode_with_registers(bytes, 0, CpuModel::C167CR, map).unwrap()), "bset HALT");
+    let bytes: &[u8] = &[0x1F, 0x89];
+    assert_eq!(format!("{}", decode_with_registers(bytes, 0, CpuModel::C167CR, map).unwrap()), "bset SYSCTL.1");
+}
+
+#[test]
+fn invalid_descriptions() {
+    let duplicate = "device = \"x\"\n[[register]]\nname = \"A\"\naddress = 0xFE00\n[[register]]\nname = \"B\"\naddress = 0xFE00\n";
+    assert_eq!(RegisterMap::from_toml(duplicate), Err(SfrError::Duplicate { register: "B".to_string() }));
+
+    let odd = "device = \"x\"\n[[register]]\nname = \"A\"\naddress = 0xFE01\n";
+    match RegisterMap::from_toml(odd) {
+        Err(SfrError::InvalidEntry { key: "address", .. }) => {},
+        other => assert!(false, "Expected an invalid address, got: {:?}", other)
+    }
+
+    let wide = "device = \"x\"\n[[register]]\nname = \"A\"\naddress = 0xFE00\nwidth = 8\nfields = [{ name = \"F\", bit = 6, width = 4 }]\n";
+    match RegisterMap::from_toml(wide) {
+        Err(SfrError::InvalidEntry { key: "fields", .. }) => {},
+        other => assert!(false, "Expected an invalid field, got: {:?}", other)
+    }
+
+    let stray = "device = \"x\"\nextends = \"c167cr\"\nomit = [\"MSW\"]\n";
+    match RegisterMap::from_toml(stray) {
+        Err(SfrError::InvalidEntry { key: "omit", .. }) => {},
+        other => assert!(false, "Expected an invalid omit, got: {:?}", other)
+    }
+
+    let orphan = "device = \"x\"\nextends = \"c168\"\n";
+    assert_eq!(RegisterMap::from_toml(orphan), Err(SfrError::UnknownDevice("c168".to_string())));
+
+    match RegisterMap::from_toml("device = ") {
+        Err(SfrError::Syntax(_)) => {},
+        other => assert!(false, "Expected a syntax error, got: {:?}", other)
+    }
+}
+
+#[test]
+fn bit_names() {
+    let map = RegisterMap::builtin("c167cr").unwrap();
+    let (psw, ien) = map.by_bit_name("ien").unwrap();
+    assert_eq!((psw.name.as_str(), ien.bit), ("PSW", 11));
+    assert_eq!(psw.bit_offset(), Some(0x88));
+    assert_eq!(map.bit_name(0xFF10, 11), Some("IEN".to_string()));
+    assert_eq!(map.bit_name(0xFF10, 7), None);
+
+    // Multi-bit fields aren't bits, and nothing outside the bit-addressable area is either
+    assert!(map.by_bit_name("ILVL").is_none());
+    assert_eq!(map.by_name("DPP0").unwrap().bit_offset(), None);
+
+    assert_eq!(psw.describe(0xF800), vec!["IEN".to_string(), "ILVL = 15".to_string()]);
+    assert!(psw.describe(0x0000).is_empty());
+}
+
+#[test]
+fn ambiguous_bit_names() {
+    let map = RegisterMap::from_toml(r#"
+        device = "twins"
+
+        [[register]]
+        name = "LEFT"
+        address = 0xFF80
+        fields = [{ name = "GO", bit = 0 }]
+
+        [[register]]
+        name = "RIGHT"
+        address = 0xFF82
+        fields = [{ name = "GO", bit = 0 }]
+    "#).unwrap();
+
+    assert!(map.by_bit_name("GO").is_none());
+    assert_eq!(map.bit_name(0xFF82, 0), Some("RIGHT.GO".to_string()));
+}
@@ -1,9 +1,6 @@
 macro_rules! test_requires_decode {
     () => {
-        use std::convert::TryFrom;
-        use ::instruction::*;
-        use ::encoding::Encoding;
-        use opformat::*;
+        use ::decoder::*;
     };
 }
 
@@ -17,12 +14,15 @@ macro_rules! test_disasm_op {
     ( $input:expr, $expected:expr ) => {
 //        eprintln!("crash avoidance");
         let bytes: &[u8] = &$input;
-        let op: Instruction = Instruction::try_from(bytes).unwrap();
-        let encoding: Encoding = Encoding::from(&op.encoding);
-        let values = (encoding.decode)(&op, &bytes).expect("Instruction was invalid");
-        let desc: String = format_op(&op, &values, 0x0000 as u32);
+        let decoded: DecodedInstruction = decode(bytes, 0x0000).expect("Instruction was invalid");
 
-        assert_eq!(desc, $expected);
+        assert_eq!(format!("{}", decoded), $expected);
+    };
+    ( $cpu:expr, $input:expr, $expected:expr ) => {
+        let bytes: &[u8] = &$input;
+        let decoded: DecodedInstruction = decode_for_cpu(bytes, 0x0000, $cpu).expect("Instruction was invalid");
+
+        assert_eq!(format!("{}", decoded), $expected);
     };
 }
 
@@ -30,10 +30,8 @@ macro_rules! test_disasm_op_failure {
     ( $input:expr ) => {
 //        eprintln!("crash avoidance");
         let bytes: &[u8] = &$input;
-        let op: Instruction = Instruction::try_from(bytes).unwrap();
-        let encoding: Encoding = Encoding::from(&op.encoding);
-        let values = (encoding.decode)(&op, &bytes);
-        assert!(values.is_err());
+        let decoded = decode(bytes, 0x0000);
+        assert!(decoded.is_err());
     };
 }
 
@@ -41,10 +39,8 @@ macro_rules! test_disasm_op_no_panic {
     ( $input:expr ) => {
 //        eprintln!("crash avoidance");
         let bytes: &[u8] = &$input;
-        let op: Instruction = Instruction::try_from(bytes).unwrap();
-        let encoding: Encoding = Encoding::from(&op.encoding);
-        let values = (encoding.decode)(&op, &bytes);
-        assert!(values.is_err() || values.is_ok());
+        let decoded = decode(bytes, 0x0000);
+        assert!(decoded.is_err() || decoded.is_ok());
     };
 }
 
@@ -63,4 +59,17 @@ macro_rules! test_asm_op {
         let expected_bytes: &[u8] = &$expected;
         assert_eq!(asm_bytes, expected_bytes);
     };
+    ( $cpu:expr, $input:expr, $expected:expr ) => {
+        let data: &str = concat!($input, "\0");
+        let (remainder, asm_ops) = asm_lines(data).unwrap();
+        assert_eq!(remainder.len(), 0, "There should be zero bytes remaining from the parser");
+        assert_eq!(asm_ops.len(), 1, "There should be exactly one operation");
+
+        let mut op_lut: OpLookUpTable = OpLookUpTable::new();
+        build_lut(&mut op_lut);
+
+        let asm_bytes: Vec<u8> = operation_to_bytes_for_cpu(&asm_ops[0], &op_lut, $cpu).expect("The operation was unable to be encoded");
+        let expected_bytes: &[u8] = &$expected;
+        assert_eq!(asm_bytes, expected_bytes);
+    };
 }
@@ -0,0 +1,83 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use ::cpu::CpuModel;
+use ::decoder::*;
+use ::timing::*;
+
+fn times(bytes: &[u8]) -> (u32, u32) {
+    let times = decode(bytes, 0).unwrap().state_times(CpuModel::C167CR).unwrap();
+    (times.states, times.taken)
+}
+
+#[test]
+fn state_times() {
+    // add r1, r2 and add r1, #1234h
+    assert_eq!(times(&[0x00, 0x12]), (2, 2));
+    assert_eq!(times(&[0x06, 0xF1, 0x34, 0x12]), (4, 4));
+
+    // mul r1, r2 and div r2
+    assert_eq!(times(&[0x0B, 0x12]), (10, 10));
+    assert_eq!(times(&[0x4B, 0x22]), (20, 20));
+
+    // mov r1, [r2+#0004h]
+    assert_eq!(times(&[0xD4, 0x12, 0x04, 0x00]), (6, 6));
+}
+
+#[test]
+fn branches() {
+    // jmpr cc_Z only pays for the refill when it's taken, jmpr cc_UC always does
+    assert_eq!(times(&[0x2D, 0x04]), (2, 4));
+    assert_eq!(times(&[0x0D, 0x04]), (4, 4));
+
+    // jb and ret
+    assert_eq!(times(&[0x8A, 0x01, 0x04, 0x00]), (4, 6));
+    assert_eq!(times(&[0xCB, 0x00]), (4, 4));
+
+    // The XC16x isn't modelled
+    assert_eq!(decode(&[0x00, 0x12], 0).unwrap().state_times(CpuModel::XC16x), None);
+}
+
+#[test]
+fn bus_cycles() {
+    // 16-bit demultiplexed, no wait states
+    let bus = BusCycle::from_buscon(0x00AF);
+    assert_eq!(bus, BusCycle { wait_states: 0, tristate_wait: false, ale_lengthened: false, multiplexed: false, width: 16 });
+    assert_eq!(bus.states(), 2);
+
+    // 8-bit multiplexed with a wait state, a tristate wait state and a long ALE
+    let bus = BusCycle::from_buscon(0x024E);
+    assert_eq!(bus.states(), 6);
+    assert_eq!(bus.access_states(1), 6);
+    assert_eq!(bus.access_states(2), 12);
+}
+
+#[test]
+fn external_accesses() {
+    // 16-bit multiplexed, no wait states
+    let bus = BusCycle::from_buscon(0x00EF);
+
+    // mov r1, 1234h fetches two words and reads one
+    let decoded = decode(&[0xF2, 0xF1, 0x34, 0x12], 0).unwrap();
+    assert_eq!(bus_states(&decoded, &bus, true, false), 2);
+    assert_eq!(bus_states(&decoded, &bus, false, true), 3);
+    assert_eq!(bus_states(&decoded, &bus, true, true), 5);
+
+    // The system stack is internal
+    let decoded = decode(&[0xEC, 0xF1], 0).unwrap();
+    assert_eq!(bus_states(&decoded, &bus, false, true), 0);
+}
@@ -0,0 +1,142 @@
+/*
+    This file is part of r2-c166.
+
+    r2-c166 is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    r2-c166 is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with r2-c166.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+// Instruction state times as the C167 user's manual gives them.  One state is one CPU clock
+// (2 TCL), 50ns at 20MHz.  The minimum times assume code in internal ROM and data in internal
+// RAM or the SFRs, external accesses are added on top from the BUSCON settings.
+
+use ::cpu::CpuModel;
+use ::decoder::{DecodedInstruction, FlowKind};
+use ::effects::Address;
+use ::instruction::{OpCondition, Operand};
+
+// Refilling the pipeline after a taken branch
+const BRANCH_STATES: u32 = 2;
+// [Rw+#data16] computes the address before the access
+const OFFSET_STATES: u32 = 2;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StateTimes {
+    // Execution continuing with the next instruction
+    pub states: u32,
+    // A branch going to its target, the jump cache isn't modelled so this is the miss time
+    pub taken: u32,
+}
+
+// One external bus cycle as a BUSCONx register sets it up
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BusCycle {
+    pub wait_states: u8,
+    // MTTC clear
+    pub tristate_wait: bool,
+    // ALECTL set
+    pub ale_lengthened: bool,
+    pub multiplexed: bool,
+    pub width: u8,
+}
+
+impl BusCycle {
+    pub fn from_buscon(buscon: u16) -> BusCycle {
+        BusCycle {
+            // MCTC holds the wait states inverted
+            wait_states: 15 - (buscon & 0x0F) as u8,
+            tristate_wait: buscon & (1 << 5) == 0,
+            ale_lengthened: buscon & (1 << 9) != 0,
+            multiplexed: buscon & (1 << 6) != 0,
+            width: match buscon & (1 << 7) {
+                0 => 8,
+                _ => 16
+            },
+        }
+    }
+
+    // A demultiplexed cycle takes two states and a multiplexed one three, before waiting
+    pub fn states(&self) -> u32 {
+        let base: u32 = match self.multiplexed {
+            true => 3,
+            false => 2
+        };
+
+        base + self.wait_states as u32 + self.tristate_wait as u32 + self.ale_lengthened as u32
+    }
+
+    // Moving width bytes, a word takes two cycles on an 8-bit bus
+    pub fn access_states(&self, width: u8) -> u32 {
+        match width > 1 && self.width == 8 {
+            true => 2 * self.states(),
+            false => self.states()
+        }
+    }
+}
+
+// Everything but the C166S V2 core of the XC16x shares the same state times, the V2's
+// pipeline and branch prediction would need a model of their own
+pub fn state_times(decoded: &DecodedInstruction, cpu: CpuModel) -> Option<StateTimes> {
+    if cpu == CpuModel::XC16x {
+        return None;
+    }
+
+    // Two states per instruction word
+    let mut states = decoded.length as u32;
+
+    match decoded.mnemonic.as_str() {
+        "mul" | "mulu" => states = 10,
+        "div" | "divu" | "divl" | "divlu" => states = 20,
+        _ => {}
+    }
+
+    let offset = decoded.operands.iter().any(|operand| match operand {
+        Operand::IndirectAndImmediate(_, _) => true,
+        _ => false
+    });
+    if offset {
+        states += OFFSET_STATES;
+    }
+
+    let conditional = match decoded.arguments.op1 {
+        Some(Operand::Condition(OpCondition::cc_UC)) => false,
+        Some(Operand::Condition(_)) => true,
+        _ => decoded.flow == FlowKind::ConditionalJump
+    };
+
+    match decoded.flow {
+        FlowKind::Fallthrough => Some(StateTimes { states: states, taken: states }),
+        _ if conditional => Some(StateTimes { states: states, taken: states + BRANCH_STATES }),
+        _ => Some(StateTimes { states: states + BRANCH_STATES, taken: states + BRANCH_STATES })
+    }
+}
+
+// States added by running from external memory (code) and by operands in external memory (data).
+// The system stack always lives in internal RAM.
+pub fn bus_states(decoded: &DecodedInstruction, bus: &BusCycle, code: bool, data: bool) -> u32 {
+    let mut states = 0;
+
+    // Internal ROM delivers a word every two states
+    if code {
+        let words = (decoded.length / 2) as u32;
+        states += words * (bus.access_states(2) - 2);
+    }
+
+    if data {
+        states += decoded.effects().memory.iter()
+            .filter(|access| access.address != Address::Stack)
+            .map(|access| bus.access_states(access.width))
+            .sum::<u32>();
+    }
+
+    states
+}